@@ -17,30 +17,43 @@
 //!     │       └── Compile all pages again → Write HTML files
 //!     │           (Virtual JSON now returns complete data)
 //!     │
-//!     └── process_assets() ──► Copy/process asset files
+//!     ├── process_assets() ──► Copy/process asset files
+//!     │
+//!     ├── build_taxonomy_pages() ──► `/tags/<slug>/`, `/sections/<slug>/`, `/authors/<slug>/`
+//!     │
+//!     ├── build_aliases() ──► Redirect stubs at former page URLs
+//!     │
+//!     └── copy_extra_files() ──► Copy `[build].copy` entries
 //! ```
 
 use crate::{
-    compiler::meta::Pages,
+    compiler::meta::{Pages, is_companion, is_dir_defaults},
     compiler::{
-        collect_all_files, collect_metadata, compile_pages_with_data, process_asset,
-        process_rel_asset,
+        assets::{copy_extra_files, is_asset_selected},
+        collect_all_files, collect_metadata, collect_page_files, compile_pages_with_data,
+        manifest::{CACHE_DIR, MANIFEST, Manifest},
+        pages::process_page,
+        process_asset, process_rel_asset,
+        taxonomy::build_taxonomy_pages,
     },
-    config::SiteConfig,
-    data::virtual_fs,
+    config::{LastmodSource, SiteConfig},
+    data::{GLOBAL_SITE_DATA, virtual_fs},
+    generator::aliases::build_aliases,
     log,
     logger::ProgressBars,
+    report::{BUILD_STATS, Stage},
     typst_lib,
     utils::{category::get_deps_mtime, css, git},
 };
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use gix::ThreadSafeRepository;
 use rayon::prelude::*;
 use std::{
     ffi::OsStr,
-    fs,
-    path::Path,
+    fs, io,
+    path::{Path, PathBuf},
     sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
 };
 
 /// Build the entire site, processing content and assets in parallel.
@@ -58,10 +71,32 @@ use std::{
 pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeRepository, Pages)> {
     let output = &config.build.output;
     let assets = &config.build.assets;
+    BUILD_STATS.reset();
+    warn_config(config);
+
+    if let Some(msg) = check_content_dir(&config.build.content)? {
+        // Fail before `--clean` clears a previous good output
+        if config.build.fail_on_empty {
+            bail!("{msg} (`fail_on_empty` is set)");
+        }
+        log!("warn"; "{msg}");
+        BUILD_STATS.warn(msg);
+    }
+
+    // Project commit info for `/_data/git.json`
+    GLOBAL_SITE_DATA.set_git(git::read_git_info(config.get_root()).as_ref());
+    if config.build.sitemap.lastmod == LastmodSource::Git {
+        git::load_commit_times(config.get_root());
+    }
 
     // Pre-warm typst library resources if using lib mode
     if config.build.typst.use_lib {
-        typst_lib::warmup_with_root(config.get_root());
+        warmup_typst(config);
+    }
+
+    // Content hashes from the previous `--incremental` build
+    if config.build.incremental {
+        *MANIFEST.write() = Manifest::load(config);
     }
 
     // Ensure output directory has git repo (for deploy)
@@ -70,12 +105,21 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
     // Calculate deps mtime once for all content files
     let deps_mtime = get_deps_mtime(config);
 
-    // Collect asset files early for progress bar
-    let asset_files = collect_all_files(assets);
-    let content_asset_files: Vec<_> = collect_all_files(&config.build.content)
-        .into_iter()
-        .filter(|p| p.extension().is_none_or(|ext| ext != "typ"))
-        .collect();
+    // Collect asset files early for progress bar (none under `--only`)
+    let (asset_files, content_asset_files) = if config.build.only.is_some() {
+        (Vec::new(), Vec::new())
+    } else {
+        let content_assets = collect_all_files(&config.build.content)
+            .into_iter()
+            .filter(|p| p.extension().is_none_or(|ext| ext != "typ"))
+            .filter(|p| !is_companion(p) && !is_dir_defaults(p))
+            .collect();
+        let assets = collect_all_files(assets)
+            .into_iter()
+            .filter(|p| is_asset_selected(p, config))
+            .collect();
+        (assets, content_assets)
+    };
 
     // ========================================================================
     // Collect metadata from all pages
@@ -84,11 +128,8 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
     // Metadata extraction is static and unaffected.
     // HTML output is discarded (incomplete due to empty JSON).
 
-    // First, count .typ files for progress bar
-    let typ_file_count = collect_all_files(&config.build.content)
-        .into_iter()
-        .filter(|p| p.extension().is_some_and(|ext| ext == "typ"))
-        .count();
+    // First, count pages for progress bar
+    let typ_file_count = collect_page_files(config).len();
 
     if !quiet {
         log!("metadata"; "collecting...");
@@ -98,16 +139,21 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
     } else {
         Some(ProgressBars::new(&[("metadata", typ_file_count)]))
     };
-    let page_paths = collect_metadata(config, || {
+    let metadata_start = Instant::now();
+    let pages_meta = collect_metadata(config, || {
         if let Some(ref p) = metadata_progress {
             p.inc_by_name("metadata");
         }
     })?;
+    BUILD_STATS.record(Stage::Metadata, metadata_start.elapsed());
     if let Some(p) = metadata_progress {
         p.finish();
     }
     if !quiet {
-        log!("metadata"; "found {} pages", page_paths.len());
+        log!("metadata"; "found {} pages", pages_meta.len());
+    }
+    if pages_meta.is_empty() && config.build.fail_on_empty {
+        bail!("no pages to build, all filtered out (`fail_on_empty` is set)");
     }
 
     // Create progress bars for Phase 2
@@ -115,13 +161,14 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
         None
     } else {
         Some(ProgressBars::new(&[
-            ("content", page_paths.len()),
+            ("content", pages_meta.len()),
             ("assets", asset_files.len() + content_asset_files.len()),
         ]))
     };
 
     let has_error = AtomicBool::new(false);
-    let clean = config.build.clean;
+    // `--force-rebuild` rewrites everything like `--clean`, but in place
+    let clean = config.build.clean || config.build.force_rebuild;
 
     // ========================================================================
     // Compile pages with complete data + Process assets
@@ -135,11 +182,14 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
     let (compile_result, assets_result) = rayon::join(
         || {
             // Compile all pages with complete data
-            match compile_pages_with_data(&page_paths, config, clean, deps_mtime, || {
+            let compile_start = Instant::now();
+            let result = compile_pages_with_data(pages_meta, config, clean, deps_mtime, || {
                 if let Some(ref p) = progress {
                     p.inc_by_name("content");
                 }
-            }) {
+            });
+            BUILD_STATS.record(Stage::Compile, compile_start.elapsed());
+            match result {
                 Ok(pages) => Ok(pages),
                 Err(e) => {
                     if !has_error.swap(true, Ordering::Relaxed) {
@@ -165,6 +215,7 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
                     if let Some(ref p) = progress {
                         p.inc_by_name("assets");
                     }
+                    BUILD_STATS.inc_assets();
                     Ok(())
                 })
             };
@@ -184,11 +235,15 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
                     if let Some(ref p) = progress {
                         p.inc_by_name("assets");
                     }
+                    BUILD_STATS.inc_assets();
                     Ok(())
                 })
             };
 
-            rayon::join(process_assets, process_content_assets)
+            let assets_start = Instant::now();
+            let result = rayon::join(process_assets, process_content_assets);
+            BUILD_STATS.record(Stage::Assets, assets_start.elapsed());
+            result
         },
     );
 
@@ -200,16 +255,33 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
     let (assets_res, content_assets_res) = assets_result;
     assets_res?;
     content_assets_res?;
+    copy_extra_files(config, clean)?;
 
     // Write virtual data files to disk for external tools
     // Use output_dir() to place _data inside the site content directory (with path_prefix)
     virtual_fs::write_to_disk(&config.paths().output_dir().join(&config.build.data))?;
 
+    // Render per-tag/per-section index pages from the complete data
+    build_taxonomy_pages(config, quiet)?;
+
+    // Redirect former page URLs to their current location
+    build_aliases(config, &pages)?;
+
     // Build Tailwind CSS if enabled
     if config.build.css.tailwind.enable {
         crate::compiler::assets::rebuild_tailwind(config, quiet)?;
     }
 
+    // Compile Sass/SCSS if enabled
+    if config.build.css.sass.enable {
+        crate::compiler::assets::rebuild_sass(config, quiet)?;
+    }
+
+    // Post-process produced stylesheets with PostCSS
+    if config.build.css.postcss.enable {
+        crate::compiler::assets::run_postcss(config, quiet)?;
+    }
+
     // Generate auto-enhance CSS if enabled
     if config.build.css.auto_enhance {
         let enhance_output_dir = config.paths().output_dir();
@@ -217,13 +289,195 @@ pub fn build_site(config: &SiteConfig, quiet: bool) -> Result<(ThreadSafeReposit
         css::generate_enhance_css(&enhance_output_dir)?;
     }
 
+    if config.build.incremental {
+        MANIFEST.write().save(config)?;
+    }
+
     if !quiet {
+        if config.build.verbose {
+            log_slowest_pages();
+        }
         log_build_result(output)?;
     }
 
     Ok((repo, pages))
 }
 
+/// Run `build` against a staging output directory and swap it into place
+/// only if it succeeds (`[build].atomic`).
+///
+/// The staging directory `<output>.tmp-<pid>` starts out holding only the
+/// previous output's `.git`. On failure it is removed and the previous output
+/// is left untouched.
+pub fn build_atomic<T>(
+    config: &SiteConfig,
+    build: impl FnOnce(&SiteConfig) -> Result<T>,
+) -> Result<T> {
+    let output = &config.build.output;
+    let staging = sibling_dir(output, "tmp");
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+    let git_dir = output.join(".git");
+    if git_dir.is_dir() {
+        copy_dir_all(&git_dir, &staging.join(".git"))?;
+    }
+
+    let mut staged = config.clone();
+    staged.build.output = staging.clone();
+    let result = build(&staged).and_then(|value| {
+        replace_dir(&staging, output)?;
+        Ok(value)
+    });
+    if result.is_err() && staging.exists() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// `<dir>.<tag>-<pid>`, next to `dir` so renames stay on one filesystem.
+fn sibling_dir(dir: &Path, tag: &str) -> PathBuf {
+    let name = dir.file_name().unwrap_or_default().to_string_lossy();
+    dir.with_file_name(format!("{name}.{tag}-{}", std::process::id()))
+}
+
+/// Move `from` to `to`, replacing whatever `to` held.
+///
+/// The old `to` is renamed aside first and restored if the move fails.
+/// Falls back to copying when the rename crosses filesystems.
+fn replace_dir(from: &Path, to: &Path) -> Result<()> {
+    let backup = sibling_dir(to, "old");
+    let had_old = to.exists();
+    if had_old {
+        fs::rename(to, &backup)
+            .with_context(|| format!("Failed to move aside {}", to.display()))?;
+    }
+
+    let moved = match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            copy_dir_all(from, to).and_then(|()| Ok(fs::remove_dir_all(from)?))
+        }
+        result => result.map_err(Into::into),
+    };
+    if let Err(e) = moved {
+        if to.exists() {
+            let _ = fs::remove_dir_all(to);
+        }
+        if had_old {
+            fs::rename(&backup, to)?;
+        }
+        return Err(e.context(format!("Failed to replace {}", to.display())));
+    }
+
+    if had_old {
+        fs::remove_dir_all(&backup)?;
+    }
+    Ok(())
+}
+
+/// Recursively copy the directory `from` to `to`.
+fn copy_dir_all(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Build a single content file, for `tola build <FILE>`.
+///
+/// Metadata is still collected from every page so the file sees complete
+/// `/_data`, but only its HTML is written; assets and rss/sitemap are skipped.
+/// Returns the written HTML path.
+pub fn build_file(file: &Path, config: &SiteConfig) -> Result<PathBuf> {
+    BUILD_STATS.reset();
+    warn_config(config);
+    check_content_dir(&config.build.content)?;
+    let path = file
+        .canonicalize()
+        .with_context(|| format!("content file not found: {}", file.display()))?;
+
+    GLOBAL_SITE_DATA.set_git(git::read_git_info(config.get_root()).as_ref());
+    if config.build.sitemap.lastmod == LastmodSource::Git {
+        git::load_commit_times(config.get_root());
+    }
+    // The page itself is always compiled in-process
+    warmup_typst(config);
+
+    collect_metadata(config, || {})?;
+    let page = process_page(&path, config, true, None, false)?
+        .ok_or_else(|| anyhow!("{} is a draft or not yet published", file.display()))?;
+
+    log!("build"; "{}", page.paths.html.display());
+    Ok(page.paths.html)
+}
+
+/// Collect page metadata without writing any output, for `tola serve --no-build`.
+///
+/// Leaves `GLOBAL_SITE_DATA` and the dependency graph as [`build_site`]'s
+/// first phase would, so watch rebuilds see the whole site.
+pub fn collect_site_data(config: &SiteConfig) -> Result<()> {
+    BUILD_STATS.reset();
+    warn_config(config);
+    check_content_dir(&config.build.content)?;
+
+    GLOBAL_SITE_DATA.set_git(git::read_git_info(config.get_root()).as_ref());
+    if config.build.sitemap.lastmod == LastmodSource::Git {
+        git::load_commit_times(config.get_root());
+    }
+    if config.build.typst.use_lib {
+        warmup_typst(config);
+    }
+
+    collect_metadata(config, || {})?;
+    Ok(())
+}
+
+/// Report config warnings that aren't validation errors, into fresh stats.
+fn warn_config(config: &SiteConfig) {
+    if let Some(msg) = config.language_warning() {
+        log!("warn"; "{msg}");
+        BUILD_STATS.warn(msg);
+    }
+}
+
+/// Set `sys.inputs` and pre-warm typst library resources.
+fn warmup_typst(config: &SiteConfig) {
+    typst_lib::set_inputs(&config.build.typst.inputs);
+    // Font discovery is reused from `.tola-cache/` for repeated CLI builds
+    let cache_dir = config.get_root().join(CACHE_DIR);
+    typst_lib::warmup_with_root(
+        config.get_root(),
+        config.build.incremental.then_some(cache_dir.as_path()),
+    );
+}
+
+/// Check that the content directory exists and contains pages.
+///
+/// Returns an error if the directory is missing, or a warning message
+/// if it exists but has no `.typ` files.
+fn check_content_dir(content: &Path) -> Result<Option<String>> {
+    if !content.is_dir() {
+        bail!(
+            "content directory not found: {}; run `tola init`?",
+            content.display()
+        );
+    }
+
+    let has_pages = collect_all_files(content)
+        .iter()
+        .any(|p| p.extension().is_some_and(|ext| ext == "typ"));
+
+    Ok((!has_pages).then(|| format!("no .typ files found in {}", content.display())))
+}
+
 /// Ensure output directory exists with a git repository.
 ///
 /// Creates the directory and repo if missing, opens existing repo otherwise.
@@ -244,6 +498,21 @@ fn ensure_output_repo(output: &Path, clean: bool) -> Result<ThreadSafeRepository
     }
 }
 
+/// Number of pages listed by `log_slowest_pages`.
+const SLOWEST_PAGES: usize = 10;
+
+/// Log the slowest pages to compile (`-v`).
+fn log_slowest_pages() {
+    let slowest = BUILD_STATS.slowest_pages(SLOWEST_PAGES);
+    if slowest.is_empty() {
+        return;
+    }
+    log!("compile"; "slowest pages:");
+    for (path, elapsed) in slowest {
+        log!("compile"; "{:>6}ms  {path}", elapsed.as_millis());
+    }
+}
+
 /// Log build result based on output directory contents
 fn log_build_result(output: &Path) -> Result<()> {
     let file_count = fs::read_dir(output)?
@@ -252,10 +521,484 @@ fn log_build_result(output: &Path) -> Result<()> {
         .count();
 
     if file_count == 0 {
-        log!("warn"; "output is empty, check if content has .typ files");
+        const MSG: &str = "output is empty, check if content has .typ files";
+        log!("warn"; "{MSG}");
+        BUILD_STATS.warn(MSG);
     } else {
         log!("build"; "done");
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_config(dir: &Path) -> SiteConfig {
+        let mut config = SiteConfig::default();
+        config.set_root(dir);
+        config.build.content = dir.join("content");
+        config.build.output = dir.join("public");
+        config.build.assets = dir.join("assets");
+        config
+    }
+
+    #[test]
+    fn test_missing_content_dir_errors() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path());
+
+        let err = build_site(&config, true).unwrap_err().to_string();
+        assert!(err.contains("content directory not found"), "{err}");
+        assert!(err.contains("tola init"), "{err}");
+    }
+
+    #[test]
+    fn test_empty_content_dir_warns() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path());
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::write(config.build.content.join("image.png"), "").unwrap();
+
+        let warning = check_content_dir(&config.build.content).unwrap();
+        assert!(warning.is_some_and(|w| w.contains("no .typ files")));
+
+        // Still builds (an empty site), rather than failing
+        let (_, pages) = build_site(&config, true).unwrap();
+        assert_eq!(pages.len(), 0);
+
+        fs::write(config.build.content.join("index.typ"), "= Home").unwrap();
+        assert!(check_content_dir(&config.build.content).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fail_on_empty() {
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        config.build.fail_on_empty = true;
+        fs::create_dir_all(&config.build.content).unwrap();
+
+        let err = build_site(&config, true).unwrap_err().to_string();
+        assert!(err.contains("no .typ files"), "{err}");
+        assert!(
+            !config.build.output.exists(),
+            "fails before touching output"
+        );
+
+        // Pages that are all drafts also count as empty
+        fs::write(
+            config.build.content.join("draft.typ"),
+            "#metadata((draft: true))<tola-meta>\n= Draft",
+        )
+        .unwrap();
+        let err = build_site(&config, true).unwrap_err().to_string();
+        assert!(err.contains("no pages"), "{err}");
+    }
+
+    #[test]
+    fn test_collect_site_data_keeps_output() {
+        use crate::compiler::deps::DEPENDENCY_GRAPH;
+
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::create_dir_all(dir.path().join("utils")).unwrap();
+        fs::write(dir.path().join("utils/lib.typ"), "#let name = \"Home\"").unwrap();
+        let page = config.build.content.join("index.typ");
+        fs::write(&page, "#import \"/utils/lib.typ\": name\n= #name").unwrap();
+        fs::create_dir_all(&config.build.output).unwrap();
+        let html = config.build.output.join("index.html");
+        fs::write(&html, "existing").unwrap();
+
+        collect_site_data(&config).unwrap();
+
+        // Nothing is compiled to output, but watch knows what the page imports
+        assert_eq!(fs::read_to_string(&html).unwrap(), "existing");
+        assert_eq!(fs::read_dir(&config.build.output).unwrap().count(), 1);
+        let graph = DEPENDENCY_GRAPH.read();
+        let deps = graph.get_dependencies(&page).unwrap();
+        assert!(deps.contains(&dir.path().join("utils/lib.typ")), "{deps:?}");
+    }
+
+    #[test]
+    fn test_copy_extra_files() {
+        use crate::config::CopyEntry;
+
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::write(config.build.content.join("index.typ"), "= Home").unwrap();
+        fs::create_dir_all(dir.path().join("extra")).unwrap();
+        fs::write(
+            dir.path().join("extra/_headers"),
+            "/*\n  X-Frame-Options: DENY\n",
+        )
+        .unwrap();
+        config.build.copy = vec![CopyEntry {
+            from: dir.path().join("extra/_headers"),
+            to: "_headers".into(),
+        }];
+
+        build_site(&config, true).unwrap();
+
+        let copied = fs::read_to_string(config.build.output.join("_headers")).unwrap();
+        assert_eq!(copied, "/*\n  X-Frame-Options: DENY\n");
+    }
+
+    #[test]
+    fn test_atomic_build_keeps_output_on_failure() {
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::write(config.build.content.join("index.typ"), "= Home").unwrap();
+
+        build_site(&config, true).unwrap();
+        let output = config.build.output.clone();
+        let before = fs::read(output.join("index.html")).unwrap();
+        fs::write(output.join("stale.html"), "stale").unwrap();
+
+        // A page that fails to compile aborts the build mid-way
+        fs::write(config.build.content.join("broken.typ"), "#let x = (").unwrap();
+        assert!(build_atomic(&config, |c| build_site(c, true)).is_err());
+        assert_eq!(fs::read(output.join("index.html")).unwrap(), before);
+        assert!(output.join("stale.html").exists());
+        assert!(!sibling_dir(&output, "tmp").exists());
+
+        // A successful build replaces the output wholesale, keeping its repo
+        fs::remove_file(config.build.content.join("broken.typ")).unwrap();
+        build_atomic(&config, |c| build_site(c, true)).unwrap();
+        assert!(output.join("index.html").exists());
+        assert!(output.join(".git").is_dir());
+        assert!(!output.join("stale.html").exists());
+        assert!(!sibling_dir(&output, "tmp").exists());
+        assert!(!sibling_dir(&output, "old").exists());
+    }
+
+    #[test]
+    fn test_force_rebuild_ignores_up_to_date_output() {
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::create_dir_all(&config.build.assets).unwrap();
+        fs::write(config.build.content.join("index.typ"), "= Home").unwrap();
+        fs::write(config.build.assets.join("site.css"), "body {}").unwrap();
+        build_site(&config, true).unwrap();
+
+        // Edited outputs are newer than their sources, so they look up to date
+        let output = config.build.output.clone();
+        fs::write(output.join("index.html"), "edited").unwrap();
+        fs::write(output.join("site.css"), "edited").unwrap();
+
+        build_site(&config, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(output.join("index.html")).unwrap(),
+            "edited"
+        );
+        assert_eq!(
+            fs::read_to_string(output.join("site.css")).unwrap(),
+            "edited"
+        );
+
+        config.build.force_rebuild = true;
+        build_site(&config, true).unwrap();
+        assert!(
+            fs::read_to_string(output.join("index.html"))
+                .unwrap()
+                .contains("Home")
+        );
+        assert_eq!(
+            fs::read_to_string(output.join("site.css")).unwrap(),
+            "body {}"
+        );
+    }
+
+    #[test]
+    fn test_assets_include_exclude() {
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::write(config.build.content.join("index.typ"), "= Home").unwrap();
+        let assets = &config.build.assets;
+        fs::create_dir_all(assets.join("images/src")).unwrap();
+        fs::write(assets.join("images/logo.png"), "png").unwrap();
+        fs::write(assets.join("images/src/logo.psd"), "psd").unwrap();
+        fs::write(assets.join("site.css"), "body {}").unwrap();
+
+        config.build.assets_exclude = vec!["*.psd".into()];
+        build_site(&config, true).unwrap();
+        let output = config.build.output.clone();
+        assert!(output.join("images/logo.png").exists());
+        assert!(output.join("site.css").exists());
+        assert!(!output.join("images/src/logo.psd").exists());
+
+        config.build.clean = true;
+        config.build.assets_include = vec!["images/**".into()];
+        build_site(&config, true).unwrap();
+        assert!(output.join("images/logo.png").exists());
+        assert!(!output.join("site.css").exists());
+        assert!(!output.join("images/src/logo.psd").exists());
+    }
+
+    #[test]
+    fn test_only_compiles_matching_pages() {
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        config.build.only = Some("posts/**".into());
+        let content = &config.build.content;
+        fs::create_dir_all(content.join("posts/2024")).unwrap();
+        fs::write(content.join("index.typ"), "= Home").unwrap();
+        fs::write(content.join("posts/hello.typ"), "= Hello").unwrap();
+        fs::write(content.join("posts/2024/deep.typ"), "= Deep").unwrap();
+        fs::write(content.join("photo.png"), "png").unwrap();
+
+        let (_, pages) = build_site(&config, true).unwrap();
+
+        let output = &config.build.output;
+        assert!(output.join("posts/hello/index.html").exists());
+        assert!(output.join("posts/2024/deep/index.html").exists());
+        assert!(!output.join("index.html").exists());
+        assert!(!output.join("photo.png").exists());
+
+        // Unmatched pages are still listed, so `/_data` and feeds stay complete
+        assert_eq!(pages.len(), 3);
+    }
+
+    #[test]
+    fn test_build_single_file() {
+        let dir = TempDir::new().unwrap();
+        // `build_file` canonicalizes its argument, so the content dir must match
+        let root = dir.path().canonicalize().unwrap();
+        let mut config = make_config(&root);
+        config.build.typst.use_lib = true;
+        config.build.rss.enable = true;
+        config.build.sitemap.enable = true;
+        let content = &config.build.content;
+        fs::create_dir_all(content.join("posts")).unwrap();
+        fs::write(content.join("index.typ"), "= Home").unwrap();
+        fs::write(content.join("posts/hello.typ"), "= Hello").unwrap();
+        fs::create_dir_all(&config.build.assets).unwrap();
+        fs::write(config.build.assets.join("style.css"), "body {}").unwrap();
+
+        let html = build_file(&content.join("posts/hello.typ"), &config).unwrap();
+
+        let output = &config.build.output;
+        assert_eq!(html, output.join("posts/hello/index.html"));
+        assert!(fs::read_to_string(&html).unwrap().contains("Hello"));
+        assert!(!output.join("index.html").exists());
+        assert!(!output.join("style.css").exists());
+        assert!(!output.join("rss.xml").exists());
+        assert!(!output.join("sitemap.xml").exists());
+
+        // Files outside `content` are rejected
+        fs::write(root.join("stray.typ"), "= Stray").unwrap();
+        assert!(build_file(&root.join("stray.typ"), &config).is_err());
+    }
+
+    #[test]
+    fn test_auto_copy_referenced_files() {
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        config.build.auto_copy_referenced = true;
+        // Keep attribute quotes for the assertions below
+        config.build.minify = false;
+        let root = dir.path();
+        fs::create_dir_all(root.join("photos")).unwrap();
+        ::image::RgbImage::new(2, 2)
+            .save(root.join("photos/cat.png"))
+            .unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("docs/plan.pdf"), "pdf").unwrap();
+        fs::write(root.join("secret.json"), "{}").unwrap();
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::write(
+            config.build.content.join("index.typ"),
+            "#html.elem(\"img\", attrs: (src: \"../photos/cat.png\"))\n\
+             #json(\"../secret.json\")\n\
+             #link(\"../docs/plan.pdf#p2\")[plan]",
+        )
+        .unwrap();
+
+        let (_, pages) = build_site(&config, true).unwrap();
+
+        let output = &config.build.output;
+        // Referenced image and linked file are mirrored; loaded data is not
+        assert!(output.join("photos/cat.png").is_file());
+        assert_eq!(fs::read(output.join("docs/plan.pdf")).unwrap(), b"pdf");
+        assert!(!output.join("secret.json").exists());
+
+        let html = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(html.contains(r#"src="/photos/cat.png""#), "{html}");
+        assert!(html.contains(r#"href="/docs/plan.pdf#p2""#), "{html}");
+
+        // The copies aren't orphans
+        let orphans = crate::compiler::reconcile_output(&config, &pages).unwrap();
+        assert!(orphans.is_empty(), "{orphans:?}");
+
+        // A copy no page links to anymore is, even though it could be copied
+        fs::write(root.join("docs/old.pdf"), "old").unwrap();
+        fs::write(output.join("docs/old.pdf"), "old").unwrap();
+        let orphans = crate::compiler::reconcile_output(&config, &pages).unwrap();
+        assert_eq!(orphans, [output.join("docs/old.pdf")]);
+    }
+
+    #[test]
+    fn test_partials_not_built_but_importable() {
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        let content = &config.build.content;
+        fs::create_dir_all(content.join("_partials")).unwrap();
+        fs::write(
+            content.join("_partials/x.typ"),
+            "#let greet = [Hi from partial]",
+        )
+        .unwrap();
+        fs::write(
+            content.join("post.typ"),
+            "#import \"_partials/x.typ\": greet\n#greet",
+        )
+        .unwrap();
+
+        let (_, pages) = build_site(&config, true).unwrap();
+
+        assert_eq!(pages.len(), 1);
+        let html = fs::read_to_string(config.build.output.join("post/index.html")).unwrap();
+        assert!(html.contains("Hi from partial"), "{html}");
+        assert!(!config.build.output.join("_partials").exists());
+    }
+
+    #[test]
+    fn test_incremental_skips_identical_rewrite() {
+        use std::time::{Duration, SystemTime};
+
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        config.build.incremental = true;
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::create_dir_all(&config.build.assets).unwrap();
+        let (page, asset) = (
+            config.build.content.join("post.typ"),
+            config.build.assets.join("a.txt"),
+        );
+        fs::write(&page, "= Post").unwrap();
+        fs::write(&asset, "asset").unwrap();
+
+        build_site(&config, true).unwrap();
+        assert!(dir.path().join(".tola-cache/manifest.json").exists());
+
+        // Backdate outputs, then touch the sources without changing content
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let outputs = [
+            config.build.output.join("post/index.html"),
+            config.build.output.join("a.txt"),
+        ];
+        for out in &outputs {
+            fs::File::options()
+                .write(true)
+                .open(out)
+                .unwrap()
+                .set_modified(old)
+                .unwrap();
+        }
+        fs::write(&page, "= Post").unwrap();
+        fs::write(&asset, "asset").unwrap();
+
+        build_site(&config, true).unwrap();
+        for out in &outputs {
+            assert_eq!(
+                fs::metadata(out).unwrap().modified().unwrap(),
+                old,
+                "{out:?}"
+            );
+        }
+
+        // A real change is rebuilt
+        fs::write(&page, "= Changed").unwrap();
+        build_site(&config, true).unwrap();
+        let html = fs::read_to_string(&outputs[0]).unwrap();
+        assert!(html.contains("Changed"), "{html}");
+    }
+
+    #[test]
+    fn test_pages_and_assets_share_output_dirs() {
+        // Both stages run concurrently and create the same directories
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        let (content, assets) = (&config.build.content, &config.build.assets);
+        for i in 0..8 {
+            let section = format!("s{i}");
+            fs::create_dir_all(content.join(&section)).unwrap();
+            fs::create_dir_all(assets.join(&section).join("page")).unwrap();
+            fs::write(
+                content.join(&section).join("page.typ"),
+                format!("= Page {i}"),
+            )
+            .unwrap();
+            for j in 0..16 {
+                fs::write(
+                    assets.join(&section).join(format!("page/{j}.txt")),
+                    j.to_string(),
+                )
+                .unwrap();
+            }
+        }
+
+        let (_, pages) = build_site(&config, true).unwrap();
+
+        assert_eq!(pages.len(), 8);
+        let output = &config.build.output;
+        for i in 0..8 {
+            let page_dir = output.join(format!("s{i}/page"));
+            let html = fs::read_to_string(page_dir.join("index.html")).unwrap();
+            assert!(html.contains(&format!("Page {i}")), "{html}");
+            for j in 0..16 {
+                let copied = fs::read_to_string(page_dir.join(format!("{j}.txt"))).unwrap();
+                assert_eq!(copied, j.to_string());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_compile_sass() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path());
+        config.build.typst.use_lib = true;
+        fs::create_dir_all(&config.build.content).unwrap();
+        fs::write(config.build.content.join("index.typ"), "= Home").unwrap();
+        fs::create_dir_all(&config.build.assets).unwrap();
+        let input = config.build.assets.join("main.scss");
+        fs::write(&input, "$fg: red;\nbody { color: $fg; }\n").unwrap();
+        // Stand-in for the sass CLI: `sass --no-source-map <input> <output> [style]`
+        let mock = dir.path().join("mock-sass");
+        fs::write(
+            &mock,
+            "#!/bin/sh\nsed -e '/^\\$/d' -e 's/\\$fg/red/' \"$2\" > \"$3\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&mock, fs::Permissions::from_mode(0o755)).unwrap();
+        config.build.css.sass.enable = true;
+        config.build.css.sass.input = Some(input);
+        config.build.css.sass.command = vec![mock.to_string_lossy().into_owned()];
+
+        build_site(&config, true).unwrap();
+
+        let css = fs::read_to_string(config.build.output.join("main.css")).unwrap();
+        assert!(css.contains("color: red"), "{css}");
+        assert!(!config.build.output.join("main.scss").exists());
+    }
+}
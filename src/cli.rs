@@ -3,14 +3,14 @@
 //! Defines all CLI arguments and subcommands using clap.
 
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Tola static site generator CLI
 #[derive(Parser, Debug, Clone)]
 #[command(version, about, long_about = None, arg_required_else_help = true)]
 pub struct Cli {
-    /// Output directory path (relative to project root)
-    #[arg(short, long)]
+    /// Output directory path (relative to current directory, case preserved)
+    #[arg(short, long, visible_alias = "dest")]
     pub output: Option<PathBuf>,
 
     /// Content directory path (relative to project root)
@@ -25,6 +25,14 @@ pub struct Cli {
     #[arg(short = 'C', long, default_value = "tola.toml")]
     pub config: PathBuf,
 
+    /// Warn about unknown config keys instead of failing
+    #[arg(long, global = true)]
+    pub lenient_config: bool,
+
+    /// Fail on any warning (broken links, orphans, Typst warnings, unknown config keys)
+    #[arg(long, global = true)]
+    pub strict: bool,
+
     /// subcommands
     #[command(subcommand)]
     pub command: Commands,
@@ -37,6 +45,27 @@ pub struct BuildArgs {
     #[arg(long)]
     pub clean: bool,
 
+    /// Rebuild every page and asset even if the output looks up to date
+    #[arg(long)]
+    pub force_rebuild: bool,
+
+    /// Skip pages and assets whose content hashes match `.tola-cache/manifest.json`
+    #[arg(long)]
+    pub incremental: bool,
+
+    /// Only compile content files matching this glob (relative to `content`),
+    /// e.g. `--only 'posts/**'`. Assets and rss/sitemap are skipped unless requested
+    #[arg(long, value_name = "GLOB")]
+    pub only: Option<String>,
+
+    /// Fail instead of building an empty site when no pages are found
+    #[arg(long)]
+    pub fail_on_empty: bool,
+
+    /// Log per-page compile durations and the slowest pages
+    #[arg(short, long)]
+    pub verbose: bool,
+
     /// Minify the html content
     #[arg(short, long, action = clap::ArgAction::Set, num_args = 0..=1, default_missing_value = "true", require_equals = false)]
     pub minify: Option<bool>,
@@ -53,6 +82,14 @@ pub struct BuildArgs {
     #[arg(long, action = clap::ArgAction::Set, num_args = 0..=1, default_missing_value = "true", require_equals = false)]
     pub sitemap: Option<bool>,
 
+    /// include pages dated in the future
+    #[arg(long, action = clap::ArgAction::Set, num_args = 0..=1, default_missing_value = "true", require_equals = false)]
+    pub future: Option<bool>,
+
+    /// include pages past their expiry_date
+    #[arg(long, action = clap::ArgAction::Set, num_args = 0..=1, default_missing_value = "true", require_equals = false)]
+    pub expired: Option<bool>,
+
     /// Override base URL for the site.
     ///
     /// Useful for CI/CD deployments where the production URL differs from local development.
@@ -62,6 +99,26 @@ pub struct BuildArgs {
     ///   tola build --base-url "https://tola-ssg.github.io/example-sites/starter"
     #[arg(long = "base-url")]
     pub base_url: Option<String>,
+
+    /// Override the URL path prefix for subdirectory deployment.
+    ///
+    /// Takes precedence over the path component of `[base].url` / `--base-url`.
+    /// Useful for previews whose location varies per build:
+    ///   TOLA_PATH_PREFIX="pr-123" tola build
+    #[arg(long = "path-prefix", env = "TOLA_PATH_PREFIX")]
+    pub path_prefix: Option<String>,
+
+    /// Add a `sys.inputs` value (repeatable), merged over `[build.typst].inputs`
+    #[arg(long = "input", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    pub inputs: Vec<(String, String)>,
+}
+
+/// Parse a `KEY=VALUE` pair (value may contain `=`).
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got `{s}`")),
+    }
 }
 
 /// Available subcommands
@@ -79,6 +136,43 @@ pub enum Commands {
     Build {
         #[command(flatten)]
         build_args: BuildArgs,
+
+        /// Write a JSON build report (counts, sizes, stage timings) to this path
+        #[arg(long, value_name = "PATH")]
+        report: Option<PathBuf>,
+
+        /// Write an HTML build summary (page count, largest files, slowest
+        /// compiles, warnings) to this path, `build-report.html` in the
+        /// system temp directory by default
+        #[arg(long, value_name = "PATH", num_args = 0..=1)]
+        report_html: Option<Option<PathBuf>>,
+
+        /// Open the HTML build summary in the browser (implies `--report-html`)
+        #[arg(long)]
+        open_report: bool,
+
+        /// Package the built site into this `.zip`, `.tar.gz` or `.tgz` archive
+        #[arg(long, value_name = "PATH")]
+        archive: Option<PathBuf>,
+
+        /// Write Typst errors and warnings as JSON (`[{file, line, col, severity,
+        /// message}]`, 1-indexed) to this path, for editor problem matchers
+        #[arg(long, value_name = "PATH")]
+        errors_json: Option<PathBuf>,
+
+        /// Build only this content file (e.g. `content/posts/hello.typ`),
+        /// skipping assets, rss and sitemap
+        #[arg(value_name = "FILE")]
+        file: Option<PathBuf>,
+
+        /// List every page with its output path, URL and status (published,
+        /// draft, scheduled, expired) instead of building
+        #[arg(long, conflicts_with = "file")]
+        list: bool,
+
+        /// Print `--list` as JSON
+        #[arg(long, requires = "list")]
+        json: bool,
     },
 
     /// Serve the site. Rebuild and reload on change automatically
@@ -98,6 +192,14 @@ pub enum Commands {
         /// enable watch
         #[arg(short, long, action = clap::ArgAction::Set, num_args = 0..=1, default_missing_value = "true", require_equals = false)]
         watch: Option<bool>,
+
+        /// Poll for changes instead of using OS file events (e.g. on NFS)
+        #[arg(long)]
+        watch_poll: bool,
+
+        /// Serve the existing output without an initial build
+        #[arg(long)]
+        no_build: bool,
     },
 
     /// Deletes the output directory if there is one and rebuilds the site
@@ -107,6 +209,37 @@ pub enum Commands {
         #[arg(short, long, action = clap::ArgAction::Set, num_args = 0..=1, default_missing_value = "true", require_equals = false)]
         force: Option<bool>,
     },
+
+    /// Check that required tools and configured paths are available
+    Doctor,
+
+    /// Print version information
+    Version {
+        /// Also print the embedded Typst version and feature defaults
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// Inspect the effective configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+/// `tola config` subcommands
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommand {
+    /// Print the config exactly as a build sees it: paths absolutized and
+    /// CLI overrides applied
+    Print {
+        #[command(flatten)]
+        build_args: BuildArgs,
+
+        /// Print JSON instead of TOML
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[allow(unused)]
@@ -123,4 +256,64 @@ impl Cli {
     pub const fn is_deploy(&self) -> bool {
         matches!(self.command, Commands::Deploy { .. })
     }
+    pub const fn is_doctor(&self) -> bool {
+        matches!(self.command, Commands::Doctor)
+    }
+
+    /// Build arguments of commands that build the site.
+    pub const fn build_args(&self) -> Option<&BuildArgs> {
+        match &self.command {
+            Commands::Build { build_args, .. }
+            | Commands::Serve { build_args, .. }
+            | Commands::Config {
+                command: ConfigCommand::Print { build_args, .. },
+            } => Some(build_args),
+            _ => None,
+        }
+    }
+
+    /// Path passed to `tola build --report`, if any.
+    pub fn report_path(&self) -> Option<&Path> {
+        match &self.command {
+            Commands::Build { report, .. } => report.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Where `tola build --report-html`/`--open-report` writes the HTML summary.
+    pub fn report_html_path(&self) -> Option<PathBuf> {
+        let Commands::Build {
+            report_html,
+            open_report,
+            ..
+        } = &self.command
+        else {
+            return None;
+        };
+        let default = || std::env::temp_dir().join("build-report.html");
+        match report_html {
+            Some(Some(path)) => Some(path.clone()),
+            Some(None) => Some(default()),
+            None => open_report.then(default),
+        }
+    }
+
+    /// Whether `tola build --open-report` was given.
+    pub const fn open_report(&self) -> bool {
+        matches!(
+            self.command,
+            Commands::Build {
+                open_report: true,
+                ..
+            }
+        )
+    }
+
+    /// Path passed to `tola build --errors-json`, if any.
+    pub fn errors_json_path(&self) -> Option<&Path> {
+        match &self.command {
+            Commands::Build { errors_json, .. } => errors_json.as_deref(),
+            _ => None,
+        }
+    }
 }
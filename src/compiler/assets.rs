@@ -1,11 +1,39 @@
-use crate::compiler::is_up_to_date;
 use crate::compiler::meta::AssetMeta;
-use crate::config::SiteConfig;
+use crate::compiler::{collect_all_files, is_copy_up_to_date, record_copy};
+use crate::config::{CopyEntry, SiteConfig, TailwindEntry};
 use crate::log;
 use crate::utils::css;
 use anyhow::{Result, anyhow};
+use gix::{bstr::ByteSlice, glob::wildmatch};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Check a file in the assets directory against `[build].assets_include` and
+/// `[build].assets_exclude`.
+///
+/// Patterns match the path relative to the assets directory, or just the file
+/// name when they contain no `/` (like `.gitignore`).
+pub fn is_asset_selected(path: &Path, config: &SiteConfig) -> bool {
+    let build = &config.build;
+    let rel = path.strip_prefix(&build.assets).unwrap_or(path);
+    let rel = rel.to_string_lossy().replace('\\', "/");
+    let name = rel.rsplit('/').next().unwrap_or_default();
+
+    let matches = |pattern: &String| {
+        let text = if pattern.contains('/') {
+            rel.as_str()
+        } else {
+            name
+        };
+        wildmatch(
+            pattern.as_bytes().as_bstr(),
+            text.as_bytes().as_bstr(),
+            wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+        )
+    };
+    (build.assets_include.is_empty() || build.assets_include.iter().any(matches))
+        && !build.assets_exclude.iter().any(matches)
+}
 
 /// Process an asset file from the assets directory.
 pub fn process_asset(
@@ -14,10 +42,13 @@ pub fn process_asset(
     clean: bool,
     log_file: bool,
 ) -> Result<()> {
+    if !is_asset_selected(asset_path, config) {
+        return Ok(());
+    }
     let meta = AssetMeta::from_source(asset_path.to_path_buf(), config)?;
 
     // Skip if up-to-date (assets don't depend on templates)
-    if !clean && is_up_to_date(asset_path, &meta.paths.dest, None) {
+    if !clean && is_copy_up_to_date(asset_path, &meta.paths.dest, config) {
         return Ok(());
     }
 
@@ -39,8 +70,14 @@ pub fn process_asset(
         return Ok(());
     }
 
+    // Skip Sass sources (compiled to CSS by `rebuild_sass`)
+    if css::is_sass_source(asset_path, config) {
+        return Ok(());
+    }
+
     // Default: copy file
     fs::copy(&meta.paths.source, &meta.paths.dest)?;
+    record_copy(&meta.paths.source, &meta.paths.dest, config);
     Ok(())
 }
 
@@ -62,7 +99,7 @@ pub fn process_rel_asset(
     let output_path = output.join(rel_path);
 
     // Relative assets don't depend on templates/config, just check source vs dest
-    if !clean && is_up_to_date(path, &output_path, None) {
+    if !clean && is_copy_up_to_date(path, &output_path, config) {
         return Ok(());
     }
 
@@ -74,21 +111,169 @@ pub fn process_rel_asset(
         fs::create_dir_all(parent)?;
     }
 
-    fs::copy(path, output_path)?;
+    fs::copy(path, &output_path)?;
+    record_copy(path, &output_path, config);
     Ok(())
 }
 
+/// Source → destination pairs for `[build].copy` (directories expanded).
+pub fn copy_targets(config: &SiteConfig) -> Vec<(PathBuf, PathBuf)> {
+    let output = config.paths().output_dir();
+    config
+        .build
+        .copy
+        .iter()
+        .flat_map(|CopyEntry { from, to }| {
+            let dest = output.join(to);
+            if from.is_dir() {
+                collect_all_files(from)
+                    .into_iter()
+                    .filter_map(|src| {
+                        let rel = src.strip_prefix(from).ok()?.to_path_buf();
+                        Some((src, dest.join(rel)))
+                    })
+                    .collect()
+            } else {
+                vec![(from.clone(), dest)]
+            }
+        })
+        .collect()
+}
+
+/// Copy `[build].copy` entries verbatim into the output directory.
+pub fn copy_extra_files(config: &SiteConfig, clean: bool) -> Result<()> {
+    for (src, dest) in copy_targets(config) {
+        if !src.exists() {
+            return Err(anyhow!("[build.copy] source not found: {}", src.display()));
+        }
+        if !clean && is_copy_up_to_date(&src, &dest, config) {
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&src, &dest)?;
+        record_copy(&src, &dest, config);
+    }
+    Ok(())
+}
+
+/// Copy a file referenced from content to the same path below the output
+/// directory: `<root>/photos/cat.jpg` → `<output>/photos/cat.jpg`.
+///
+/// Returns the root-relative path, or `None` if the file isn't eligible
+/// (see [`referenced_rel_path`]).
+pub fn copy_referenced(src: &Path, config: &SiteConfig) -> Result<Option<PathBuf>> {
+    let Some(rel) = referenced_rel_path(src, config) else {
+        return Ok(None);
+    };
+    let dest = config.paths().output_dir().join(&rel);
+    if !is_copy_up_to_date(src, &dest, config) {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(src, &dest)?;
+        record_copy(src, &dest, config);
+    }
+    Ok(Some(rel))
+}
+
+/// Root-relative path of a file `[build].auto_copy_referenced` may copy.
+///
+/// Eligible files exist under the project root, outside `assets`, `content`
+/// (copied anyway), `[build].deps`, the output and hidden directories, and
+/// aren't Typst sources.
+pub fn referenced_rel_path(src: &Path, config: &SiteConfig) -> Option<PathBuf> {
+    if !config.build.auto_copy_referenced || !src.is_file() {
+        return None;
+    }
+    let build = &config.build;
+    let excluded = [&build.assets, &build.content, &build.output]
+        .into_iter()
+        .chain(&build.deps)
+        .any(|dir| src.starts_with(dir));
+    if excluded || src.extension().is_some_and(|ext| ext == "typ") {
+        return None;
+    }
+    let rel = src.strip_prefix(config.get_root()).ok()?;
+    let hidden = rel
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+    (!hidden).then(|| rel.to_path_buf())
+}
+
+/// Output path of a Tailwind entrypoint.
+///
+/// An explicit `output` is relative to the site output directory;
+/// otherwise the input's asset destination.
+pub fn tailwind_output(entry: &TailwindEntry, config: &SiteConfig) -> Result<PathBuf> {
+    if let Some(output) = &entry.output {
+        return Ok(config.paths().output_dir().join(output));
+    }
+    let meta = AssetMeta::from_source(entry.input.clone(), config)?;
+    Ok(meta.paths.dest)
+}
+
 /// Rebuild tailwind CSS.
 ///
 /// Delegates to `utils::css::rebuild_tailwind` with asset path resolution.
 /// When `quiet` is true, output is suppressed (for watch mode).
 pub fn rebuild_tailwind(config: &SiteConfig, quiet: bool) -> Result<()> {
-    css::rebuild_tailwind(
-        config,
-        |input| {
-            let meta = AssetMeta::from_source(input.to_path_buf(), config)?;
-            Ok(meta.paths.dest)
-        },
-        quiet,
-    )
+    css::rebuild_tailwind(config, |entry| tailwind_output(entry, config), quiet)
+}
+
+/// Output path of the compiled Sass stylesheet.
+///
+/// `[build.css.sass].output` is relative to the site output directory;
+/// otherwise the input's asset destination with a `.css` extension.
+pub fn sass_output(config: &SiteConfig) -> Result<PathBuf> {
+    let sass = &config.build.css.sass;
+    if let Some(output) = &sass.output {
+        return Ok(config.paths().output_dir().join(output));
+    }
+    let input = sass
+        .input
+        .as_ref()
+        .ok_or_else(|| anyhow!("Sass input path not configured"))?;
+    let meta = AssetMeta::from_source(input.clone(), config)?;
+    Ok(meta.paths.dest.with_extension("css"))
+}
+
+/// Rebuild Sass CSS.
+///
+/// When `quiet` is true, output is suppressed (for watch mode).
+pub fn rebuild_sass(config: &SiteConfig, quiet: bool) -> Result<()> {
+    css::rebuild_sass(config, |_| sass_output(config), quiet)
+}
+
+/// Stylesheets in the output directory: copied `.css` assets plus
+/// Tailwind and Sass outputs.
+pub fn css_outputs(config: &SiteConfig) -> Vec<PathBuf> {
+    let mut outputs: Vec<PathBuf> = collect_all_files(&config.build.assets)
+        .into_iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "css"))
+        .filter(|p| is_asset_selected(p, config))
+        .filter(|p| !css::is_tailwind_input(p, config))
+        .filter_map(|p| AssetMeta::from_source(p, config).ok())
+        .map(|meta| meta.paths.dest)
+        .collect();
+    if config.build.css.tailwind.enable {
+        for entry in config.build.css.tailwind.entries() {
+            outputs.extend(tailwind_output(&entry, config).ok());
+        }
+    }
+    if config.build.css.sass.enable {
+        outputs.extend(sass_output(config).ok());
+    }
+    outputs.retain(|p| p.is_file());
+    outputs.sort();
+    outputs.dedup();
+    outputs
+}
+
+/// Run the PostCSS pass over every produced stylesheet.
+///
+/// When `quiet` is true, output is suppressed (for watch mode).
+pub fn run_postcss(config: &SiteConfig, quiet: bool) -> Result<()> {
+    css::run_postcss(&css_outputs(config), config, quiet)
 }
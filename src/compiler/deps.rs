@@ -3,6 +3,12 @@
 //! Tracks which content files depend on which templates/utils files,
 //! enabling precise rebuilds when shared files change.
 //!
+//! A page's dependencies are every local file its compilation read, so the
+//! set is transitive: `page.typ` → `base.typ` → `partials/head.typ` records
+//! both templates. Typst asks the world for each imported source even when
+//! the importing module's evaluation is memoized, so pages compiled against
+//! a warm cache record the full chain too.
+//!
 //! # Architecture
 //!
 //! ```text
@@ -81,6 +87,12 @@ impl DependencyGraph {
         self.forward.insert(content_file, deps);
     }
 
+    /// Get the recorded dependencies of a content file.
+    #[inline]
+    pub fn get_dependencies(&self, content_file: &Path) -> Option<&FxHashSet<PathBuf>> {
+        self.forward.get(content_file)
+    }
+
     /// Get all content files that depend on the given file.
     #[inline]
     pub fn get_dependents(&self, dependency: &Path) -> Option<&FxHashSet<PathBuf>> {
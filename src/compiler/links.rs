@@ -0,0 +1,156 @@
+//! Broken internal link detection.
+//!
+//! After a build, every `href`/`src` in the output HTML that points into the
+//! site (`/blog/post/`, not `//cdn` or `https://...`) must resolve to a file:
+//! either the path itself or its `index.html` (`[build].page_extension`).
+//!
+//! Runs when `[build].check_links` is set or under `--strict`, where a broken
+//! link fails the build.
+
+use crate::{
+    config::{BuildConfig, SiteConfig},
+    log,
+    report::BUILD_STATS,
+};
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+use walkdir::WalkDir;
+
+/// Check internal links in the output HTML, warning about each broken one.
+///
+/// Returns the warning messages (empty if checking is disabled).
+pub fn check_links(config: &SiteConfig) -> Vec<String> {
+    if !config.build.check_links && !config.build.strict {
+        return Vec::new();
+    }
+
+    let broken = find_broken_links(&config.build);
+    for msg in &broken {
+        log!("warn"; "{msg}");
+        BUILD_STATS.warn(msg);
+    }
+    broken
+}
+
+/// `"broken link `/missing/` in posts/a/index.html"` for each unresolved link.
+fn find_broken_links(build: &BuildConfig) -> Vec<String> {
+    let output = &build.output;
+    let index_filename = build.index_filename();
+    let mut broken = Vec::new();
+    for path in page_files(build) {
+        let Ok(html) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let page = path.strip_prefix(output).unwrap_or(&path).display();
+        for link in internal_links(&html) {
+            if !resolves(output, link, &index_filename) {
+                broken.push(format!("broken link `{link}` in {page}"));
+            }
+        }
+    }
+    broken
+}
+
+/// All page files under the output dir, sorted, skipping hidden entries.
+fn page_files(build: &BuildConfig) -> Vec<PathBuf> {
+    let mut files: Vec<_> = WalkDir::new(&build.output)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .map(walkdir::DirEntry::into_path)
+        .filter(|p| build.is_page_file(p))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Site-root links (`/...`) in `href`/`src` attributes, quoted or not.
+pub(super) fn internal_links(html: &str) -> impl Iterator<Item = &str> {
+    static RE_LINK: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"\b(?:href|src)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).unwrap()
+    });
+
+    RE_LINK
+        .captures_iter(html)
+        .filter_map(|caps| caps.iter().skip(1).flatten().next())
+        .map(|m| m.as_str())
+        .filter(|link| link.starts_with('/') && !link.starts_with("//"))
+}
+
+/// Check if a site-root link resolves to a file (or directory index) in `output`.
+fn resolves(output: &Path, link: &str, index_filename: &str) -> bool {
+    let target = link_target(output, link);
+    target.is_file() || target.join(index_filename).is_file()
+}
+
+/// Path in `output` a site-root link points at, without query or fragment.
+pub(super) fn link_target(output: &Path, link: &str) -> PathBuf {
+    let path = link.split(['?', '#']).next().unwrap_or_default();
+    let path = urlencoding::decode(path).map_or_else(|_| path.into(), |p| p.into_owned());
+    output.join(path.trim_start_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_internal_links() {
+        let html = r##"<a href="/a/">A</a><img src='/b.png'><a href=/c/#x>C</a>
+            <a href="//cdn.example.com/x.js">ext</a><a href="https://x.com">x</a><a href="#top">t</a>"##;
+        let links: Vec<_> = internal_links(html).collect();
+        assert_eq!(links, ["/a/", "/b.png", "/c/#x"]);
+    }
+
+    #[test]
+    fn test_strict_checks_links() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        fs::write(content.join("index.typ"), "#link(\"/about/\")[About]").unwrap();
+        fs::write(content.join("post.typ"), "#link(\"/missing/\")[Gone]").unwrap();
+        fs::write(content.join("about.typ"), "= About").unwrap();
+
+        let mut config = SiteConfig::default();
+        config.set_root(dir.path());
+        config.build.content = content;
+        config.build.output = dir.path().join("public");
+        config.build.assets = dir.path().join("assets");
+        config.build.typst.use_lib = true;
+        config.build.strict = true;
+
+        crate::build::build_site(&config, true).unwrap();
+        let warnings = check_links(&config);
+        assert_eq!(warnings, ["broken link `/missing` in post/index.html"]);
+    }
+
+    #[test]
+    fn test_find_broken_links() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path();
+        fs::create_dir_all(output.join("posts/hello world")).unwrap();
+        fs::write(output.join("posts/hello world/index.html"), "").unwrap();
+        fs::write(output.join("logo.png"), "").unwrap();
+        fs::write(
+            output.join("index.html"),
+            r#"<a href="/posts/hello%20world/#intro">ok</a><img src="/logo.png?v=1">
+               <a href="/missing/">broken</a>"#,
+        )
+        .unwrap();
+
+        let build = BuildConfig {
+            output: output.to_path_buf(),
+            ..Default::default()
+        };
+        assert_eq!(
+            find_broken_links(&build),
+            ["broken link `/missing/` in index.html"]
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Page listing for `tola build --list`.
+//!
+//! Collects the paths and metadata of every content page, drafts included,
+//! without writing any output. Useful to check where slugs put a page and
+//! which URL it ends up at.
+//!
+//! ```text
+//! STATUS     SOURCE                     OUTPUT                           URL
+//! published  content/posts/hello.typ    public/posts/hello/index.html    /posts/hello/
+//! draft      content/posts/wip.typ      public/posts/wip/index.html      /posts/wip/
+//! ```
+
+use super::pages::{PageStatus, page_status, read_page_meta};
+use super::{check_output_conflicts, collect_page_files};
+use crate::config::SiteConfig;
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// A listed page, with paths relative to the site root.
+#[derive(Debug, Serialize)]
+pub struct PageEntry {
+    pub source: PathBuf,
+    pub output: PathBuf,
+    pub url: String,
+    pub status: PageStatus,
+}
+
+/// All content pages sorted by source path, including unpublished ones.
+///
+/// Pages whose metadata compile times out are skipped with a warning.
+pub fn list_pages(config: &SiteConfig) -> Result<Vec<PageEntry>> {
+    let files = collect_page_files(config);
+    check_output_conflicts(&files, config)?;
+
+    let root = config.get_root();
+    let rel = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+    let entries: Vec<Option<PageEntry>> = files
+        .par_iter()
+        .map(|path| {
+            let Some(page) = read_page_meta(path, config)? else {
+                return Ok(None);
+            };
+            Ok(Some(PageEntry {
+                source: rel(&page.paths.source),
+                output: rel(&page.paths.html),
+                url: page.paths.url_path,
+                status: page_status(page.content_meta.as_ref(), config),
+            }))
+        })
+        .collect::<Result<_>>()?;
+
+    let mut entries: Vec<_> = entries.into_iter().flatten().collect();
+    entries.sort_by(|a, b| a.source.cmp(&b.source));
+    Ok(entries)
+}
+
+/// Render the listing as an aligned table, or as pretty JSON.
+pub fn render_page_list(entries: &[PageEntry], json: bool) -> Result<String> {
+    if json {
+        return Ok(serde_json::to_string_pretty(entries)? + "\n");
+    }
+
+    let rows: Vec<[String; 4]> = entries
+        .iter()
+        .map(|entry| {
+            [
+                entry.status.as_str().to_string(),
+                entry.source.display().to_string(),
+                entry.output.display().to_string(),
+                entry.url.clone(),
+            ]
+        })
+        .collect();
+    let header = ["STATUS", "SOURCE", "OUTPUT", "URL"].map(String::from);
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_list_includes_drafts() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(content.join("posts")).unwrap();
+        fs::write(
+            content.join("posts/Hello World.typ"),
+            "#metadata((title: \"Hello\")) <tola-meta>\n= Hello",
+        )
+        .unwrap();
+        fs::write(
+            content.join("posts/wip.typ"),
+            "#metadata((title: \"WIP\", draft: true)) <tola-meta>\n= WIP",
+        )
+        .unwrap();
+
+        let mut config = SiteConfig::default();
+        config.set_root(dir.path());
+        config.build.content = content;
+        config.build.output = dir.path().join("public");
+        config.build.typst.use_lib = true;
+
+        let entries = list_pages(&config).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].source,
+            Path::new("content/posts/Hello World.typ")
+        );
+        assert_eq!(entries[0].url, "/posts/hello-world/");
+        assert_eq!(
+            entries[0].output,
+            Path::new("public/posts/hello-world/index.html")
+        );
+        assert_eq!(entries[0].status, PageStatus::Published);
+        assert_eq!(entries[1].url, "/posts/wip/");
+        assert_eq!(entries[1].status, PageStatus::Draft);
+        // Nothing is written
+        assert!(!config.build.output.exists());
+
+        let table = render_page_list(&entries, false).unwrap();
+        assert!(table.starts_with("STATUS"));
+        assert!(table.contains("draft      content/posts/wip.typ"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_page_list(&entries, true).unwrap()).unwrap();
+        assert_eq!(json[1]["status"], "draft");
+        assert_eq!(json[0]["url"], "/posts/hello-world/");
+    }
+}
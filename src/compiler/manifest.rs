@@ -0,0 +1,233 @@
+//! Content-hash manifest for `tola build --incremental`.
+//!
+//! mtimes change on every checkout, so CI builds can't trust `is_up_to_date`.
+//! Instead, `.tola-cache/manifest.json` records for each source file:
+//!
+//! ```text
+//! content/posts/hello.typ → { hash, deps: { templates/base.typ → hash, ... }, output, output_hash }
+//! ```
+//!
+//! An output is fresh when the source, every recorded dependency, and the
+//! output itself still hash the same. Paths are stored relative to the project
+//! root so the manifest survives checkouts into a different directory.
+//!
+//! The whole manifest is discarded when `tola.toml` or the tola version changes.
+
+use crate::config::SiteConfig;
+use crate::data::{is_virtual_data_path, read_virtual_data};
+use crate::utils::hash;
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Cache directory (relative to the project root).
+pub const CACHE_DIR: &str = ".tola-cache";
+
+/// Manifest file name inside [`CACHE_DIR`].
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Global manifest, loaded at the start of an incremental build.
+pub static MANIFEST: LazyLock<RwLock<Manifest>> =
+    LazyLock::new(|| RwLock::new(Manifest::default()));
+
+/// Hashes recorded for one source file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ManifestEntry {
+    hash: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    deps: BTreeMap<PathBuf, String>,
+    output: PathBuf,
+    output_hash: String,
+}
+
+/// Source path → recorded hashes, see the module docs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Fingerprint of `tola.toml` and the tola version
+    config: String,
+    entries: BTreeMap<PathBuf, ManifestEntry>,
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+impl Manifest {
+    /// Load the project's manifest, or an empty one if missing, unreadable
+    /// or written for a different config.
+    pub fn load(config: &SiteConfig) -> Self {
+        let root = config.get_root().to_path_buf();
+        let fingerprint = config_fingerprint(config);
+
+        let manifest = fs::read(manifest_path(config))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+            .filter(|m| m.config == fingerprint);
+
+        Self {
+            root,
+            ..manifest.unwrap_or(Self {
+                config: fingerprint,
+                ..Self::default()
+            })
+        }
+    }
+
+    /// Write the manifest, dropping entries whose source no longer exists.
+    pub fn save(&mut self, config: &SiteConfig) -> Result<()> {
+        let root = &self.root;
+        self.entries.retain(|source, _| root.join(source).exists());
+
+        let path = manifest_path(config);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Check if `output` is fresh for `source`: same output path, and the
+    /// source, dependencies and output all hash as recorded.
+    pub fn is_fresh(&self, source: &Path, output: &Path) -> bool {
+        let Some(entry) = self.entries.get(&self.rel(source)) else {
+            return false;
+        };
+
+        entry.output == self.rel(output)
+            && hash_file(output).is_some_and(|h| h == entry.output_hash)
+            && hash_file(source).is_some_and(|h| h == entry.hash)
+            && entry
+                .deps
+                .iter()
+                .all(|(dep, h)| hash_dep(&self.root.join(dep)).is_some_and(|cur| cur == *h))
+    }
+
+    /// Record the current hashes of `source`, its `deps` and `output`.
+    ///
+    /// Sources or outputs that can't be read are forgotten instead.
+    pub fn record<'a>(
+        &mut self,
+        source: &Path,
+        deps: impl IntoIterator<Item = &'a PathBuf>,
+        output: &Path,
+    ) {
+        let key = self.rel(source);
+        let (Some(hash), Some(output_hash)) = (hash_file(source), hash_file(output)) else {
+            self.entries.remove(&key);
+            return;
+        };
+
+        let deps = deps
+            .into_iter()
+            .filter(|dep| dep.as_path() != source)
+            .filter_map(|dep| Some((self.rel(dep), hash_dep(dep)?)))
+            .collect();
+
+        let entry = ManifestEntry {
+            hash,
+            deps,
+            output: self.rel(output),
+            output_hash,
+        };
+        self.entries.insert(key, entry);
+    }
+
+    /// Path relative to the project root (unchanged if outside it).
+    fn rel(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.root).unwrap_or(path).to_path_buf()
+    }
+}
+
+/// `<root>/.tola-cache/manifest.json`
+fn manifest_path(config: &SiteConfig) -> PathBuf {
+    config.get_root().join(CACHE_DIR).join(MANIFEST_FILE)
+}
+
+/// Fingerprint of everything that invalidates all entries at once.
+fn config_fingerprint(config: &SiteConfig) -> String {
+    let toml = fs::read(&config.config_path).unwrap_or_default();
+    let version = env!("CARGO_PKG_VERSION").as_bytes();
+    format!(
+        "{:016x}",
+        hash::compute(&[toml.as_slice(), b"\0", version].concat())
+    )
+}
+
+/// Hash a file's content.
+fn hash_file(path: &Path) -> Option<String> {
+    let file = fs::File::open(path).ok()?;
+    hash::compute_reader(file).ok().map(|h| format!("{h:016x}"))
+}
+
+/// Hash a dependency, which may be a virtual data file (`/_data/*.json`).
+fn hash_dep(path: &Path) -> Option<String> {
+    if is_virtual_data_path(path) {
+        return read_virtual_data(path).map(|data| format!("{:016x}", hash::compute(&data)));
+    }
+    hash_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_config(root: &Path) -> SiteConfig {
+        let mut config = SiteConfig {
+            config_path: root.join("tola.toml"),
+            ..Default::default()
+        };
+        config.set_root(root);
+        config
+    }
+
+    #[test]
+    fn test_fresh_until_content_changes() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path());
+        let (src, dep, out) = (
+            dir.path().join("a.typ"),
+            dir.path().join("base.typ"),
+            dir.path().join("a.html"),
+        );
+        fs::write(&src, "= A").unwrap();
+        fs::write(&dep, "#let x = 1").unwrap();
+        fs::write(&out, "<h1>A</h1>").unwrap();
+
+        let mut manifest = Manifest::load(&config);
+        assert!(!manifest.is_fresh(&src, &out));
+        manifest.record(&src, [&dep], &out);
+        assert!(manifest.is_fresh(&src, &out));
+
+        // Identical rewrite (new mtime) stays fresh
+        fs::write(&src, "= A").unwrap();
+        assert!(manifest.is_fresh(&src, &out));
+
+        fs::write(&dep, "#let x = 2").unwrap();
+        assert!(!manifest.is_fresh(&src, &out));
+    }
+
+    #[test]
+    fn test_save_and_reload() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path());
+        fs::write(&config.config_path, "[base]").unwrap();
+        let (src, out) = (dir.path().join("a.txt"), dir.path().join("out.txt"));
+        fs::write(&src, "a").unwrap();
+        fs::write(&out, "a").unwrap();
+
+        let mut manifest = Manifest::load(&config);
+        manifest.record(&src, [], &out);
+        manifest.save(&config).unwrap();
+
+        let raw = fs::read_to_string(manifest_path(&config)).unwrap();
+        assert!(raw.contains("\"a.txt\""), "paths are root-relative: {raw}");
+        assert!(Manifest::load(&config).is_fresh(&src, &out));
+
+        // A different config invalidates everything
+        fs::write(&config.config_path, "[base]\ntitle = \"x\"").unwrap();
+        assert!(!Manifest::load(&config).is_fresh(&src, &out));
+    }
+}
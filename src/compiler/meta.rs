@@ -60,11 +60,21 @@
 //! }
 //! ```
 
-use crate::{config::SiteConfig, utils::slug::slugify_path};
+use crate::{
+    compiler::is_index_page,
+    config::{LastmodSource, SiteConfig, TextDirection},
+    log,
+    report::BUILD_STATS,
+    utils::{
+        date::DateTimeUtc,
+        git,
+        slug::slugify_path,
+        typst_element::{TypstElement, html_escape},
+    },
+};
 use anyhow::{Result, anyhow};
 use serde::Deserialize;
 use std::{
-    borrow::Cow,
     fs,
     path::{Path, PathBuf},
     time::SystemTime,
@@ -79,6 +89,16 @@ use std::{
 /// Used to extract page metadata from `#metadata(...) <tola-meta>` in typst files.
 pub const TOLA_META_LABEL: &str = "tola-meta";
 
+/// Extensions of front-matter companions read next to a page
+/// (`post.typ` → `post.yaml`).
+pub const COMPANION_EXTENSIONS: &[&str] = &["yaml", "yml"];
+
+/// Per-directory default front matter, cascading into every page below it.
+pub const DIR_DEFAULTS_FILE: &str = "_defaults.toml";
+
+/// List keys combined (union) rather than replaced when metadata cascades.
+const UNION_KEYS: &[&str] = &["tags"];
+
 // ============================================================================
 // Asset Metadata
 // ============================================================================
@@ -171,14 +191,196 @@ pub struct ContentMeta {
     #[serde(default, deserialize_with = "deserialize_summary")]
     pub summary: Option<String>,
     pub date: Option<String>,
-    #[allow(dead_code)] // Reserved for future use
+    /// Last update date (same formats as `date`): sitemap `<lastmod>` and
+    /// RSS `<atom:updated>`
     pub update: Option<String>,
-    pub author: Option<String>,
+    /// Page authors: `author: "Alice"` or `author: ("Alice", "Bob")`.
+    #[serde(default, rename = "author", deserialize_with = "deserialize_authors")]
+    pub authors: Vec<String>,
     #[serde(default)]
     pub draft: bool,
+    /// Date after which the page is unpublished (same formats as `date`).
+    pub expiry_date: Option<String>,
     /// Tags for categorizing the page.
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Section override; defaults to the top-level content subdirectory.
+    pub section: Option<String>,
+    /// Former URL paths (e.g. `/old/post/`) that redirect to this page.
+    #[serde(default)]
+    pub aliases: Vec<PathBuf>,
+    /// Text direction override for this page (`[base].direction`).
+    pub direction: Option<TextDirection>,
+    /// Write the compiled HTML verbatim: no head injection, link rewriting,
+    /// SVG extraction or minification.
+    #[serde(default)]
+    pub raw: bool,
+    /// Keep the page out of search engines: `<meta name="robots">` and no
+    /// sitemap entry. The page is still built.
+    #[serde(default)]
+    pub noindex: bool,
+    /// `false` keeps the page out of the RSS feed (`in_feed` also accepted).
+    #[serde(alias = "in_feed")]
+    pub rss: Option<bool>,
+    /// `false` keeps the page out of the sitemap.
+    pub sitemap: Option<bool>,
+    /// Body HTML before the `<tola-more>`/`<!--more-->` marker (set post-compile).
+    #[serde(skip)]
+    pub summary_html: Option<String>,
+}
+
+impl ContentMeta {
+    /// Parse page metadata from the inline `<tola-meta>` value merged over the
+    /// page's YAML companion and the `_defaults.toml` of its directories.
+    ///
+    /// Top-level keys set inline win; the companion fills in the rest. Directory
+    /// defaults apply from `content_dir` downwards, deeper ones overriding,
+    /// with [`UNION_KEYS`] lists merged instead.
+    pub fn from_sources(
+        source: &Path,
+        content_dir: &Path,
+        inline: Option<serde_json::Value>,
+    ) -> Option<Self> {
+        use serde_json::Value;
+        let own = match (read_companion(source), inline) {
+            (Some(Value::Object(mut companion)), Some(Value::Object(inline))) => {
+                companion.extend(inline);
+                Some(Value::Object(companion))
+            }
+            (companion, inline) => inline.or(companion),
+        };
+        let merged = match (read_dir_defaults(source, content_dir), own) {
+            (Some(mut defaults), Some(Value::Object(own))) => {
+                merge_meta(&mut defaults, own);
+                Value::Object(defaults)
+            }
+            (Some(defaults), None) => Value::Object(defaults),
+            (_, own) => own?,
+        };
+        serde_json::from_value(merged).ok()
+    }
+
+    /// Whether the page is listed in the RSS feed (independent of `draft`).
+    pub fn in_feed(&self) -> bool {
+        self.rss != Some(false)
+    }
+
+    /// Whether the page is listed in the sitemap.
+    pub fn in_sitemap(&self) -> bool {
+        self.sitemap != Some(false) && !self.noindex
+    }
+}
+
+/// `_defaults.toml` files applying to `source`, outermost directory first.
+pub fn dir_defaults_paths(source: &Path, content_dir: &Path) -> Vec<PathBuf> {
+    let Some(rel_dir) = source
+        .parent()
+        .and_then(|dir| dir.strip_prefix(content_dir).ok())
+    else {
+        return Vec::new();
+    };
+
+    let mut dir = content_dir.to_path_buf();
+    let mut paths = Vec::new();
+    for component in std::iter::once(None).chain(rel_dir.components().map(Some)) {
+        if let Some(component) = component {
+            dir.push(component);
+        }
+        let path = dir.join(DIR_DEFAULTS_FILE);
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Check if `path` is a per-directory `_defaults.toml`.
+pub fn is_dir_defaults(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == DIR_DEFAULTS_FILE)
+}
+
+/// Cascade the directory defaults of `source`, warning on (and skipping)
+/// files that aren't valid TOML or don't fit [`ContentMeta`].
+fn read_dir_defaults(
+    source: &Path,
+    content_dir: &Path,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    let mut merged: Option<serde_json::Map<_, _>> = None;
+    for path in dir_defaults_paths(source, content_dir) {
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let defaults = toml::from_str::<serde_json::Map<_, _>>(&text)
+            .map_err(|e| e.to_string())
+            .and_then(|defaults| {
+                let value = serde_json::Value::Object(defaults.clone());
+                serde_json::from_value::<ContentMeta>(value)
+                    .map(|_| defaults)
+                    .map_err(|e| e.to_string())
+            });
+        match defaults {
+            Ok(defaults) => merge_meta(merged.get_or_insert_default(), defaults),
+            Err(e) => {
+                let msg = format!("{}: invalid default front matter: {e}", path.display());
+                log!("warn"; "{msg}");
+                BUILD_STATS.warn(msg);
+            }
+        }
+    }
+    merged
+}
+
+/// Merge `over` into `base`: keys of `over` win, except [`UNION_KEYS`] lists,
+/// which gain the items they don't have yet.
+fn merge_meta(
+    base: &mut serde_json::Map<String, serde_json::Value>,
+    over: serde_json::Map<String, serde_json::Value>,
+) {
+    use serde_json::Value;
+    for (key, value) in over {
+        if let Value::Array(items) = &value
+            && UNION_KEYS.contains(&key.as_str())
+            && let Some(Value::Array(existing)) = base.get_mut(&key)
+        {
+            for item in items {
+                if !existing.contains(item) {
+                    existing.push(item.clone());
+                }
+            }
+            continue;
+        }
+        base.insert(key, value);
+    }
+}
+
+/// Find the front-matter companion (`.yaml`/`.yml`) of a `.typ` page.
+pub fn companion_path(source: &Path) -> Option<PathBuf> {
+    COMPANION_EXTENSIONS
+        .iter()
+        .map(|ext| source.with_extension(ext))
+        .find(|path| path.is_file())
+}
+
+/// Check if `path` is the front-matter companion of a sibling `.typ` page.
+pub fn is_companion(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| COMPANION_EXTENSIONS.contains(&ext))
+        && path.with_extension("typ").is_file()
+}
+
+/// Read a page's companion as JSON, warning if it isn't valid YAML.
+fn read_companion(source: &Path) -> Option<serde_json::Value> {
+    let path = companion_path(source)?;
+    let text = fs::read_to_string(&path).ok()?;
+    serde_yaml_ng::from_str(&text)
+        .inspect_err(|e| {
+            let msg = format!("{}: invalid front matter: {e}", path.display());
+            log!("warn"; "{msg}");
+            BUILD_STATS.warn(msg);
+        })
+        .ok()
 }
 
 /// Primary metadata structure for a content page.
@@ -202,7 +404,7 @@ pub struct ContentMeta {
 pub struct PageMeta {
     /// Path information
     pub paths: PagePaths,
-    /// Last modification time of the HTML file
+    /// Last modification time of the source (mtime, or last commit with `lastmod = "git"`)
     pub lastmod: Option<SystemTime>,
     /// Content metadata from `<tola-meta>` (None if not present)
     pub content_meta: Option<ContentMeta>,
@@ -239,49 +441,26 @@ impl PageMeta {
     /// - File is not in content directory
     /// - File is not a .typ file
     pub fn from_paths(source: PathBuf, config: &SiteConfig) -> Result<Self> {
-        let content_dir = &config.build.content;
-        let paths = config.paths();
-        let output_dir = paths.output_dir();
-        let base_url = config
-            .base
-            .url
-            .as_deref()
-            .unwrap_or_default()
-            .trim_end_matches('/');
-
-        // Strip content dir and .typ extension
-        let relative = source
-            .strip_prefix(content_dir)
-            .map_err(|_| anyhow!("File is not in content directory: {}", source.display()))?
-            .to_str()
-            .ok_or_else(|| anyhow!("Invalid path encoding"))?
-            .strip_suffix(".typ")
-            .ok_or_else(|| anyhow!("Not a .typ file: {}", source.display()))?
-            .to_owned();
-
-        let is_root_index = relative == "index";
-
-        // Compute HTML output path
-        // Only slugify the relative path part to preserve output dir and index.html
-        let html = if is_root_index {
-            output_dir.join("index.html")
-        } else {
-            let slugified_relative = slugify_path(Path::new(&relative), config);
-            output_dir.join(slugified_relative).join("index.html")
-        };
+        // url_path already includes path_prefix, so only the origin is prepended
+        let base_url = config.base_origin();
+        let (relative, html) = Self::output_paths(&source, config)?;
+        let index_filename = config.build.index_filename();
 
         // Compute URL path from the final HTML path to ensure consistency
         let full_path_url = url_from_output_path(&html, config)?;
 
-        // Remove "index.html" for pretty URLs
-        let url_path = if full_path_url.ends_with("/index.html") {
-            full_path_url.trim_end_matches("index.html").to_string()
-        } else {
-            full_path_url
+        // Remove the index file name for pretty URLs
+        let url_path = match full_path_url.strip_suffix(&index_filename) {
+            Some(dir_url) if dir_url.ends_with('/') => dir_url.to_string(),
+            _ => full_path_url,
         };
 
         let full_url = format!("{base_url}{url_path}");
-        let lastmod = fs::metadata(&source).and_then(|m| m.modified()).ok();
+        let git_lastmod = match config.build.sitemap.lastmod {
+            LastmodSource::Git => git::last_commit_time(&source),
+            LastmodSource::Mtime => None,
+        };
+        let lastmod = git_lastmod.or_else(|| fs::metadata(&source).and_then(|m| m.modified()).ok());
 
         Ok(Self {
             paths: PagePaths {
@@ -297,6 +476,47 @@ impl PageMeta {
         })
     }
 
+    /// HTML file a source .typ file is written to, see [`Self::from_paths`].
+    pub fn html_path(source: &Path, config: &SiteConfig) -> Result<PathBuf> {
+        Ok(Self::output_paths(source, config)?.1)
+    }
+
+    /// Source path relative to the content dir without `.typ`, and its HTML file.
+    fn output_paths(source: &Path, config: &SiteConfig) -> Result<(String, PathBuf)> {
+        let content_dir = &config.build.content;
+        let paths = config.paths();
+        let output_dir = paths.output_dir();
+
+        // Strip content dir and .typ extension
+        let relative = source
+            .strip_prefix(content_dir)
+            .map_err(|_| anyhow!("File is not in content directory: {}", source.display()))?
+            .to_str()
+            .ok_or_else(|| anyhow!("Invalid path encoding"))?
+            .strip_suffix(".typ")
+            .ok_or_else(|| anyhow!("Not a .typ file: {}", source.display()))?
+            .to_owned();
+
+        // The root index, and `[build].index_names` pages, stand for their directory
+        let relative_path = Path::new(&relative);
+        let page_dir = if relative == "index" || is_index_page(source, config) {
+            relative_path.parent().unwrap_or(Path::new(""))
+        } else {
+            relative_path
+        };
+
+        // Compute HTML output path
+        // Only slugify the relative path part to preserve output dir and index file
+        let index_filename = config.build.index_filename();
+        let html = if page_dir.as_os_str().is_empty() {
+            output_dir.join(&index_filename)
+        } else {
+            let slugified_relative = slugify_path(page_dir, config);
+            output_dir.join(slugified_relative).join(&index_filename)
+        };
+        Ok((relative, html))
+    }
+
     /// Set content metadata and check for draft status.
     ///
     /// Returns `Some(self)` if not a draft, `None` if draft.
@@ -310,7 +530,17 @@ impl PageMeta {
     }
 
     /// Get lastmod as YYYY-MM-DD string for sitemap.
+    ///
+    /// The metadata `update` date wins over the file's modification time.
     pub fn lastmod_ymd(&self) -> Option<String> {
+        let update = self.content_meta.as_ref().and_then(|c| c.update.as_deref());
+        if let Some(DateTimeUtc {
+            year, month, day, ..
+        }) = update.and_then(DateTimeUtc::parse)
+        {
+            return Some(format!("{year:04}-{month:02}-{day:02}"));
+        }
+
         let modified = self.lastmod?;
         let duration = modified.duration_since(std::time::UNIX_EPOCH).ok()?;
         #[allow(clippy::cast_possible_wrap)] // Safe: seconds/86400 fits in i64
@@ -370,81 +600,23 @@ where
     }
 }
 
-/// Typst content element for summary field deserialization.
-///
-/// Parses JSON-serialized Typst content and converts to HTML.
-///
-/// # Supported Elements
-///
-/// | Element    | HTML Output               |
-/// |------------|---------------------------|
-/// | Space      | ` ` (space)               |
-/// | Linebreak  | `<br/>`                   |
-/// | Text       | Escaped text              |
-/// | Strike     | `<s>text</s>`             |
-/// | Link       | `<a href="...">text</a>`  |
-/// | Sequence   | Concatenated children     |
-/// | Unknown    | Empty string (ignored)    |
-#[derive(Debug, Deserialize, PartialEq, Eq)]
-#[serde(tag = "func", rename_all = "lowercase")]
-enum TypstElement {
-    Space,
-    Linebreak,
-    Text {
-        text: String,
-    },
-    Strike {
-        text: String,
-    },
-    Link {
-        dest: String,
-        body: Box<Self>,
-    },
-    Sequence {
-        children: Vec<Self>,
-    },
-    #[serde(other)]
-    Unknown,
-}
-
-impl TypstElement {
-    /// Convert Typst element to HTML string.
-    fn to_html(&self) -> String {
-        match self {
-            Self::Space => " ".into(),
-            Self::Linebreak => "<br/>".into(),
-            Self::Text { text } => html_escape(text).into_owned(),
-            Self::Strike { text } => format!("<s>{}</s>", html_escape(text)),
-            Self::Link { dest, body } => {
-                format!("<a href=\"{dest}\">{}</a>", body.to_html())
-            }
-            Self::Sequence { children } => children.iter().map(Self::to_html).collect(),
-            Self::Unknown => String::new(),
-        }
+/// Deserialize `author` from a single name or a list of names.
+fn deserialize_authors<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Authors {
+        One(String),
+        Many(Vec<String>),
     }
-}
 
-/// Escape HTML special characters.
-///
-/// Uses `Cow` to avoid allocation when no escaping is needed.
-#[inline]
-fn html_escape(s: &str) -> Cow<'_, str> {
-    // Fast path: check if escaping is needed
-    if !s.contains(['<', '>', '&', '"']) {
-        return Cow::Borrowed(s);
-    }
-
-    let mut result = String::with_capacity(s.len());
-    for c in s.chars() {
-        match c {
-            '<' => result.push_str("&lt;"),
-            '>' => result.push_str("&gt;"),
-            '&' => result.push_str("&amp;"),
-            '"' => result.push_str("&quot;"),
-            _ => result.push(c),
-        }
-    }
-    Cow::Owned(result)
+    Ok(match Option::<Authors>::deserialize(deserializer)? {
+        Some(Authors::One(name)) => vec![name],
+        Some(Authors::Many(names)) => names,
+        None => Vec::new(),
+    })
 }
 
 // ============================================================================
@@ -498,6 +670,7 @@ const fn days_to_ymd(days: i64) -> (i32, u32, u32) {
 mod tests {
     use super::*;
     use std::time::{Duration, UNIX_EPOCH};
+    use tempfile::TempDir;
 
     #[test]
     fn test_days_to_ymd_unix_epoch() {
@@ -689,6 +862,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_page_meta_readme_as_index() {
+        let mut config = SiteConfig::default();
+        config.build.output = PathBuf::from("public");
+        config.build.content = PathBuf::from("content");
+        config.build.index_names = vec!["index".into(), "README".into()];
+
+        let page = |source: &str| PageMeta::from_paths(source.into(), &config).unwrap();
+
+        let readme = page("content/posts/README.typ");
+        assert_eq!(readme.paths.html, PathBuf::from("public/posts/index.html"));
+        assert_eq!(readme.paths.url_path, "/posts/");
+        assert_eq!(page("content/README.typ").paths.url_path, "/");
+        assert_eq!(page("content/docs/index.typ").paths.url_path, "/docs/");
+        // Only the stem counts, not a name ending in it
+        assert_eq!(
+            page("content/posts/MY-README.typ").paths.url_path,
+            "/posts/my-readme/"
+        );
+
+        // By default only the root index stands for its directory
+        config.build.index_names = Vec::new();
+        let page = |source: &str| PageMeta::from_paths(source.into(), &config).unwrap();
+        assert_eq!(
+            page("content/posts/README.typ").paths.url_path,
+            "/posts/readme/"
+        );
+        assert_eq!(page("content/index.typ").paths.url_path, "/");
+        assert_eq!(page("content/a/b/index.typ").paths.url_path, "/a/b/index/");
+    }
+
+    #[test]
+    fn test_page_meta_htm_extension() {
+        let mut config = SiteConfig::default();
+        config.base.url = Some("https://example.com".into());
+        config.build.output = PathBuf::from("public");
+        config.build.content = PathBuf::from("content");
+        config.build.page_extension = "htm".into();
+
+        let page = |source: &str| PageMeta::from_paths(source.into(), &config).unwrap();
+
+        let root = page("content/index.typ");
+        assert_eq!(root.paths.html, PathBuf::from("public/index.htm"));
+        assert_eq!(root.paths.url_path, "/");
+
+        let post = page("content/posts/hello.typ");
+        assert_eq!(
+            post.paths.html,
+            PathBuf::from("public/posts/hello/index.htm")
+        );
+        assert_eq!(post.paths.url_path, "/posts/hello/");
+        assert_eq!(post.paths.full_url, "https://example.com/posts/hello/");
+
+        let nested = page("content/posts/index.typ");
+        assert_eq!(
+            nested.paths.html,
+            PathBuf::from("public/posts/index/index.htm")
+        );
+        assert_eq!(nested.paths.url_path, "/posts/index/");
+    }
+
     #[test]
     fn test_pages_empty() {
         let pages = Pages::default();
@@ -769,125 +1003,6 @@ mod tests {
         );
     }
 
-    // ========================================================================
-    // TypstElement tests
-    // ========================================================================
-
-    #[test]
-    fn test_typst_element_text() {
-        let json = r#"{"func": "text", "text": "Hello World"}"#;
-        let elem: TypstElement = serde_json::from_str(json).unwrap();
-        assert_eq!(elem.to_html(), "Hello World");
-    }
-
-    #[test]
-    fn test_typst_element_space() {
-        let json = r#"{"func": "space"}"#;
-        let elem: TypstElement = serde_json::from_str(json).unwrap();
-        assert!(matches!(elem, TypstElement::Space));
-        assert_eq!(elem.to_html(), " ");
-    }
-
-    #[test]
-    fn test_typst_element_linebreak() {
-        let json = r#"{"func": "linebreak"}"#;
-        let elem: TypstElement = serde_json::from_str(json).unwrap();
-        assert!(matches!(elem, TypstElement::Linebreak));
-        assert_eq!(elem.to_html(), "<br/>");
-    }
-
-    #[test]
-    fn test_typst_element_strike() {
-        let json = r#"{"func": "strike", "text": "deleted"}"#;
-        let elem: TypstElement = serde_json::from_str(json).unwrap();
-        assert_eq!(elem.to_html(), "<s>deleted</s>");
-    }
-
-    #[test]
-    fn test_typst_element_link() {
-        let json = r#"{"func": "link", "dest": "https://example.com", "body": {"func": "text", "text": "click here"}}"#;
-        let elem: TypstElement = serde_json::from_str(json).unwrap();
-        if let TypstElement::Link { dest, body } = &elem {
-            assert_eq!(dest, "https://example.com");
-            assert!(matches!(body.as_ref(), TypstElement::Text { text } if text == "click here"));
-        } else {
-            panic!("Expected Link element");
-        }
-        assert_eq!(
-            elem.to_html(),
-            r#"<a href="https://example.com">click here</a>"#
-        );
-    }
-
-    #[test]
-    fn test_typst_element_sequence() {
-        let json = r#"{"func": "sequence", "children": [{"func": "text", "text": "Hello"}, {"func": "space"}, {"func": "text", "text": "World"}]}"#;
-        let elem: TypstElement = serde_json::from_str(json).unwrap();
-        if let TypstElement::Sequence { children } = &elem {
-            assert_eq!(children.len(), 3);
-            assert!(matches!(&children[0], TypstElement::Text { text } if text == "Hello"));
-            assert!(matches!(&children[1], TypstElement::Space));
-            assert!(matches!(&children[2], TypstElement::Text { text } if text == "World"));
-        } else {
-            panic!("Expected Sequence element");
-        }
-        assert_eq!(elem.to_html(), "Hello World");
-    }
-
-    #[test]
-    fn test_typst_element_unknown() {
-        let json = r#"{"func": "some_unknown_func"}"#;
-        let elem: TypstElement = serde_json::from_str(json).unwrap();
-        assert!(matches!(elem, TypstElement::Unknown));
-        assert_eq!(elem.to_html(), "");
-    }
-
-    #[test]
-    fn test_typst_element_nested_sequence() {
-        let json = r#"{
-            "func": "sequence",
-            "children": [
-                {"func": "text", "text": "Start "},
-                {"func": "link", "dest": "https://rust-lang.org", "body": {"func": "text", "text": "Rust"}},
-                {"func": "text", "text": " is great"}
-            ]
-        }"#;
-        let elem: TypstElement = serde_json::from_str(json).unwrap();
-        assert_eq!(
-            elem.to_html(),
-            r#"Start <a href="https://rust-lang.org">Rust</a> is great"#
-        );
-    }
-
-    // ========================================================================
-    // html_escape tests
-    // ========================================================================
-
-    #[test]
-    fn test_html_escape_plain() {
-        assert_eq!(html_escape("hello world"), "hello world");
-    }
-
-    #[test]
-    fn test_html_escape_special_chars() {
-        assert_eq!(html_escape("<script>"), "&lt;script&gt;");
-        assert_eq!(html_escape("a & b"), "a &amp; b");
-        assert_eq!(html_escape("say \"hi\""), "say &quot;hi&quot;");
-    }
-
-    #[test]
-    fn test_html_escape_mixed() {
-        assert_eq!(
-            html_escape("<a href=\"#\">link & text</a>"),
-            "&lt;a href=&quot;#&quot;&gt;link &amp; text&lt;/a&gt;"
-        );
-    }
-
-    #[test]
-    fn test_html_escape_empty() {
-        assert_eq!(html_escape(""), "");
-    }
-
     // ========================================================================
     // ContentMeta summary deserialization tests
     // ========================================================================
@@ -921,6 +1036,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_content_meta_summary_emph_strong_raw() {
+        // As serialized by Typst for `[An _em *x*_ *bold* `a<b`]`
+        let json = r#"{"summary": {"func": "sequence", "children": [
+            {"func": "text", "text": "An"},
+            {"func": "space"},
+            {"func": "emph", "body": {"func": "sequence", "children": [
+                {"func": "text", "text": "em"},
+                {"func": "space"},
+                {"func": "strong", "body": {"func": "text", "text": "x"}}
+            ]}},
+            {"func": "space"},
+            {"func": "strong", "body": {"func": "text", "text": "bold & co"}},
+            {"func": "space"},
+            {"block": false, "func": "raw", "text": "a<b"}
+        ]}}"#;
+        let meta: ContentMeta = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            meta.summary.as_deref(),
+            Some(
+                "An <em>em <strong>x</strong></em> <strong>bold &amp; co</strong> <code>a&lt;b</code>"
+            )
+        );
+    }
+
     #[test]
     fn test_content_meta_summary_none() {
         let json = r#"{"title": "No Summary"}"#;
@@ -962,10 +1102,130 @@ mod tests {
         assert_eq!(meta.summary, Some("This is the summary".to_string()));
         assert_eq!(meta.date, Some("2025-01-15".to_string()));
         assert_eq!(meta.update, Some("2025-01-20".to_string()));
-        assert_eq!(meta.author, Some("Alice".to_string()));
+        assert_eq!(meta.authors, vec!["Alice".to_string()]);
         assert!(!meta.draft);
     }
 
+    #[test]
+    fn test_content_meta_authors_string_or_list() {
+        let one: ContentMeta = serde_json::from_str(r#"{"author": "Alice"}"#).unwrap();
+        assert_eq!(one.authors, ["Alice"]);
+
+        let many: ContentMeta = serde_json::from_str(r#"{"author": ["Alice", "Bob"]}"#).unwrap();
+        assert_eq!(many.authors, ["Alice", "Bob"]);
+
+        let none: ContentMeta = serde_json::from_str(r#"{"author": null}"#).unwrap();
+        assert!(none.authors.is_empty());
+        let missing: ContentMeta = serde_json::from_str("{}").unwrap();
+        assert!(missing.authors.is_empty());
+    }
+
+    #[test]
+    fn test_yaml_companion_meta() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("post.typ");
+        fs::write(&source, "= Post").unwrap();
+        fs::write(
+            dir.path().join("post.yaml"),
+            "title: From YAML\ndate: 2024-03-01\ntags: [rust, web]\n",
+        )
+        .unwrap();
+
+        let meta = ContentMeta::from_sources(&source, dir.path(), None).unwrap();
+        assert_eq!(meta.title.as_deref(), Some("From YAML"));
+        assert_eq!(meta.date.as_deref(), Some("2024-03-01"));
+        assert_eq!(meta.tags, ["rust", "web"]);
+        assert!(is_companion(&dir.path().join("post.yaml")));
+
+        // Inline `<tola-meta>` keys win, the companion fills in the rest
+        let inline = serde_json::json!({ "title": "Inline" });
+        let meta = ContentMeta::from_sources(&source, dir.path(), Some(inline)).unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Inline"));
+        assert_eq!(meta.date.as_deref(), Some("2024-03-01"));
+        assert_eq!(meta.tags, ["rust", "web"]);
+    }
+
+    #[test]
+    fn test_dir_defaults_scalar_override() {
+        let dir = TempDir::new().unwrap();
+        let posts = dir.path().join("blog/rust");
+        fs::create_dir_all(&posts).unwrap();
+        fs::write(
+            dir.path().join("blog/_defaults.toml"),
+            "author = \"Alice\"\n",
+        )
+        .unwrap();
+        fs::write(
+            posts.join("_defaults.toml"),
+            "author = \"Bob\"\ndraft = true\n",
+        )
+        .unwrap();
+        let source = posts.join("post.typ");
+        fs::write(&source, "= Post").unwrap();
+
+        // The deeper directory wins over its parent
+        let meta = ContentMeta::from_sources(&source, dir.path(), None).unwrap();
+        assert_eq!(meta.authors, ["Bob"]);
+        assert!(meta.draft);
+
+        // The page's own front matter wins over every directory
+        let inline = serde_json::json!({ "author": "Carol", "draft": false });
+        let meta = ContentMeta::from_sources(&source, dir.path(), Some(inline)).unwrap();
+        assert_eq!(meta.authors, ["Carol"]);
+        assert!(!meta.draft);
+
+        // Outside the subtree nothing cascades
+        let other = dir.path().join("about.typ");
+        fs::write(&other, "= About").unwrap();
+        assert!(ContentMeta::from_sources(&other, dir.path(), None).is_none());
+        assert!(is_dir_defaults(&posts.join("_defaults.toml")));
+    }
+
+    #[test]
+    fn test_dir_defaults_wrong_type_skipped() {
+        let dir = TempDir::new().unwrap();
+        let posts = dir.path().join("posts");
+        fs::create_dir_all(&posts).unwrap();
+        fs::write(dir.path().join("_defaults.toml"), "author = \"Alice\"\n").unwrap();
+        fs::write(posts.join("_defaults.toml"), "draft = \"yes\"\n").unwrap();
+        let source = posts.join("post.typ");
+        fs::write(&source, "= Post").unwrap();
+
+        // The unusable file is skipped instead of dropping the page's metadata
+        let inline = serde_json::json!({ "title": "Post" });
+        let meta = ContentMeta::from_sources(&source, dir.path(), Some(inline)).unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Post"));
+        assert_eq!(meta.authors, ["Alice"]);
+        assert!(!meta.draft);
+    }
+
+    #[test]
+    fn test_dir_defaults_tag_union() {
+        let dir = TempDir::new().unwrap();
+        let posts = dir.path().join("blog/rust");
+        fs::create_dir_all(&posts).unwrap();
+        fs::write(
+            dir.path().join("blog/_defaults.toml"),
+            "tags = [\"blog\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            posts.join("_defaults.toml"),
+            "tags = [\"rust\", \"blog\"]\n",
+        )
+        .unwrap();
+        let source = posts.join("post.typ");
+        fs::write(&source, "= Post").unwrap();
+
+        let meta = ContentMeta::from_sources(&source, dir.path(), None).unwrap();
+        assert_eq!(meta.tags, ["blog", "rust"]);
+
+        let inline = serde_json::json!({ "title": "Post", "tags": ["async"] });
+        let meta = ContentMeta::from_sources(&source, dir.path(), Some(inline)).unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Post"));
+        assert_eq!(meta.tags, ["blog", "rust", "async"]);
+    }
+
     #[test]
     fn test_content_meta_draft_default() {
         let json = r#"{"title": "Draft Test"}"#;
@@ -7,6 +7,11 @@
 //! - **assets**: Copy and optimize static assets
 //! - **watch**: Incremental builds on file changes
 //! - **deps**: Dependency tracking for precise rebuilds
+//! - **manifest**: Content-hash manifest for `--incremental` builds
+//! - **orphans**: Detect/prune stale output files
+//! - **links**: Detect broken internal links in the output
+//! - **list**: List pages and their output paths (`tola build --list`)
+//! - **taxonomy**: Render per-tag/per-section index pages
 //!
 //! # Build Flow
 //!
@@ -19,10 +24,19 @@
 
 pub mod assets;
 pub mod deps;
+pub mod links;
+pub mod list;
+pub mod manifest;
 pub mod meta;
+pub mod orphans;
 pub mod pages;
+pub mod taxonomy;
 pub mod watch;
 
+use crate::compiler::meta::PageMeta;
+use crate::config::SiteConfig;
+use anyhow::{Result, bail};
+use rustc_hash::FxHashMap;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use walkdir::WalkDir;
@@ -33,6 +47,8 @@ use walkdir::WalkDir;
 
 pub use assets::process_asset;
 pub use assets::process_rel_asset;
+pub use links::check_links;
+pub use orphans::reconcile_output;
 pub use pages::collect_metadata;
 pub use pages::compile_pages_with_data;
 pub use watch::process_watched_files;
@@ -64,6 +80,55 @@ pub fn collect_all_files(dir: &Path) -> Vec<PathBuf> {
         .collect()
 }
 
+/// Check if a content file is a partial: some component of its path below
+/// the content directory starts with `[build].ignore_prefix`.
+pub fn is_partial(path: &Path, config: &SiteConfig) -> bool {
+    let prefix = &config.build.ignore_prefix;
+    if prefix.is_empty() {
+        return false;
+    }
+    path.strip_prefix(&config.build.content).is_ok_and(|rel| {
+        rel.components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with(prefix.as_str()))
+    })
+}
+
+/// Check if a `.typ` file is named like a directory index (`[build].index_names`).
+pub fn is_index_page(path: &Path, config: &SiteConfig) -> bool {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| config.build.index_names.iter().any(|name| name == stem))
+}
+
+/// Fail if several sources would be written to the same output, e.g.
+/// `posts.typ` and `posts/index.typ`, or `index.typ` and `README.typ`
+/// under `[build].index_names`.
+pub fn check_output_conflicts(files: &[PathBuf], config: &SiteConfig) -> Result<()> {
+    let mut outputs: FxHashMap<PathBuf, &Path> = FxHashMap::default();
+    for file in files {
+        let html = PageMeta::html_path(file, config)?;
+        if let Some(other) = outputs.get(&html) {
+            bail!(
+                "conflicting pages {} and {} both write {}",
+                other.display(),
+                file.display(),
+                html.display()
+            );
+        }
+        outputs.insert(html, file);
+    }
+    Ok(())
+}
+
+/// Collect the `.typ` files that become pages (partials excluded).
+pub fn collect_page_files(config: &SiteConfig) -> Vec<PathBuf> {
+    collect_all_files(&config.build.content)
+        .into_iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "typ"))
+        .filter(|p| !is_partial(p, config))
+        .collect()
+}
+
 /// Check if destination is up-to-date compared to source and dependencies.
 pub fn is_up_to_date(src: &Path, dst: &Path, deps_mtime: Option<SystemTime>) -> bool {
     let Ok(src_meta) = src.metadata() else {
@@ -94,3 +159,82 @@ pub fn is_up_to_date(src: &Path, dst: &Path, deps_mtime: Option<SystemTime>) ->
 
     true
 }
+
+/// Check if a copied file is up-to-date: by content hash under
+/// `--incremental`, by mtime otherwise. Never under `--force-rebuild`.
+pub fn is_copy_up_to_date(src: &Path, dst: &Path, config: &SiteConfig) -> bool {
+    if config.build.force_rebuild {
+        false
+    } else if config.build.incremental {
+        manifest::MANIFEST.read().is_fresh(src, dst)
+    } else {
+        is_up_to_date(src, dst, None)
+    }
+}
+
+/// Record a copied file in the manifest (no-op unless `--incremental`).
+pub fn record_copy(src: &Path, dst: &Path, config: &SiteConfig) {
+    if config.build.incremental {
+        manifest::MANIFEST.write().record(src, [], dst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_partial() {
+        let mut config = SiteConfig::default();
+        config.build.content = "/site/content".into();
+        let partial = |p: &str| is_partial(Path::new(p), &config);
+
+        assert!(partial("/site/content/_partials/x.typ"));
+        assert!(partial("/site/content/posts/_note.typ"));
+        assert!(!partial("/site/content/posts/hello.typ"));
+        // Only components below the content dir count
+        assert!(!partial("/site/content/index.typ"));
+
+        config.build.ignore_prefix = String::new();
+        assert!(!is_partial(
+            Path::new("/site/content/_partials/x.typ"),
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_check_output_conflicts() {
+        let mut config = SiteConfig::default();
+        config.build.content = PathBuf::from("/c");
+        config.build.output = PathBuf::from("/out");
+        config.build.index_names = vec!["index".into(), "README".into()];
+        let files = |paths: &[&str]| paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+        let conflict = |paths: &[&str], config: &SiteConfig| {
+            check_output_conflicts(&files(paths), config)
+                .err()
+                .map(|e| e.to_string())
+        };
+
+        // One index per directory is fine
+        let ok = ["/c/index.typ", "/c/posts/README.typ", "/c/posts/a.typ"];
+        assert_eq!(conflict(&ok, &config), None);
+
+        let clash = ["/c/posts/index.typ", "/c/posts/README.typ"];
+        let err = conflict(&clash, &config).unwrap();
+        assert!(
+            err.contains("posts/index.typ") && err.contains("posts/README.typ"),
+            "{err}"
+        );
+        // A page and the index of the directory named like it
+        let err = conflict(&["/c/posts.typ", "/c/posts/index.typ"], &config).unwrap();
+        assert!(err.contains("/out/posts/index.html"), "{err}");
+
+        // Without `index_names`, nested index pages get their own directory
+        config.build.index_names = Vec::new();
+        assert_eq!(conflict(&clash, &config), None);
+        assert_eq!(
+            conflict(&["/c/posts.typ", "/c/posts/index.typ"], &config),
+            None
+        );
+    }
+}
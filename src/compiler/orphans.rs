@@ -0,0 +1,267 @@
+//! Orphaned output detection.
+//!
+//! Incremental builds never delete anything, so removing a source file leaves
+//! its HTML (and extracted SVGs) behind in the output directory. After a build,
+//! the expected output set is reconstructed from:
+//!
+//! - Pages: `PageMeta.paths.html` (plus `svg-*` files next to them)
+//! - Assets: selected files in `build.assets`, non-`.typ` files in `build.content`,
+//!   and `[build].copy` destinations
+//! - Generated files: rss feed, sitemap, Tailwind/Sass CSS, taxonomy index
+//!   pages, alias redirect stubs, and the virtual data directory
+//! - Referenced files copied by `[build].auto_copy_referenced` that a page
+//!   still links to
+//!
+//! Anything else in the output directory is an orphan. Hidden entries (`.git`,
+//! `.enhance-*.css`, ...) and paths in `[build].keep` are never touched.
+
+use super::{
+    assets::{copy_targets, is_asset_selected, referenced_rel_path, sass_output, tailwind_output},
+    collect_all_files,
+    links::{internal_links, link_target},
+    meta::{AssetMeta, Pages, is_companion, is_dir_defaults},
+    taxonomy::taxonomy_outputs,
+};
+use crate::{config::SiteConfig, generator::aliases::alias_outputs, log, report::BUILD_STATS};
+use anyhow::Result;
+use rustc_hash::FxHashSet;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// Find orphaned output files and delete them if `[build].prune_orphans` is set.
+///
+/// Returns the orphaned paths (already deleted when pruning).
+pub fn reconcile_output(config: &SiteConfig, pages: &Pages) -> Result<Vec<PathBuf>> {
+    let orphans = find_orphans(config, pages);
+    if orphans.is_empty() {
+        return Ok(orphans);
+    }
+
+    let output = &config.build.output;
+    if config.build.prune_orphans {
+        for path in &orphans {
+            fs::remove_file(path)?;
+            log!("prune"; "{}", display_rel(path, output));
+        }
+        remove_empty_dirs(output);
+    } else {
+        for path in &orphans {
+            log!("orphan"; "{}", display_rel(path, output));
+        }
+        let msg = format!(
+            "{} orphaned files in output, set `prune_orphans = true` to delete",
+            orphans.len()
+        );
+        log!("warn"; "{msg}");
+        BUILD_STATS.warn(msg);
+    }
+
+    Ok(orphans)
+}
+
+/// Collect output files not produced by the current build.
+fn find_orphans(config: &SiteConfig, pages: &Pages) -> Vec<PathBuf> {
+    let output = &config.build.output;
+    let output_dir = config.paths().output_dir();
+
+    let mut expected = planned_outputs(config, pages);
+    expected.extend(alias_outputs(config, pages));
+    // Directories holding a page may also hold its extracted `svg-*` files
+    let page_dirs: FxHashSet<PathBuf> = pages
+        .iter()
+        .filter_map(|p| p.paths.html.parent().map(Path::to_path_buf))
+        .collect();
+
+    let data_dir = output_dir.join(&config.build.data);
+    let keep: Vec<PathBuf> = config.build.keep.iter().map(|k| output.join(k)).collect();
+
+    WalkDir::new(output)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .map(walkdir::DirEntry::into_path)
+        .filter(|path| !expected.contains(path))
+        .filter(|path| !path.starts_with(&data_dir))
+        .filter(|path| !keep.iter().any(|k| path.starts_with(k)))
+        .filter(|path| !is_page_svg(path, &page_dirs))
+        .collect()
+}
+
+/// Every file the build writes, except alias redirect stubs and extracted SVGs.
+pub fn planned_outputs(config: &SiteConfig, pages: &Pages) -> FxHashSet<PathBuf> {
+    let output_dir = config.paths().output_dir();
+
+    let mut expected: FxHashSet<PathBuf> = pages.iter().map(|p| p.paths.html.clone()).collect();
+    expected.extend(
+        collect_all_files(&config.build.assets)
+            .into_iter()
+            .filter(|p| is_asset_selected(p, config))
+            .filter_map(|p| AssetMeta::from_source(p, config).ok())
+            .map(|meta| meta.paths.dest),
+    );
+    expected.extend(
+        collect_all_files(&config.build.content)
+            .into_iter()
+            .filter(|p| p.extension().is_none_or(|ext| ext != "typ"))
+            .filter(|p| !is_companion(p) && !is_dir_defaults(p))
+            .filter_map(|p| {
+                p.strip_prefix(&config.build.content)
+                    .ok()
+                    .map(|rel| output_dir.join(rel))
+            }),
+    );
+    expected.extend(copy_targets(config).into_iter().map(|(_, dest)| dest));
+    if config.build.css.tailwind.enable {
+        expected.extend(
+            config
+                .build
+                .css
+                .tailwind
+                .entries()
+                .iter()
+                .filter_map(|entry| tailwind_output(entry, config).ok()),
+        );
+    }
+    if config.build.css.sass.enable
+        && let Ok(path) = sass_output(config)
+    {
+        expected.insert(path);
+    }
+    expected.extend(taxonomy_outputs(config));
+    expected.extend(referenced_copies(config, pages));
+    if config.build.rss.enable {
+        expected.insert(output_dir.join(&config.build.rss.path));
+    }
+    if config.build.sitemap.enable {
+        expected.insert(output_dir.join(&config.build.sitemap.path));
+    }
+    expected
+}
+
+/// Copies made by `[build].auto_copy_referenced` for the links in the pages'
+/// HTML, which also covers pages skipped as up to date.
+fn referenced_copies(config: &SiteConfig, pages: &Pages) -> FxHashSet<PathBuf> {
+    if !config.build.auto_copy_referenced {
+        return FxHashSet::default();
+    }
+    let output = &config.build.output;
+    let output_dir = config.paths().output_dir();
+    let is_copy = |target: &Path| {
+        target
+            .strip_prefix(&output_dir)
+            .is_ok_and(|rel| referenced_rel_path(&config.get_root().join(rel), config).is_some())
+    };
+
+    pages
+        .iter()
+        .filter_map(|page| fs::read_to_string(&page.paths.html).ok())
+        .flat_map(|html| {
+            internal_links(&html)
+                .map(|link| link_target(output, link))
+                .collect::<Vec<_>>()
+        })
+        .filter(|target| is_copy(target))
+        .collect()
+}
+
+/// Check if `path` is an extracted SVG (`svg-N.svg`/`.avif`/`.png`) next to a page.
+fn is_page_svg(path: &Path, page_dirs: &FxHashSet<PathBuf>) -> bool {
+    let is_svg_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with("svg-"));
+    is_svg_name && path.parent().is_some_and(|dir| page_dirs.contains(dir))
+}
+
+/// Remove directories left empty after pruning (deepest first).
+fn remove_empty_dirs(output: &Path) {
+    let dirs: Vec<PathBuf> = WalkDir::new(output)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_dir())
+        .map(walkdir::DirEntry::into_path)
+        .collect();
+
+    // Pre-order traversal, reversed: children are visited before parents
+    for dir in dirs.into_iter().rev() {
+        // Fails (and is ignored) for non-empty directories
+        let _ = fs::remove_dir(dir);
+    }
+}
+
+fn display_rel(path: &Path, output: &Path) -> String {
+    path.strip_prefix(output)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build::build_site;
+    use tempfile::TempDir;
+
+    fn make_site() -> (TempDir, SiteConfig) {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        fs::write(content.join("index.typ"), "= Home").unwrap();
+        fs::write(content.join("old.typ"), "= Old").unwrap();
+
+        let mut config = SiteConfig::default();
+        config.set_root(dir.path());
+        config.build.content = content;
+        config.build.output = dir.path().join("public");
+        config.build.assets = dir.path().join("assets");
+        config.build.typst.use_lib = true;
+        (dir, config)
+    }
+
+    #[test]
+    fn test_prune_removes_deleted_source_html() {
+        let (dir, mut config) = make_site();
+        let (_, pages) = build_site(&config, true).unwrap();
+        assert!(reconcile_output(&config, &pages).unwrap().is_empty());
+
+        let stale = config.build.output.join("old/index.html");
+        assert!(stale.exists());
+
+        fs::remove_file(dir.path().join("content/old.typ")).unwrap();
+        config.build.prune_orphans = true;
+        let (_, pages) = build_site(&config, true).unwrap();
+        let orphans = reconcile_output(&config, &pages).unwrap();
+
+        assert_eq!(orphans, vec![stale.clone()]);
+        assert!(!stale.exists());
+        assert!(!stale.parent().unwrap().exists());
+        assert!(config.build.output.join("index.html").exists());
+        // Hidden directories (deploy repo) are left alone, even if empty
+        assert!(config.build.output.join(".git/refs/tags").exists());
+    }
+
+    #[test]
+    fn test_report_only_and_keep_list() {
+        let (_dir, mut config) = make_site();
+        let (_, pages) = build_site(&config, true).unwrap();
+
+        let extra = config.build.output.join("CNAME");
+        let stray = config.build.output.join("stray.html");
+        fs::write(&extra, "example.com").unwrap();
+        fs::write(&stray, "").unwrap();
+        config.build.keep = vec!["CNAME".into()];
+
+        let orphans = reconcile_output(&config, &pages).unwrap();
+        assert_eq!(orphans, vec![stray.clone()]);
+        // Without prune_orphans, files are only reported
+        assert!(stray.exists());
+        assert!(extra.exists());
+    }
+}
@@ -1,15 +1,34 @@
-use crate::compiler::meta::{ContentMeta, PageMeta, Pages, TOLA_META_LABEL};
-use crate::compiler::{collect_all_files, is_up_to_date};
+use crate::compiler::manifest::MANIFEST;
+use crate::compiler::meta::{
+    ContentMeta, PageMeta, Pages, TOLA_META_LABEL, companion_path, dir_defaults_paths,
+};
+use crate::compiler::{
+    check_output_conflicts, collect_all_files, collect_page_files, is_up_to_date,
+};
 use crate::data::{GLOBAL_SITE_DATA, PageData};
-use crate::utils::exec::FilterRule;
+use crate::report::BUILD_STATS;
+use crate::utils::category::content_section;
+use crate::utils::date::DateTimeUtc;
+use crate::utils::exec::{self, FilterRule, strip_ansi};
 use crate::utils::minify::{MinifyType, minify};
-use crate::utils::xml::process_html;
-use crate::{config::SiteConfig, exec, log, typst_lib};
+use crate::utils::xml::{
+    link::relative_link_depth,
+    process_page_html,
+    summary::{extract_summary, split_more},
+};
+use crate::{config::SiteConfig, log, typst_lib};
 use anyhow::Result;
+use gix::{bstr::ByteSlice, glob::wildmatch};
 use rayon::prelude::*;
+use serde::Serialize;
+use std::ffi::OsString;
 use std::fs;
-use std::path::Path;
-use std::time::SystemTime;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use thiserror::Error;
 
 /// Skip known HTML export warnings (used by `compile_cli`).
 const TYPST_FILTER: FilterRule = FilterRule::new(&[
@@ -74,15 +93,15 @@ pub fn process_page(
     let mut page = PageMeta::from_paths(path.to_path_buf(), config)?;
 
     // Check if up-to-date
-    if !clean && is_up_to_date(path, &page.paths.html, deps_mtime) {
+    if !clean && is_page_up_to_date(path, &page.paths.html, deps_mtime, config) {
         return Ok(None);
     }
 
     // Compile the page and get metadata
     let (html_content, content_meta) = compile_meta(path, config)?;
 
-    // Skip drafts
-    if is_draft(content_meta.as_ref()) {
+    // Skip drafts and scheduled (future-dated) pages
+    if is_unpublished(content_meta.as_ref(), config) {
         // Remove from global data if it was previously published
         // (This handles the case where a page is marked as draft after being published)
         return Ok(None);
@@ -90,9 +109,10 @@ pub fn process_page(
 
     page.content_meta = content_meta;
     page.compiled_html = Some(html_content);
+    warn_update_before_date(&page);
 
     // Update global site data for virtual JSON files
-    GLOBAL_SITE_DATA.insert_page(page_meta_to_data(&page));
+    GLOBAL_SITE_DATA.insert_page(page_meta_to_data(&page, config));
 
     // Write the page
     write_page(&page, config, true, None, log_file)?;
@@ -113,7 +133,7 @@ fn write_page(
     log_file: bool,
 ) -> Result<()> {
     // Check if up-to-date (only for batch mode, process_page already checked)
-    if !clean && is_up_to_date(&page.paths.source, &page.paths.html, deps_mtime) {
+    if !clean && is_page_up_to_date(&page.paths.source, &page.paths.html, deps_mtime, config) {
         return Ok(());
     }
 
@@ -134,30 +154,149 @@ fn write_page(
         compile_cli(&page.paths.source, config)?
     };
 
+    // Raw pages are written exactly as Typst exported them
+    if page.content_meta.as_ref().is_some_and(|c| c.raw) {
+        fs::write(&page.paths.html, &html_content)?;
+        return Ok(());
+    }
+
     // Post-process and write
-    // Check if the source file was named "index.typ" for relative path resolution
-    let is_source_index = page
-        .paths
-        .source
-        .file_stem()
-        .is_some_and(|stem| stem == "index");
-    let html_content = process_html(&page.paths.html, &html_content, config, is_source_index)?;
+    // Relative links are source-relative; climb back from the output depth
+    let link_depth = relative_link_depth(&page.paths.source, &page.paths.html, config);
+    let html_content = process_page_html(page, &html_content, config, link_depth)?;
     let html_content = minify(MinifyType::Html(&html_content), config);
     fs::write(&page.paths.html, &*html_content)?;
 
     Ok(())
 }
 
+/// Check a page's output against its source, front-matter companion and
+/// directory defaults.
+fn is_page_up_to_date(
+    source: &Path,
+    html: &Path,
+    deps_mtime: Option<SystemTime>,
+    config: &SiteConfig,
+) -> bool {
+    is_up_to_date(source, html, deps_mtime)
+        && companion_path(source).is_none_or(|companion| is_up_to_date(&companion, html, None))
+        && dir_defaults_paths(source, &config.build.content)
+            .iter()
+            .all(|defaults| is_up_to_date(defaults, html, None))
+}
+
+/// A file whose compilation exceeded `[build.typst].timeout_secs`.
+#[derive(Debug, Error)]
+#[error("{file}: compilation timed out after {secs}s, skipped")]
+pub struct CompileTimeout {
+    pub file: String,
+    pub secs: u64,
+}
+
+impl CompileTimeout {
+    fn new(path: &Path, config: &SiteConfig) -> Self {
+        let file = path.strip_prefix(&config.build.content).unwrap_or(path);
+        Self {
+            file: file.display().to_string(),
+            secs: config.build.typst.timeout_secs,
+        }
+    }
+}
+
+/// Run `compile` for `path`, giving up after `[build.typst].timeout_secs`.
+///
+/// Typst compilation can't be cancelled, so in lib mode it runs on a thread
+/// of its own that is left to finish on timeout, its result dropped. In CLI
+/// mode the `typst` process itself is killed (see [`run_typst`]).
+fn with_timeout<T: Send + 'static>(
+    path: &Path,
+    config: &SiteConfig,
+    compile: fn(&Path, &SiteConfig) -> Result<T>,
+) -> Result<T> {
+    let secs = config.build.typst.timeout_secs;
+    if secs == 0 || !config.build.typst.use_lib {
+        return compile(path, config);
+    }
+
+    // The deadline starts now, however busy other compilations keep the CPU
+    let (tx, rx) = mpsc::channel();
+    let (owned_path, owned_config) = (path.to_path_buf(), config.clone());
+    thread::Builder::new()
+        .name("tola-compile".into())
+        .spawn(move || {
+            let _ = tx.send(compile(&owned_path, &owned_config));
+        })?;
+
+    match rx.recv_timeout(Duration::from_secs(secs)) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(CompileTimeout::new(path, config).into()),
+        Err(RecvTimeoutError::Disconnected) => {
+            let file = CompileTimeout::new(path, config).file;
+            Err(anyhow::anyhow!("{file}: compilation panicked"))
+        }
+    }
+}
+
+/// Warn about and skip a page that timed out; other errors pass through.
+fn skip_timed_out<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) => {
+            let timeout = e.downcast::<CompileTimeout>()?;
+            log!("warn"; "{timeout}");
+            BUILD_STATS.warn(timeout.to_string());
+            Ok(None)
+        }
+    }
+}
+
 /// Compile a typst file and extract metadata (lib or CLI mode).
 ///
-/// Also records dependencies for incremental rebuild tracking.
+/// Also records dependencies for incremental rebuild tracking. Fails with
+/// [`CompileTimeout`] when `[build.typst].timeout_secs` is exceeded.
 pub fn compile_meta(path: &Path, config: &SiteConfig) -> Result<(Vec<u8>, Option<ContentMeta>)> {
+    let start = Instant::now();
+    let result = with_timeout(path, config, compile_meta_inner);
+    let elapsed = start.elapsed();
+
+    let rel = path
+        .strip_prefix(&config.build.content)
+        .unwrap_or(path)
+        .display()
+        .to_string();
+    if config.build.verbose {
+        log!("compile"; "compiled {rel} in {}ms", elapsed.as_millis());
+    }
+    BUILD_STATS.record_page(rel, elapsed);
+    let (html, mut meta) = result?;
+    fill_summary(&mut meta, &html, config);
+    Ok((html, meta))
+}
+
+/// Split the body at a "read more" marker, and fill a missing `summary` from
+/// the text before it, or the first body paragraph (`[build].auto_summary`).
+fn fill_summary(meta: &mut Option<ContentMeta>, html: &[u8], config: &SiteConfig) {
+    let Some(meta) = meta else {
+        return;
+    };
+
+    meta.summary_html = split_more(html);
+    if meta.summary.is_none() {
+        meta.summary = meta.summary_html.clone().or_else(|| {
+            config
+                .build
+                .auto_summary
+                .then(|| extract_summary(html, config.build.summary_length))
+                .flatten()
+        });
+    }
+}
+
+fn compile_meta_inner(path: &Path, config: &SiteConfig) -> Result<(Vec<u8>, Option<ContentMeta>)> {
     if config.build.typst.use_lib {
         let root = config.get_root();
         let result = typst_lib::compile_meta(path, root, TOLA_META_LABEL)?;
-        let meta = result
-            .metadata
-            .and_then(|json| serde_json::from_value(json).ok());
+        let meta = ContentMeta::from_sources(path, &config.build.content, result.metadata);
 
         // Record dependencies for incremental rebuild
         super::deps::DEPENDENCY_GRAPH
@@ -177,9 +316,7 @@ pub fn query_meta(path: &Path, config: &SiteConfig) -> Option<ContentMeta> {
     if config.build.typst.use_lib {
         let root = config.get_root();
         let result = typst_lib::compile_meta(path, root, TOLA_META_LABEL).ok()?;
-        result
-            .metadata
-            .and_then(|json| serde_json::from_value(json).ok())
+        ContentMeta::from_sources(path, &config.build.content, result.metadata)
     } else {
         query_meta_cli(path, config)
     }
@@ -189,22 +326,78 @@ pub fn query_meta(path: &Path, config: &SiteConfig) -> Option<ContentMeta> {
 // Internal: CLI helpers
 // ============================================================================
 
+/// Build `typst <subcommand> --input k=v ...` from the configured command.
+fn typst_command(config: &SiteConfig, subcommand: &str) -> Vec<String> {
+    let mut command = config.build.typst.command.clone();
+    command.push(subcommand.to_string());
+    for (key, value) in &config.build.typst.inputs {
+        command.push("--input".to_string());
+        command.push(format!("{key}={value}"));
+    }
+    command
+}
+
+/// Run `typst <subcommand> <args>` for `path`, killing it once it exceeds
+/// `[build.typst].timeout_secs` (failing with [`CompileTimeout`]).
+fn run_typst(
+    path: &Path,
+    config: &SiteConfig,
+    subcommand: &str,
+    args: &[OsString],
+    filter: &'static FilterRule,
+) -> Result<Output> {
+    let command: Vec<OsString> = typst_command(config, subcommand)
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    match config.build.typst.timeout_secs {
+        0 => exec::exec(None, &command, args, filter, false),
+        secs => exec::exec_timeout(None, &command, args, filter, Duration::from_secs(secs))?
+            .ok_or_else(|| CompileTimeout::new(path, config).into()),
+    }
+}
+
 /// Compile using typst CLI.
 fn compile_cli(source: &Path, config: &SiteConfig) -> Result<Vec<u8>> {
     let root = config.get_root();
 
-    let output = exec!(
-        pty=false;
-        filter=&TYPST_FILTER;
-        &config.build.typst.command;
-        "compile", "--features", "html", "--format", "html",
-        "--font-path", root, "--root", root,
-        source, "-"
-    )?;
-
+    let args: [OsString; 10] = [
+        "--features".into(),
+        "html".into(),
+        "--format".into(),
+        "html".into(),
+        "--font-path".into(),
+        root.into(),
+        "--root".into(),
+        root.into(),
+        source.into(),
+        "-".into(),
+    ];
+    let output = run_typst(source, config, "compile", &args, &TYPST_FILTER)?;
+
+    // Non-fatal warnings count under `--strict`, like in lib mode
+    let rel = source.strip_prefix(root).unwrap_or(source);
+    for warning in cli_warnings(rel, &output.stderr) {
+        BUILD_STATS.warn(warning);
+    }
     Ok(output.stdout)
 }
 
+/// `"<rel>: <message>"` for each `warning:` line `typst compile` printed,
+/// skipping the ones [`TYPST_FILTER`] hides.
+fn cli_warnings(rel: &Path, stderr: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(stderr)
+        .lines()
+        .filter_map(|line| {
+            let line = strip_ansi(line);
+            let line = line.trim();
+            let message = line.strip_prefix("warning:")?;
+            (!TYPST_FILTER.should_skip(line))
+                .then(|| format!("{}: {}", rel.display(), message.trim()))
+        })
+        .collect()
+}
+
 /// Query metadata using typst CLI.
 fn query_meta_cli(path: &Path, config: &SiteConfig) -> Option<ContentMeta> {
     use crate::utils::exec::SILENT_FILTER;
@@ -213,18 +406,28 @@ fn query_meta_cli(path: &Path, config: &SiteConfig) -> Option<ContentMeta> {
     // Format label with angle brackets for typst query selector
     let label_selector = format!("<{TOLA_META_LABEL}>");
 
-    let output = exec!(
-        filter=&SILENT_FILTER;
-        &config.build.typst.command;
-        "query", "--features", "html", "--format", "json",
-        "--font-path", root, "--root", root,
-        path, &label_selector, "--field", "value", "--one"
-    );
-
-    output.ok().and_then(|out| {
+    let args: [OsString; 13] = [
+        "--features".into(),
+        "html".into(),
+        "--format".into(),
+        "json".into(),
+        "--font-path".into(),
+        root.into(),
+        "--root".into(),
+        root.into(),
+        path.into(),
+        label_selector.into(),
+        "--field".into(),
+        "value".into(),
+        "--one".into(),
+    ];
+    let output = run_typst(path, config, "query", &args, &SILENT_FILTER);
+
+    let inline = output.ok().and_then(|out| {
         let json_str = std::str::from_utf8(&out.stdout).ok()?;
         serde_json::from_str(json_str).ok()
-    })
+    });
+    ContentMeta::from_sources(path, &config.build.content, inline)
 }
 
 /// Check if content metadata indicates a draft.
@@ -233,13 +436,106 @@ fn is_draft(meta: Option<&ContentMeta>) -> bool {
     meta.is_some_and(|c| c.draft)
 }
 
+/// Check if the page's `date` is after the current time.
+///
+/// Unparseable dates are never considered future.
+fn is_future(meta: Option<&ContentMeta>, now: DateTimeUtc) -> bool {
+    meta.and_then(|c| c.date.as_deref())
+        .and_then(DateTimeUtc::parse)
+        .is_some_and(|date| date > now)
+}
+
+/// Check if the page's `expiry_date` is at or before the current time.
+///
+/// Unparseable dates never expire.
+fn is_expired(meta: Option<&ContentMeta>, now: DateTimeUtc) -> bool {
+    meta.and_then(|c| c.expiry_date.as_deref())
+        .and_then(DateTimeUtc::parse)
+        .is_some_and(|expiry| expiry <= now)
+}
+
+/// Whether a page is built, or why not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageStatus {
+    Published,
+    /// `draft: true`
+    Draft,
+    /// `date` in the future, without `[build].future`
+    Scheduled,
+    /// `expiry_date` has passed, without `[build].expired`
+    Expired,
+}
+
+impl PageStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Published => "published",
+            Self::Draft => "draft",
+            Self::Scheduled => "scheduled",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+/// Whether a page is built, or which rule of [`is_unpublished`] excludes it.
+pub(super) fn page_status(meta: Option<&ContentMeta>, config: &SiteConfig) -> PageStatus {
+    let now = DateTimeUtc::now();
+    if is_draft(meta) {
+        PageStatus::Draft
+    } else if !config.build.expired && is_expired(meta, now) {
+        PageStatus::Expired
+    } else if !config.build.future && is_future(meta, now) {
+        PageStatus::Scheduled
+    } else {
+        PageStatus::Published
+    }
+}
+
+/// Check if a page should be excluded from the build (and thus rss/sitemap).
+///
+/// - Drafts are always excluded
+/// - Expired pages are excluded unless `[build].expired` / `--expired`
+/// - Future-dated pages are excluded unless `[build].future` / `--future`
+///
+/// Expiry wins: an expired page stays hidden under `--future` alone.
+fn is_unpublished(meta: Option<&ContentMeta>, config: &SiteConfig) -> bool {
+    page_status(meta, config) != PageStatus::Published
+}
+
+/// Warn when a page's `update` date is before its `date`.
+///
+/// Returns the warning, if any.
+fn warn_update_before_date(page: &PageMeta) -> Option<String> {
+    let content = page.content_meta.as_ref()?;
+    let date = DateTimeUtc::parse(content.date.as_deref()?)?;
+    let update = DateTimeUtc::parse(content.update.as_deref()?)?;
+    if update >= date {
+        return None;
+    }
+
+    let msg = format!(
+        "{}: update {} is before date {}",
+        page.paths.relative,
+        content.update.as_deref().unwrap_or_default(),
+        content.date.as_deref().unwrap_or_default()
+    );
+    log!("warn"; "{msg}");
+    BUILD_STATS.warn(msg.clone());
+    Some(msg)
+}
+
 // ============================================================================
 // Two-Phase Compilation Support
 // ============================================================================
 
 /// Convert a `PageMeta` to `PageData` for the global site data store.
-fn page_meta_to_data(page: &PageMeta) -> PageData {
+fn page_meta_to_data(page: &PageMeta, config: &SiteConfig) -> PageData {
     let content = page.content_meta.as_ref();
+    let date_display = config.build.date_format.as_deref().and_then(|fmt| {
+        let date = DateTimeUtc::parse(content?.date.as_deref()?)?;
+        Some(date.format(fmt, &config.base.language))
+    });
     PageData {
         url: page.paths.url_path.clone(),
         title: content
@@ -247,10 +543,19 @@ fn page_meta_to_data(page: &PageMeta) -> PageData {
             .unwrap_or_else(|| page.paths.relative.clone()),
         summary: content.and_then(|c| c.summary.clone()),
         date: content.and_then(|c| c.date.clone()),
+        date_display,
         update: content.and_then(|c| c.update.clone()),
-        author: content.and_then(|c| c.author.clone()),
+        author: content.and_then(|c| c.authors.first().cloned()),
+        authors: content.map(|c| c.authors.clone()).unwrap_or_default(),
         tags: content.map(|c| c.tags.clone()).unwrap_or_default(),
+        summary_html: content.and_then(|c| c.summary_html.clone()),
+        has_more: content.is_some_and(|c| c.summary_html.is_some()),
+        section: content
+            .and_then(|c| c.section.clone())
+            .or_else(|| content_section(Path::new(&page.paths.relative))),
         draft: content.is_some_and(|c| c.draft),
+        prev: None,
+        next: None,
     }
 }
 
@@ -260,111 +565,199 @@ fn page_meta_to_data(page: &PageMeta) -> PageData {
 /// HTML output is discarded since it may be incomplete (virtual JSON returns empty).
 ///
 /// After this phase, `GLOBAL_SITE_DATA` contains complete metadata from all pages.
+/// Returns the published pages with their metadata, reused by Phase 2.
 pub fn collect_metadata(
     config: &SiteConfig,
     on_progress: impl Fn() + Sync,
-) -> Result<Vec<std::path::PathBuf>> {
-    let content_files = collect_all_files(&config.build.content);
-
-    let typ_files: Vec<_> = content_files
-        .into_iter()
-        .filter(|p| p.extension().is_some_and(|ext| ext == "typ"))
-        .collect();
+) -> Result<Vec<PageMeta>> {
+    let typ_files = collect_page_files(config);
+    check_output_conflicts(&typ_files, config)?;
 
     // Clear global data store for fresh collection
     GLOBAL_SITE_DATA.clear();
 
-    let results: Vec<Result<Option<(std::path::PathBuf, PageMeta)>>> = typ_files
+    let results: Vec<Result<Option<PageMeta>>> = typ_files
         .par_iter()
         .map(|path| {
-            let page = PageMeta::from_paths(path.clone(), config)?;
-
-            // Compile to extract metadata (HTML discarded)
-            let content_meta = if config.build.typst.use_lib {
-                let root = config.get_root();
-                let result = typst_lib::compile_meta(path, root, TOLA_META_LABEL)?;
-
-                // Record dependencies for incremental rebuild
-                super::deps::DEPENDENCY_GRAPH
-                    .write()
-                    .record_dependencies(path, &result.accessed_files);
-
-                result
-                    .metadata
-                    .and_then(|json| serde_json::from_value(json).ok())
-            } else {
-                query_meta(path, config)
+            let Some(page) = read_page_meta(path, config)? else {
+                on_progress();
+                return Ok(None);
             };
 
-            // Skip drafts
-            if is_draft(content_meta.as_ref()) {
+            // Skip drafts and scheduled (future-dated) pages
+            if is_unpublished(page.content_meta.as_ref(), config) {
                 on_progress();
                 return Ok(None);
             }
 
-            let mut page = page;
-            page.content_meta = content_meta;
+            warn_update_before_date(&page);
 
             // Store in global data
-            GLOBAL_SITE_DATA.insert_page(page_meta_to_data(&page));
+            GLOBAL_SITE_DATA.insert_page(page_meta_to_data(&page, config));
 
             on_progress();
-            Ok(Some((path.clone(), page)))
+            Ok(Some(page))
         })
         .collect();
 
-    // Collect paths of non-draft pages
-    let mut paths = Vec::with_capacity(results.len());
+    // Collect non-draft pages
+    let mut pages = Vec::with_capacity(results.len());
     for result in results {
         match result {
-            Ok(Some((path, _))) => paths.push(path),
+            Ok(Some(page)) => pages.push(page),
             Ok(None) => {} // Draft, skip
             Err(e) => return Err(e),
         }
     }
 
-    Ok(paths)
+    Ok(pages)
+}
+
+/// Paths and metadata of a content file (HTML discarded).
+///
+/// Returns `None` if compiling it timed out.
+pub(super) fn read_page_meta(path: &Path, config: &SiteConfig) -> Result<Option<PageMeta>> {
+    let mut page = PageMeta::from_paths(path.to_path_buf(), config)?;
+    page.content_meta = if config.build.typst.use_lib {
+        let Some(meta) = skip_timed_out(with_timeout(path, config, collect_meta_lib))? else {
+            return Ok(None);
+        };
+        meta
+    } else {
+        query_meta(path, config)
+    };
+    Ok(Some(page))
+}
+
+/// Lib-mode metadata for Phase 1, recording dependencies on the way.
+fn collect_meta_lib(path: &Path, config: &SiteConfig) -> Result<Option<ContentMeta>> {
+    let root = config.get_root();
+    let result = typst_lib::compile_meta(path, root, TOLA_META_LABEL)?;
+
+    // Record dependencies for incremental rebuild
+    super::deps::DEPENDENCY_GRAPH
+        .write()
+        .record_dependencies(path, &result.accessed_files);
+
+    let mut meta = ContentMeta::from_sources(path, &config.build.content, result.metadata);
+    fill_summary(&mut meta, &result.html, config);
+    Ok(meta)
 }
 
 /// Phase 2: Compile pages with complete global data.
 ///
 /// Compiles all pages again, this time with `GLOBAL_SITE_DATA` fully populated.
 /// Virtual JSON files now return complete data, so HTML output is correct.
+///
+/// In CLI mode, metadata from Phase 1 is reused instead of spawning another
+/// `typst query` per page (the CLI can't see virtual data, so it's unchanged).
+/// The resulting `Pages` feed rss/sitemap directly, without further queries.
 pub fn compile_pages_with_data(
-    paths: &[std::path::PathBuf],
+    pages: Vec<PageMeta>,
     config: &SiteConfig,
     clean: bool,
     deps_mtime: Option<SystemTime>,
     on_progress: impl Fn() + Sync,
 ) -> Result<Pages> {
-    let results: Vec<Result<PageMeta>> = paths
-        .par_iter()
-        .map(|path| {
-            let mut page = PageMeta::from_paths(path.clone(), config)?;
+    let results: Vec<Result<Option<PageMeta>>> = pages
+        .into_par_iter()
+        .map(|mut page| {
+            let path = &page.paths.source;
+
+            // Unchanged since the last incremental build: keep the existing output
+            if config.build.incremental
+                && !clean
+                && MANIFEST.read().is_fresh(path, &page.paths.html)
+            {
+                on_progress();
+                return Ok(Some(page));
+            }
 
-            // Compile with complete data
-            let (html, content_meta) = compile_meta(path, config)?;
+            // Outside `--only`: keep the existing output, but still list the page
+            if !is_selected(path, config) {
+                on_progress();
+                return Ok(Some(page));
+            }
 
-            page.content_meta = content_meta;
-            page.compiled_html = Some(html);
+            // Compile with complete data
+            if config.build.typst.use_lib {
+                let Some((html, content_meta)) = skip_timed_out(compile_meta(path, config))? else {
+                    on_progress();
+                    return Ok(None);
+                };
+                page.content_meta = content_meta;
+                page.compiled_html = Some(html);
+            } else {
+                let Some(html) = skip_timed_out(with_timeout(path, config, compile_cli))? else {
+                    on_progress();
+                    return Ok(None);
+                };
+                fill_summary(&mut page.content_meta, &html, config);
+                page.compiled_html = Some(html);
+            }
 
-            // Write the page
-            write_page(&page, config, clean, deps_mtime, false)?;
+            // Write the page (content hashes, not mtimes, decide under --incremental)
+            write_page(
+                &page,
+                config,
+                clean || config.build.incremental,
+                deps_mtime,
+                false,
+            )?;
+            if config.build.incremental {
+                record_in_manifest(&page, config);
+            }
 
             on_progress();
-            Ok(page)
+            Ok(Some(page))
         })
         .collect();
 
-    // Collect successful pages
+    // Collect successful pages, dropping timed-out ones
     let mut items = Vec::with_capacity(results.len());
     for result in results {
-        items.push(result?);
+        items.extend(result?);
     }
 
     Ok(Pages { items })
 }
 
+/// Whether `path` matches the `--only` glob (always true without one).
+fn is_selected(path: &Path, config: &SiteConfig) -> bool {
+    let Some(glob) = &config.build.only else {
+        return true;
+    };
+    let rel = path.strip_prefix(&config.build.content).unwrap_or(path);
+    let rel = rel.to_string_lossy().replace('\\', "/");
+    wildmatch(
+        glob.as_bytes().as_bstr(),
+        rel.as_bytes().as_bstr(),
+        wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+    )
+}
+
+/// Record a written page and its dependencies in the incremental manifest.
+///
+/// CLI mode doesn't track dependencies, so every file in `[build].deps` counts.
+fn record_in_manifest(page: &PageMeta, config: &SiteConfig) {
+    let source = &page.paths.source;
+    let deps: Vec<PathBuf> = if config.build.typst.use_lib {
+        super::deps::DEPENDENCY_GRAPH
+            .read()
+            .get_dependencies(source)
+            .map(|deps| deps.iter().cloned().collect())
+            .unwrap_or_default()
+    } else {
+        config
+            .build
+            .deps
+            .iter()
+            .flat_map(|dir| collect_all_files(dir))
+            .collect()
+    };
+    MANIFEST.write().record(source, &deps, &page.paths.html);
+}
+
 /// Collect all pages from content directory with metadata.
 ///
 /// This function scans the content directory for `.typ` files and collects
@@ -384,12 +777,7 @@ pub fn compile_pages_with_data(
 /// with virtual data support, use `collect_metadata` + `compile_pages_with_data`.
 #[allow(dead_code)]
 pub fn collect_pages(config: &SiteConfig) -> Result<Pages> {
-    let content_files = collect_all_files(&config.build.content);
-
-    let typ_files: Vec<_> = content_files
-        .into_iter()
-        .filter(|p| p.extension().is_some_and(|ext| ext == "typ"))
-        .collect();
+    let typ_files = collect_page_files(config);
 
     let results: Vec<Result<Option<PageMeta>>> = typ_files
         .par_iter()
@@ -405,8 +793,8 @@ pub fn collect_pages(config: &SiteConfig) -> Result<Pages> {
                 (None, query_meta(path, config))
             };
 
-            // Skip drafts
-            if is_draft(content_meta.as_ref()) {
+            // Skip drafts and scheduled (future-dated) pages
+            if is_unpublished(content_meta.as_ref(), config) {
                 return Ok(None);
             }
 
@@ -526,7 +914,7 @@ mod tests {
 
         let meta = meta.unwrap();
         assert_eq!(meta.title, Some("Test".to_string()));
-        assert_eq!(meta.author, Some("Author".to_string()));
+        assert_eq!(meta.authors, ["Author"]);
     }
 
     #[test]
@@ -558,6 +946,450 @@ mod tests {
         assert!(is_draft(meta.as_ref()), "Should detect draft: true");
     }
 
+    #[test]
+    fn test_is_future() {
+        let now = DateTimeUtc::parse("2025-06-15").unwrap();
+        let meta = |date: &str| ContentMeta {
+            date: Some(date.into()),
+            ..Default::default()
+        };
+
+        assert!(is_future(Some(&meta("2025-06-16")), now));
+        assert!(!is_future(Some(&meta("2025-06-15")), now));
+        assert!(!is_future(Some(&meta("2024-01-01")), now));
+        assert!(!is_future(Some(&meta("not a date")), now));
+        assert!(!is_future(None, now));
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = DateTimeUtc::parse("2025-06-15").unwrap();
+        let meta = |expiry: &str| ContentMeta {
+            expiry_date: Some(expiry.into()),
+            ..Default::default()
+        };
+
+        assert!(is_expired(Some(&meta("2025-06-14")), now));
+        assert!(is_expired(Some(&meta("2025-06-15")), now));
+        assert!(!is_expired(Some(&meta("2025-06-16")), now));
+        assert!(!is_expired(Some(&meta("soon")), now));
+        assert!(!is_expired(Some(&ContentMeta::default()), now));
+    }
+
+    #[test]
+    fn test_expired_post_excluded() {
+        let mut config = SiteConfig::default();
+        let expired = ContentMeta {
+            date: Some("2020-01-01".into()),
+            expiry_date: Some("2021-01-01".into()),
+            ..Default::default()
+        };
+        assert!(is_unpublished(Some(&expired), &config));
+
+        // --future alone does not resurrect expired pages
+        config.build.future = true;
+        assert!(is_unpublished(Some(&expired), &config));
+
+        config.build.expired = true;
+        assert!(!is_unpublished(Some(&expired), &config));
+
+        // Drafts stay hidden regardless of flags
+        let draft = ContentMeta {
+            draft: true,
+            ..expired
+        };
+        assert!(is_unpublished(Some(&draft), &config));
+    }
+
+    #[test]
+    fn test_future_post_excluded_unless_enabled() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+
+        let next_year = DateTimeUtc::now().year + 1;
+        fs::write(
+            content_dir.join("scheduled.typ"),
+            format!(
+                "#metadata((title: \"Soon\", date: \"{next_year}-01-01\")) <tola-meta>\n= Soon"
+            ),
+        )
+        .unwrap();
+        fs::write(
+            content_dir.join("published.typ"),
+            "#metadata((title: \"Old\", date: \"2020-01-01\")) <tola-meta>\n= Old",
+        )
+        .unwrap();
+
+        let mut config = make_test_config(content_dir, dir.path().join("public"));
+        config.set_root(dir.path());
+
+        let pages = collect_metadata(&config, || {}).unwrap();
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].paths.source.ends_with("published.typ"));
+
+        // `--future` sets build.future
+        config.build.future = true;
+        let pages = collect_metadata(&config, || {}).unwrap();
+        assert_eq!(pages.len(), 2);
+    }
+
+    #[test]
+    fn test_auto_summary_from_first_paragraph() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("post.typ");
+        fs::write(
+            &file_path,
+            "#metadata((title: \"Post\")) <tola-meta>\n= Post\n\nThe first paragraph of the post body.\n\nSecond one.",
+        )
+        .unwrap();
+
+        let mut config = SiteConfig::default();
+        config.build.typst.use_lib = true;
+        config.set_root(dir.path());
+
+        let (_, meta) = compile_meta(&file_path, &config).unwrap();
+        assert_eq!(meta.unwrap().summary, None);
+
+        config.build.auto_summary = true;
+        config.build.summary_length = 20;
+        let (_, meta) = compile_meta(&file_path, &config).unwrap();
+        assert_eq!(
+            meta.unwrap().summary.as_deref(),
+            Some("The first paragraph…")
+        );
+
+        // An explicit summary wins
+        fs::write(
+            &file_path,
+            "#metadata((title: \"Post\", summary: \"Given\")) <tola-meta>\nBody text.",
+        )
+        .unwrap();
+        let (_, meta) = compile_meta(&file_path, &config).unwrap();
+        assert_eq!(meta.unwrap().summary.as_deref(), Some("Given"));
+    }
+
+    #[test]
+    fn test_more_delimiter_splits_summary() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        fs::write(
+            content_dir.join("post.typ"),
+            "#metadata((title: \"Post\")) <tola-meta>\nIntro text.\n\n#html.elem(\"tola-more\")\n\nThe rest.",
+        )
+        .unwrap();
+        fs::write(
+            content_dir.join("plain.typ"),
+            "#metadata((title: \"Plain\")) <tola-meta>\nBody.",
+        )
+        .unwrap();
+
+        let mut config = make_test_config(content_dir.clone(), dir.path().join("public"));
+        config.set_root(dir.path());
+
+        let (html, meta) = compile_meta(&content_dir.join("post.typ"), &config).unwrap();
+        let mut page = PageMeta::from_paths(content_dir.join("post.typ"), &config).unwrap();
+        page.content_meta = meta;
+        let data = page_meta_to_data(&page, &config);
+        assert!(data.has_more);
+        assert_eq!(data.summary_html.as_deref(), Some("<p>Intro text.</p>"));
+        assert_eq!(data.summary.as_deref(), Some("<p>Intro text.</p>"));
+        assert!(String::from_utf8_lossy(&html).contains("tola-more"));
+
+        let (_, meta) = compile_meta(&content_dir.join("plain.typ"), &config).unwrap();
+        let mut page = PageMeta::from_paths(content_dir.join("plain.typ"), &config).unwrap();
+        page.content_meta = meta;
+        let data = page_meta_to_data(&page, &config);
+        assert!(!data.has_more);
+        assert_eq!(data.summary_html, None);
+    }
+
+    #[test]
+    fn test_raw_page_written_verbatim() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        let source = content_dir.join("raw.typ");
+        fs::write(
+            &source,
+            "#metadata((title: \"Raw\", raw: true)) <tola-meta>\n#link(\"/about\")[About]",
+        )
+        .unwrap();
+
+        let mut config = make_test_config(content_dir, dir.path().join("public"));
+        config.set_root(dir.path());
+        config.build.minify = true;
+
+        let (exported, _) = compile_meta(&source, &config).unwrap();
+        let page = process_page(&source, &config, true, None, false)
+            .unwrap()
+            .unwrap();
+        assert!(page.content_meta.unwrap().raw);
+        assert_eq!(fs::read(&page.paths.html).unwrap(), exported);
+    }
+
+    #[test]
+    fn test_collect_metadata_reads_yaml_companion() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        fs::write(content_dir.join("post.typ"), "= Post").unwrap();
+        fs::write(
+            content_dir.join("post.yaml"),
+            "title: From YAML\ntags: [rust]\n",
+        )
+        .unwrap();
+
+        let mut config = make_test_config(content_dir, dir.path().join("public"));
+        config.set_root(dir.path());
+
+        let pages = collect_metadata(&config, || {}).unwrap();
+        let meta = pages[0].content_meta.as_ref().unwrap();
+        assert_eq!(meta.title.as_deref(), Some("From YAML"));
+        assert_eq!(meta.tags, ["rust"]);
+    }
+
+    #[test]
+    fn test_two_phase_pages_carry_metadata() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        fs::write(
+            content_dir.join("post.typ"),
+            "#metadata((title: \"Post\", date: \"2024-01-01\")) <tola-meta>\n= Post",
+        )
+        .unwrap();
+
+        let mut config = make_test_config(content_dir, dir.path().join("public"));
+        config.set_root(dir.path());
+
+        let collected = collect_metadata(&config, || {}).unwrap();
+        let pages = compile_pages_with_data(collected, &config, true, None, || {}).unwrap();
+
+        // rss/sitemap consume these directly, no per-page re-query
+        let page = pages.iter().next().unwrap();
+        let meta = page.content_meta.as_ref().unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Post"));
+        assert_eq!(meta.date.as_deref(), Some("2024-01-01"));
+        assert!(page.paths.html.exists());
+    }
+
+    #[test]
+    fn test_collect_metadata_applies_dir_defaults() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(content_dir.join("blog")).unwrap();
+        fs::write(
+            content_dir.join("blog/_defaults.toml"),
+            "author = \"Alice\"\ntags = [\"blog\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            content_dir.join("blog/post.typ"),
+            "#metadata((title: \"Post\", tags: (\"rust\",))) <tola-meta>\n= Post",
+        )
+        .unwrap();
+
+        let mut config = make_test_config(content_dir, dir.path().join("public"));
+        config.set_root(dir.path());
+
+        let collected = collect_metadata(&config, || {}).unwrap();
+        let meta = collected[0].content_meta.as_ref().unwrap();
+        assert_eq!(meta.authors, ["Alice"]);
+        assert_eq!(meta.tags, ["blog", "rust"]);
+    }
+
+    #[test]
+    fn test_transitive_import_rebuilds_page() {
+        use super::super::deps::DEPENDENCY_GRAPH;
+
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        let partials = dir.path().join("templates/partials");
+        fs::create_dir_all(&content_dir).unwrap();
+        fs::create_dir_all(&partials).unwrap();
+        let head = partials.join("head.typ");
+        fs::write(&head, "#let greeting = \"v1\"").unwrap();
+        fs::write(
+            dir.path().join("templates/base.typ"),
+            "#import \"/templates/partials/head.typ\": greeting\n#let page(body) = [#greeting #body]",
+        )
+        .unwrap();
+        for name in ["a.typ", "b.typ"] {
+            fs::write(
+                content_dir.join(name),
+                "#import \"/templates/base.typ\": page\n#page[body]",
+            )
+            .unwrap();
+        }
+
+        let mut config = make_test_config(content_dir.clone(), dir.path().join("public"));
+        config.set_root(dir.path());
+        let page_a = content_dir.join("a.typ");
+        let page_b = content_dir.join("b.typ");
+        // `b` evaluates `base.typ` from the memoization cache
+        for page in [&page_a, &page_b] {
+            process_page(page, &config, true, None, false).unwrap();
+        }
+
+        let dependents = DEPENDENCY_GRAPH
+            .read()
+            .get_dependents(&head)
+            .cloned()
+            .unwrap();
+        assert!(dependents.contains(&page_a));
+        assert!(dependents.contains(&page_b));
+
+        // Editing the leaf rebuilds the pages found through the graph
+        fs::write(&head, "#let greeting = \"v2\"").unwrap();
+        for page in &dependents {
+            process_page(page, &config, true, None, false).unwrap();
+        }
+        let html = PageMeta::from_paths(page_a, &config).unwrap().paths.html;
+        assert!(fs::read_to_string(html).unwrap().contains("v2"));
+    }
+
+    #[test]
+    fn test_page_section_from_dir_or_meta() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(content_dir.join("posts")).unwrap();
+        for name in ["posts/hello.typ", "posts/moved.typ", "about.typ"] {
+            fs::write(content_dir.join(name), "= Page").unwrap();
+        }
+        let config = make_test_config(content_dir.clone(), dir.path().join("public"));
+
+        let data = |name: &str, meta: Option<ContentMeta>| {
+            let mut page = PageMeta::from_paths(content_dir.join(name), &config).unwrap();
+            page.content_meta = meta;
+            page_meta_to_data(&page, &config)
+        };
+
+        assert_eq!(
+            data("posts/hello.typ", None).section.as_deref(),
+            Some("posts")
+        );
+        assert_eq!(data("about.typ", None).section, None);
+
+        let meta = ContentMeta {
+            section: Some("archive".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            data("posts/moved.typ", Some(meta)).section.as_deref(),
+            Some("archive")
+        );
+    }
+
+    #[test]
+    fn test_page_data_date_display() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        fs::write(content_dir.join("post.typ"), "= Post").unwrap();
+        let mut config = make_test_config(content_dir.clone(), dir.path().join("public"));
+
+        let mut page = PageMeta::from_paths(content_dir.join("post.typ"), &config).unwrap();
+        page.content_meta = Some(ContentMeta {
+            date: Some("2024-01-15".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(page_meta_to_data(&page, &config).date_display, None);
+
+        config.build.date_format = Some("%B %d, %Y".to_string());
+        config.base.language = "en".to_string();
+        let data = page_meta_to_data(&page, &config);
+        assert_eq!(data.date_display.as_deref(), Some("January 15, 2024"));
+        assert_eq!(data.date.as_deref(), Some("2024-01-15"));
+
+        config.base.language = "de".to_string();
+        let data = page_meta_to_data(&page, &config);
+        assert_eq!(data.date_display.as_deref(), Some("Januar 15, 2024"));
+    }
+
+    #[test]
+    fn test_compile_timeout() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        let slow = content_dir.join("slow.typ");
+        fs::write(
+            &slow,
+            // About 5s in a debug build. Keep it bounded: the timed-out
+            // compilation still holds the test compile lock until it finishes
+            "#let x = 0\n#for i in range(600000) { x = calc.rem(x + i, 7) }\n#x",
+        )
+        .unwrap();
+        let mut config = make_test_config(content_dir, dir.path().join("public"));
+        config.set_root(dir.path());
+        config.build.typst.timeout_secs = 1;
+
+        let start = Instant::now();
+        let err = compile_meta(&slow, &config).unwrap_err();
+        assert!(start.elapsed() < Duration::from_secs(3));
+        let timeout = err.downcast_ref::<CompileTimeout>().unwrap();
+        assert_eq!(timeout.file, "slow.typ");
+        assert_eq!(timeout.secs, 1);
+
+        // Timeouts skip the page, other errors still fail
+        assert!(skip_timed_out::<()>(Err(err)).unwrap().is_none());
+        assert!(skip_timed_out::<()>(Err(anyhow::anyhow!("boom"))).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_cli_compile_timeout_kills_typst() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        let page = content_dir.join("page.typ");
+        fs::write(&page, "= Page").unwrap();
+        let typst = dir.path().join("hanging-typst");
+        fs::write(&typst, "#!/bin/sh\nexec sleep 30\n").unwrap();
+        fs::set_permissions(&typst, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut config = make_test_config(content_dir, dir.path().join("public"));
+        config.set_root(dir.path());
+        config.build.typst.use_lib = false;
+        config.build.typst.command = vec![typst.to_string_lossy().into_owned()];
+        config.build.typst.timeout_secs = 1;
+
+        let start = Instant::now();
+        let err = compile_cli(&page, &config).unwrap_err();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            err.downcast_ref::<CompileTimeout>().unwrap().file,
+            "page.typ"
+        );
+    }
+
+    #[test]
+    fn test_warn_update_before_date() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        fs::write(content_dir.join("post.typ"), "= Post").unwrap();
+        let config = make_test_config(content_dir.clone(), dir.path().join("public"));
+
+        let mut page = PageMeta::from_paths(content_dir.join("post.typ"), &config).unwrap();
+        let mut dates = |date: &str, update: &str| {
+            page.content_meta = Some(ContentMeta {
+                date: Some(date.to_string()),
+                update: Some(update.to_string()),
+                ..Default::default()
+            });
+            warn_update_before_date(&page)
+        };
+
+        assert_eq!(dates("2024-01-15", "2024-01-15"), None);
+        assert_eq!(dates("2024-01-15", "2024-02-01"), None);
+        let warning = dates("2024-01-15", "2023-12-31").unwrap();
+        assert!(warning.contains("update 2023-12-31 is before date 2024-01-15"));
+    }
+
     #[test]
     fn test_compile_error_returns_err() {
         let dir = TempDir::new().unwrap();
@@ -614,6 +1446,17 @@ mod tests {
         assert_eq!(page.paths.relative, "posts/hello");
     }
 
+    #[test]
+    fn test_cli_warnings() {
+        let stderr = b"warning: html export is under active development and incomplete\n\
+            warning: unknown font family: foo\n  \xe2\x94\x8c\xe2\x94\x80 post.typ:1:1\n\
+            \x1b[33mwarning\x1b[0m: elem was ignored\n";
+        assert_eq!(
+            cli_warnings(Path::new("post.typ"), stderr),
+            ["post.typ: unknown font family: foo"]
+        );
+    }
+
     #[test]
     fn test_compile_cli_pipe() {
         // Skip if typst not available
@@ -0,0 +1,179 @@
+//! Taxonomy index pages (`/tags/<slug>/`, `/sections/<slug>/`, `/authors/<slug>/`).
+//!
+//! After the main page build, `[build].taxonomy_template` is compiled once per
+//! tag and section, and `[build].author_template` once per author. The term
+//! being rendered and its pages are exposed to the template as
+//! `/_data/current_taxonomy.json`.
+//!
+//! Terms are compiled sequentially since the current term is global state.
+
+use crate::compiler::meta::TOLA_META_LABEL;
+use crate::config::SiteConfig;
+use crate::data::{GLOBAL_SITE_DATA, TaggedPage, TaxonomyTerm};
+use crate::utils::minify::{MinifyType, minify};
+use crate::utils::slug::slugify_fragment;
+use crate::utils::xml::{link::relative_link_depth, process_html};
+use crate::{log, typst_lib};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Render one index page per tag and section with the taxonomy template,
+/// and one per author with the author template.
+///
+/// Returns the number of pages written (0 if no template is configured).
+pub fn build_taxonomy_pages(config: &SiteConfig, quiet: bool) -> Result<usize> {
+    let mut count = 0;
+    if let Some(template) = &config.build.taxonomy_template {
+        count += render_terms("tags", GLOBAL_SITE_DATA.get_tags_index(), template, config)?
+            + render_terms(
+                "sections",
+                GLOBAL_SITE_DATA.get_sections_index(),
+                template,
+                config,
+            )?;
+    }
+    if let Some(template) = &config.build.author_template {
+        count += render_terms(
+            "authors",
+            GLOBAL_SITE_DATA.get_authors_index(),
+            template,
+            config,
+        )?;
+    }
+
+    if !quiet && count > 0 {
+        log!("taxonomy"; "rendered {count} index pages");
+    }
+    Ok(count)
+}
+
+/// Output paths of all taxonomy pages for the current site data.
+///
+/// Empty if no template is configured.
+pub fn taxonomy_outputs(config: &SiteConfig) -> Vec<PathBuf> {
+    let mut rels = Vec::new();
+    if config.build.taxonomy_template.is_some() {
+        let tags = GLOBAL_SITE_DATA.get_tags_index().into_keys();
+        let sections = GLOBAL_SITE_DATA.get_sections_index().into_keys();
+        rels.extend(tags.map(|name| term_rel_path("tags", &name, config)));
+        rels.extend(sections.map(|name| term_rel_path("sections", &name, config)));
+    }
+    if config.build.author_template.is_some() {
+        let authors = GLOBAL_SITE_DATA.get_authors_index().into_keys();
+        rels.extend(authors.map(|name| term_rel_path("authors", &name, config)));
+    }
+
+    let index = config.build.index_filename();
+    rels.into_iter()
+        .map(|rel| config.paths().output_dir().join(rel).join(&index))
+        .collect()
+}
+
+/// Path of a term's index directory relative to the output dir (`tags/<slug>`).
+fn term_rel_path(kind: &str, name: &str, config: &SiteConfig) -> PathBuf {
+    Path::new(kind).join(slugify_fragment(name, config))
+}
+
+/// Render `template` for every term of one taxonomy into `/<kind>/<slug>/`.
+fn render_terms(
+    kind: &str,
+    index: BTreeMap<String, Vec<TaggedPage>>,
+    template: &Path,
+    config: &SiteConfig,
+) -> Result<usize> {
+    let mut count = 0;
+    for (name, pages) in index {
+        let rel = term_rel_path(kind, &name, config);
+        let term = TaxonomyTerm {
+            kind: kind.to_string(),
+            url: format!("{}/", config.paths().url_for_rel_path(&rel)),
+            name,
+            pages,
+        };
+
+        GLOBAL_SITE_DATA.set_current_taxonomy(Some(&term));
+        let html_path = config
+            .paths()
+            .output_dir()
+            .join(&rel)
+            .join(config.build.index_filename());
+        let result = write_term_page(template, &html_path, config)
+            .with_context(|| format!("taxonomy page {}", term.url));
+        GLOBAL_SITE_DATA.set_current_taxonomy(None);
+        result?;
+
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Compile the template for the current term and write it to `html_path`.
+fn write_term_page(template: &Path, html_path: &Path, config: &SiteConfig) -> Result<()> {
+    let result = typst_lib::compile_meta(template, config.get_root(), TOLA_META_LABEL)?;
+
+    if let Some(parent) = html_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let link_depth = relative_link_depth(template, html_path, config);
+    let html = process_html(template, html_path, &result.html, config, link_depth)?;
+    let html = minify(MinifyType::Html(&html), config);
+    fs::write(html_path, &*html)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tagged(title: &str) -> TaggedPage {
+        TaggedPage {
+            url: format!("/{}/", title.to_lowercase()),
+            title: title.to_string(),
+            date: None,
+        }
+    }
+
+    #[test]
+    fn test_two_tags_produce_two_index_pages() {
+        let dir = TempDir::new().unwrap();
+        let template = dir.path().join("taxonomy.typ");
+        fs::write(
+            &template,
+            "#let term = json(\"/_data/current_taxonomy.json\")\n\
+             = Tag #term.name\n\
+             #for page in term.pages [- #page.title]",
+        )
+        .unwrap();
+
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().join("public");
+        config.build.typst.use_lib = true;
+        config.build.minify = false;
+        config.set_root(dir.path());
+
+        let index = BTreeMap::from([
+            ("rust".to_string(), vec![tagged("Alpha"), tagged("Beta")]),
+            ("web".to_string(), vec![tagged("Alpha")]),
+        ]);
+        let count = render_terms("tags", index, &template, &config).unwrap();
+
+        assert_eq!(count, 2);
+        let rust = fs::read_to_string(config.build.output.join("tags/rust/index.html")).unwrap();
+        assert!(rust.contains("Tag rust"), "{rust}");
+        assert!(rust.contains("Alpha") && rust.contains("Beta"), "{rust}");
+        let web = fs::read_to_string(config.build.output.join("tags/web/index.html")).unwrap();
+        assert!(web.contains("Tag web"), "{web}");
+        assert!(web.contains("Alpha") && !web.contains("Beta"), "{web}");
+        assert_eq!(GLOBAL_SITE_DATA.current_taxonomy_to_json(), "{}");
+
+        // Term pages follow `[build].page_extension`
+        config.build.page_extension = "htm".to_string();
+        let index = BTreeMap::from([("go".to_string(), vec![tagged("Gamma")])]);
+        render_terms("tags", index, &template, &config).unwrap();
+        assert!(config.build.output.join("tags/go/index.htm").is_file());
+    }
+}
@@ -16,12 +16,15 @@
 //!         └──── calls ────────────► process_watched_files()
 //! ```
 
-use crate::compiler::assets::{process_asset, rebuild_tailwind};
+use crate::compiler::assets::{process_asset, rebuild_sass, rebuild_tailwind, run_postcss};
+use crate::compiler::is_partial;
 use crate::compiler::pages::process_page;
+use crate::compiler::taxonomy::build_taxonomy_pages;
 use crate::config::SiteConfig;
 use crate::data::virtual_fs;
 use crate::logger::ProgressBars;
 use crate::utils::category::{FileCategory, categorize_path, normalize_path};
+use crate::utils::css;
 use anyhow::{Result, bail};
 use rayon::prelude::*;
 use std::path::PathBuf;
@@ -55,16 +58,30 @@ pub fn process_watched_files(files: &[PathBuf], config: &SiteConfig, clean: bool
     // Use output_dir() to place _data inside the site content directory (with path_prefix)
     if !content_files.is_empty() {
         let _ = virtual_fs::write_to_disk(&config.paths().output_dir().join(&config.build.data));
+        build_taxonomy_pages(config, true)?;
     }
 
     // Process asset files (tailwind input is handled specially inside)
     process_assets(&asset_files, config, progress.as_ref())?;
 
     // Rebuild tailwind if enabled (centralized handling for all file changes)
-    if config.build.css.tailwind.enable && !files.is_empty() {
+    let tailwind_rebuilt = config.build.css.tailwind.enable && !files.is_empty();
+    if tailwind_rebuilt {
         rebuild_tailwind(config, true)?;
     }
 
+    // Recompile Sass when any Sass source (input or partial) changed
+    let sass_rebuilt = files.iter().any(|f| css::is_sass_source(f, config));
+    if sass_rebuilt {
+        rebuild_sass(config, true)?;
+    }
+
+    // Re-run PostCSS only when a stylesheet in the output was rewritten
+    let css_rewritten = tailwind_rebuilt || sass_rebuilt || !asset_files.is_empty();
+    if config.build.css.postcss.enable && css_rewritten {
+        run_postcss(config, true)?;
+    }
+
     // Report errors (deduplicated)
     report_errors(content_errors)?;
 
@@ -85,6 +102,8 @@ fn categorize_files<'a>(
 
     for path in files.iter().filter(|p| p.exists()) {
         match categorize_path(path, config) {
+            // Partials are rebuilt through their dependents, never as pages
+            FileCategory::Content if is_partial(&normalize_path(path), config) => {}
             FileCategory::Content => content.push(path),
             FileCategory::Asset => assets.push(path),
             _ => {}
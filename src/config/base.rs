@@ -43,18 +43,81 @@ pub struct BaseConfig {
     pub url: Option<String>,
 
     /// BCP 47 language code (e.g., "zh-Hans", "en-US").
+    /// Underscores are accepted and normalized to hyphens (`zh_Hans` → `zh-Hans`).
     #[serde(default = "defaults::base::language")]
     #[educe(Default = defaults::base::language())]
     pub language: String,
 
+    /// Text direction written as `<html dir="...">`.
+    #[serde(default)]
+    pub direction: TextDirection,
+
     /// Copyright notice for site footer.
     #[serde(default)]
     pub copyright: String,
 }
 
+/// Normalize a language tag towards BCP 47: trimmed, `_` separators as `-`.
+pub fn normalize_language(tag: &str) -> String {
+    tag.trim().replace('_', "-")
+}
+
+/// Loose BCP 47 shape check: a 2–8 letter primary subtag (or `x`/`i`),
+/// then `-` separated alphanumeric subtags of 1–8 characters.
+///
+/// Registry lookups are out of scope, so unusual but well-formed tags pass.
+pub fn is_plausible_language(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    let primary_ok = matches!(primary.len(), 2..=8)
+        && primary.bytes().all(|b| b.is_ascii_alphabetic())
+        || primary.eq_ignore_ascii_case("x")
+        || primary.eq_ignore_ascii_case("i");
+    primary_ok
+        && subtags
+            .all(|sub| matches!(sub.len(), 1..=8) && sub.bytes().all(|b| b.is_ascii_alphanumeric()))
+}
+
+/// Writing direction of page text.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDirection {
+    /// Left to right.
+    Ltr,
+    /// Right to left.
+    Rtl,
+    /// Infer from the language code: `rtl` for right-to-left scripts
+    /// (Arabic, Hebrew, Persian, ...), otherwise no `dir` attribute (default).
+    #[default]
+    Auto,
+}
+
+/// Primary language subtags written right to left.
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "ku", "ps", "sd", "syr", "ug", "ur", "yi",
+];
+
+impl TextDirection {
+    /// The `dir` attribute value for a page in `language`, if any.
+    pub fn resolve(self, language: &str) -> Option<&'static str> {
+        match self {
+            Self::Ltr => Some("ltr"),
+            Self::Rtl => Some("rtl"),
+            Self::Auto => {
+                let primary = language.split(['-', '_']).next().unwrap_or_default();
+                RTL_LANGUAGES
+                    .iter()
+                    .any(|rtl| rtl.eq_ignore_ascii_case(primary))
+                    .then_some("rtl")
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::SiteConfig;
+    use super::{is_plausible_language, normalize_language};
 
     #[test]
     fn test_base_config_full() {
@@ -91,6 +154,28 @@ mod tests {
         assert_eq!(config.base.copyright, "");
     }
 
+    #[test]
+    fn test_direction() {
+        use super::TextDirection;
+
+        let config: SiteConfig = toml::from_str(
+            r#"
+            [base]
+            title = "Test"
+            description = "Test blog"
+            direction = "rtl"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.base.direction, TextDirection::Rtl);
+        assert_eq!(config.base.direction.resolve("en"), Some("rtl"));
+
+        assert_eq!(TextDirection::Auto.resolve("ar-EG"), Some("rtl"));
+        assert_eq!(TextDirection::Auto.resolve("he"), Some("rtl"));
+        assert_eq!(TextDirection::Auto.resolve("zh-Hans"), None);
+        assert_eq!(TextDirection::Ltr.resolve("fa"), Some("ltr"));
+    }
+
     #[test]
     fn test_unknown_field_rejection() {
         let config = r#"
@@ -137,6 +222,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_language_tag_shape() {
+        assert_eq!(normalize_language(" zh_Hans "), "zh-Hans");
+        assert_eq!(normalize_language("en-US"), "en-US");
+
+        for tag in [
+            "en",
+            "zh-Hans",
+            "sr-Latn-RS",
+            "de-CH-1996",
+            "x-klingon",
+            "yue",
+        ] {
+            assert!(is_plausible_language(tag), "{tag}");
+        }
+        for tag in [
+            "",
+            "e",
+            "english language",
+            "en--US",
+            "en-",
+            "12-US",
+            "en-toolongsubtag",
+        ] {
+            assert!(!is_plausible_language(tag), "{tag}");
+        }
+    }
+
     #[test]
     fn test_base_config_empty_strings() {
         let config = r#"
@@ -5,6 +5,7 @@
 use super::defaults;
 use educe::Educe;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 // ============================================================================
@@ -62,6 +63,54 @@ impl SlugSeparator {
     }
 }
 
+/// Source of page `lastmod` dates (sitemap).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LastmodSource {
+    /// Source file modification time (default). Resets on checkout.
+    #[default]
+    Mtime,
+    /// Date of the last git commit touching the source file (mtime if untracked).
+    Git,
+}
+
+/// How item authors appear in the RSS feed.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RssAuthorMode {
+    /// `<author>email (Name)</author>`, combining `base.email` and
+    /// `base.author` when the post author has no email.
+    Email,
+    /// `<dc:creator>Name</dc:creator>`, never exposing an email.
+    Name,
+    /// `email` when a real email is known, `name` otherwise (default).
+    #[default]
+    Auto,
+}
+
+/// Archive type of `[build].archive`, chosen by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// `.zip`
+    Zip,
+    /// `.tar.gz` / `.tgz`
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from a file name, `None` if unsupported.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
 /// SVG image extraction method for embedded raster images.
 #[allow(clippy::doc_markdown)] // ImageMagick, FFmpeg are product names
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -125,6 +174,16 @@ pub struct BuildConfig {
     #[educe(Default = defaults::build::assets())]
     pub assets: PathBuf,
 
+    /// Glob allowlist of files in `assets` to copy (e.g. `["images/**"]`).
+    /// Empty copies everything.
+    #[serde(default)]
+    pub assets_include: Vec<String>,
+
+    /// Glob patterns of files in `assets` never copied (e.g. `["*.psd"]`).
+    /// Patterns without a `/` match the file name in any directory.
+    #[serde(default)]
+    pub assets_exclude: Vec<String>,
+
     /// Dependency directories (templates/, utilities/, ui/,  etc.).
     /// Files in these directories trigger dependent content rebuilds when changed.
     #[serde(default = "defaults::build::deps")]
@@ -137,16 +196,174 @@ pub struct BuildConfig {
     #[educe(Default = defaults::build::data())]
     pub data: PathBuf,
 
+    /// Name prefix marking content files/directories as partials
+    /// (e.g. `_partials/foo.typ`): importable, but never built as pages.
+    /// Empty disables it.
+    #[serde(default = "defaults::build::ignore_prefix")]
+    #[educe(Default = defaults::build::ignore_prefix())]
+    pub ignore_prefix: String,
+
+    /// File names (without `.typ`) that serve as their directory's index,
+    /// e.g. `["index", "README"]` maps `posts/README.typ` to `/posts/`.
+    /// Empty by default: only the root `index.typ` maps to its directory,
+    /// while `posts/index.typ` maps to `/posts/index/`.
+    #[serde(default = "defaults::build::index_names")]
+    #[educe(Default = defaults::build::index_names())]
+    pub index_names: Vec<String>,
+
+    /// Extension of page output files, e.g. `htm` for hosts that require it.
+    /// Pages are written as `<page>/index.<ext>` and linked as `<page>/`.
+    #[serde(default = "defaults::build::page_extension")]
+    #[educe(Default = defaults::build::page_extension())]
+    pub page_extension: String,
+
     /// Minify HTML output (removes whitespace).
     #[serde(default = "defaults::r#true")]
     #[educe(Default = true)]
     pub minify: bool,
 
+    /// Inject intrinsic `width`/`height` into `<img>` tags pointing at local
+    /// raster images, preventing layout shift while images load.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub image_dimensions: bool,
+
+    /// Append a `<a class="heading-anchor" href="#id">¶</a>` permalink to
+    /// every heading that has an `id`.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub heading_anchors: bool,
+
+    /// Derive `summary` from the first paragraph of the page body when the
+    /// metadata omits it.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub auto_summary: bool,
+
+    /// Max characters of an auto-extracted summary (cut on a word boundary).
+    #[serde(default = "defaults::build::summary_length")]
+    #[educe(Default = defaults::build::summary_length())]
+    pub summary_length: usize,
+
+    /// Replace `:shortcode:`s (`:tada:` → 🎉) in page titles and summaries
+    /// used for feeds.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub emoji_shortcodes: bool,
+
+    /// Typst template compiled once per tag and section into
+    /// `/tags/<slug>/` and `/sections/<slug>/`; the current term and its
+    /// pages are exposed as `/_data/current_taxonomy.json`.
+    pub taxonomy_template: Option<PathBuf>,
+
+    /// Typst template compiled once per page author into `/authors/<slug>/`;
+    /// the author and their pages are exposed as
+    /// `/_data/current_taxonomy.json`.
+    pub author_template: Option<PathBuf>,
+
+    /// Reject unknown config keys. When `false` (or with `--lenient-config`),
+    /// unknown keys are dropped with a warning instead.
+    #[serde(default = "defaults::r#true")]
+    #[educe(Default = true)]
+    pub strict_config: bool,
+
+    /// Warn about internal links that don't resolve to an output file.
+    /// Always on under `--strict`.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub check_links: bool,
+
+    /// Copy files referenced from content that live under the project root
+    /// but outside `assets` and `content` (e.g. `<img src="../photos/cat.jpg">`
+    /// or `link("../docs/plan.pdf")`) to the same path in the output, and
+    /// point the links at the copy. Typst's own `image()` is inlined anyway.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub auto_copy_referenced: bool,
+
+    /// Fail the build when no pages are found, instead of writing (and
+    /// possibly deploying) an empty site. Also `--fail-on-empty`.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub fail_on_empty: bool,
+
+    /// strftime-like format for `date_display` in `/_data/pages.json`, e.g.
+    /// `"%B %d, %Y"`. Month and weekday names follow `base.language`.
+    #[serde(default)]
+    pub date_format: Option<String>,
+
+    /// Turn build warnings into an error (CLI only, `--strict`).
+    #[serde(skip)]
+    #[educe(Default = false)]
+    pub strict: bool,
+
     /// Clean output directory completely before building (CLI only, not from config file).
     #[serde(skip)]
     #[educe(Default = false)]
     pub clean: bool,
 
+    /// Ignore mtime and content-hash checks: recompile every page and recopy
+    /// every asset, without clearing the output first (CLI only, `--force-rebuild`).
+    #[serde(skip)]
+    #[educe(Default = false)]
+    pub force_rebuild: bool,
+
+    /// Log per-page compile timings (CLI only, `-v`).
+    #[serde(skip)]
+    #[educe(Default = false)]
+    pub verbose: bool,
+
+    /// Skip pages and assets whose content hashes are unchanged
+    /// (CLI only, `--incremental`).
+    #[serde(skip)]
+    #[educe(Default = false)]
+    pub incremental: bool,
+
+    /// Only compile content files matching this glob, relative to `content`
+    /// (CLI only, `--only`). Metadata is still collected from every page.
+    #[serde(skip)]
+    pub only: Option<String>,
+
+    /// Build pages whose `date` is in the future.
+    /// When disabled, scheduled posts are treated as drafts until their date passes.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub future: bool,
+
+    /// Build pages whose `expiry_date` has passed.
+    /// When disabled, expired pages are dropped from output, rss and sitemap.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub expired: bool,
+
+    /// Build into a staging directory next to `output` and swap it into place
+    /// only after the whole build (rss/sitemap included) succeeds, so a failed
+    /// build never leaves a half-written site. Every file is rewritten.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub atomic: bool,
+
+    /// Package the output directory into this archive after a successful build
+    /// (also `tola build --archive`). `.zip`, `.tar.gz` or `.tgz`.
+    #[serde(default)]
+    pub archive: Option<PathBuf>,
+
+    /// Delete output files that no longer correspond to any source after a build.
+    /// When disabled, orphaned files are only reported.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub prune_orphans: bool,
+
+    /// Output paths (relative to output directory) never treated as orphans,
+    /// e.g. `["CNAME", "downloads"]`. Directories match everything inside them.
+    #[serde(default)]
+    pub keep: Vec<PathBuf>,
+
+    /// Extra files/directories copied verbatim into the output after assets,
+    /// e.g. `{ from = "extra/_headers", to = "_headers" }`.
+    #[serde(default)]
+    pub copy: Vec<CopyEntry>,
+
     /// RSS feed generation settings.
     #[serde(default)]
     pub rss: RssConfig,
@@ -163,7 +380,7 @@ pub struct BuildConfig {
     #[serde(default)]
     pub typst: TypstConfig,
 
-    /// CSS processing configuration (auto-enhance, tailwind).
+    /// CSS processing configuration (auto-enhance, tailwind, sass, postcss).
     #[serde(default)]
     pub css: CssConfig,
 
@@ -172,6 +389,19 @@ pub struct BuildConfig {
     pub head: HeadConfig,
 }
 
+impl BuildConfig {
+    /// File name of a page's output in its directory (`index.html`).
+    pub fn index_filename(&self) -> String {
+        format!("index.{}", self.page_extension)
+    }
+
+    /// Check if `path` is a page output file (`[build].page_extension`).
+    pub fn is_page_file(&self, path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(&self.page_extension))
+    }
+}
+
 // ============================================================================
 // Sub-configurations
 // ============================================================================
@@ -194,6 +424,48 @@ pub struct RssConfig {
     #[serde(default = "defaults::build::rss::path")]
     #[educe(Default = defaults::build::rss::path())]
     pub path: PathBuf,
+
+    /// Channel `<image>`: asset path (relative to assets directory) or URL.
+    /// Must be a GIF, JPEG or PNG.
+    #[serde(default)]
+    pub image: Option<PathBuf>,
+
+    /// Channel `<ttl>`: minutes a feed may be cached before refreshing.
+    #[serde(default)]
+    pub ttl: Option<u32>,
+
+    /// Slugify tags for item `<category>` elements (raw tag names otherwise).
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = defaults::r#false())]
+    pub category_slug: bool,
+
+    /// Item author format: `"email"`, `"name"` or `"auto"`.
+    #[serde(default)]
+    pub author_mode: RssAuthorMode,
+
+    /// Set channel `<lastBuildDate>` to the latest post date instead of the
+    /// build time, so unchanged content produces an identical feed.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = defaults::r#false())]
+    pub deterministic: bool,
+
+    /// Channel `<generator>` (omitted if empty).
+    #[serde(default = "defaults::build::rss::generator")]
+    #[educe(Default = defaults::build::rss::generator())]
+    pub generator: String,
+
+    /// Channel `<managingEditor>`: editor email, e.g. `"editor@example.com (Alice)"`.
+    #[serde(default)]
+    pub managing_editor: Option<String>,
+
+    /// Channel `<webMaster>`: technical contact email.
+    #[serde(default)]
+    pub webmaster: Option<String>,
+
+    /// Truncate item descriptions to this many characters of text, on a word
+    /// boundary with `…` (unlimited if unset).
+    #[serde(default)]
+    pub summary_max_chars: Option<usize>,
 }
 
 /// `[build.sitemap]` section - Sitemap generation configuration.
@@ -216,6 +488,10 @@ pub struct SitemapConfig {
     #[serde(default = "defaults::build::sitemap::path")]
     #[educe(Default = defaults::build::sitemap::path())]
     pub path: PathBuf,
+
+    /// Where `<lastmod>` dates come from: `"mtime"` or `"git"`.
+    #[serde(default)]
+    pub lastmod: LastmodSource,
 }
 
 /// `[build.slug]` section
@@ -229,6 +505,9 @@ pub struct SlugConfig {
     pub path: SlugMode,
 
     /// Slugify URL fragments (anchors)
+    ///
+    /// With `"no"`, heading ids and `#fragment` links pass through byte-for-byte:
+    /// case, spaces and punctuation are kept, and `case` is not applied.
     #[serde(default = "defaults::build::slug::full")]
     #[educe(Default = defaults::build::slug::full())]
     pub fragment: SlugMode,
@@ -242,6 +521,41 @@ pub struct SlugConfig {
     #[serde(default = "defaults::build::slug::case")]
     #[educe(Default = defaults::build::slug::case())]
     pub case: SlugCase,
+
+    /// Replacements applied before ASCII transliteration (`full`/`ascii` modes),
+    /// e.g. `{ "ä" = "ae", "ß" = "ss" }`. Longer keys win over shorter ones.
+    #[serde(default)]
+    pub transliteration: BTreeMap<String, String>,
+}
+
+/// `[[build.copy]]` entry - a file or directory copied into the output.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CopyEntry {
+    /// Source path (relative to project root).
+    pub from: PathBuf,
+
+    /// Destination (relative to output directory).
+    /// Defaults to the same relative path as `from`.
+    #[serde(default)]
+    pub to: PathBuf,
+}
+
+impl CopyEntry {
+    /// Resolve `from` against `root` and fill in the default `to`.
+    pub fn resolve(&self, root: &Path) -> Self {
+        let to = if !self.to.as_os_str().is_empty() {
+            self.to.clone()
+        } else if self.from.is_relative() {
+            self.from.clone()
+        } else {
+            self.from.file_name().map(PathBuf::from).unwrap_or_default()
+        };
+        Self {
+            from: root.join(&self.from),
+            to,
+        }
+    }
 }
 
 /// `[build.typst]` section
@@ -259,6 +573,16 @@ pub struct TypstConfig {
     #[educe(Default = defaults::build::typst::command())]
     pub command: Vec<String>,
 
+    /// Values exposed to documents as `sys.inputs` (like `typst --input key=value`)
+    #[serde(default)]
+    pub inputs: HashMap<String, String>,
+
+    /// Max seconds to compile one file (0 = unlimited); a file exceeding it
+    /// is skipped with a warning
+    #[serde(default = "defaults::build::typst::timeout_secs")]
+    #[educe(Default = defaults::build::typst::timeout_secs())]
+    pub timeout_secs: u64,
+
     /// SVG processing options
     #[serde(default)]
     pub svg: TypstSvgConfig,
@@ -279,10 +603,44 @@ pub struct TypstSvgConfig {
     #[educe(Default = defaults::build::typst::svg::inline_max_size())]
     pub inline_max_size: String,
 
+    /// Keep SVGs below `inline_max_size` in the HTML instead of extracting
+    /// them to `svg-N` files
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub inline: bool,
+
+    /// Min size at which an extracted SVG is rasterized to AVIF instead of
+    /// written as an `.svg` file (default: `inline_max_size`)
+    #[serde(default)]
+    pub raster_min_size: Option<String>,
+
+    /// Also encode rasterized SVGs as PNG, offering the AVIF through `<picture>`
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub picture_fallback: bool,
+
     /// DPI for SVG rendering
     #[serde(default = "defaults::build::typst::svg::dpi")]
     #[educe(Default = defaults::build::typst::svg::dpi())]
     pub dpi: f32,
+
+    /// SVG optimization: 0 keeps Typst's output as-is, 1 (safe) rewrites it
+    /// with usvg, dropping comments, `<metadata>` and editor attributes,
+    /// 2 (aggressive) also rounds coordinates and transforms
+    #[serde(default = "defaults::build::typst::svg::optimize_level")]
+    #[educe(Default = defaults::build::typst::svg::optimize_level())]
+    pub optimize_level: u8,
+
+    /// Write identical SVGs on a page once, pointing every `<img>` at the first
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub dedupe: bool,
+
+    /// Max `magick`/`ffmpeg` processes running at once across all pages
+    /// (default: number of CPUs)
+    #[serde(default = "defaults::build::typst::svg::convert_jobs")]
+    #[educe(Default = defaults::build::typst::svg::convert_jobs())]
+    pub convert_jobs: usize,
 }
 
 /// `[build.css]` section - CSS processing configuration.
@@ -295,6 +653,10 @@ pub struct TypstSvgConfig {
 /// [build.css.tailwind]
 /// enable = true
 /// input = "assets/styles.css"
+///
+/// [build.css.sass]
+/// enable = true
+/// input = "assets/main.scss"
 /// ```
 #[derive(Debug, Clone, Educe, Serialize, Deserialize)]
 #[educe(Default)]
@@ -311,6 +673,14 @@ pub struct CssConfig {
     /// Tailwind CSS integration.
     #[serde(default)]
     pub tailwind: TailwindConfig,
+
+    /// Sass/SCSS compilation.
+    #[serde(default)]
+    pub sass: SassConfig,
+
+    /// PostCSS pass over produced stylesheets (e.g. autoprefixer).
+    #[serde(default)]
+    pub postcss: PostcssConfig,
 }
 
 /// `[build.css.tailwind]` section
@@ -323,10 +693,10 @@ pub struct TailwindConfig {
     #[educe(Default = false)]
     pub enable: bool,
 
-    /// Input CSS file path
+    /// Input CSS file path, or an array of `{ input, output }` entrypoints
     #[serde(default = "defaults::build::css::tailwind::input")]
     #[educe(Default = defaults::build::css::tailwind::input())]
-    pub input: Option<PathBuf>,
+    pub input: Option<TailwindInput>,
 
     /// Tailwind command and arguments
     #[serde(default = "defaults::build::css::tailwind::command")]
@@ -334,6 +704,101 @@ pub struct TailwindConfig {
     pub command: Vec<String>,
 }
 
+impl TailwindConfig {
+    /// All entrypoints (a single `input` path becomes one entry).
+    pub fn entries(&self) -> Vec<TailwindEntry> {
+        match &self.input {
+            None => Vec::new(),
+            Some(TailwindInput::Single(input)) => vec![TailwindEntry {
+                input: input.clone(),
+                output: None,
+            }],
+            Some(TailwindInput::Multiple(entries)) => entries.clone(),
+        }
+    }
+}
+
+/// `[build.css.tailwind].input` - a single path or a list of entrypoints.
+///
+/// ```toml
+/// input = "assets/app.css"
+/// # or
+/// input = [
+///     { input = "assets/app.css" },
+///     { input = "assets/admin.css", output = "admin/admin.css" },
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TailwindInput {
+    Single(PathBuf),
+    Multiple(Vec<TailwindEntry>),
+}
+
+/// One Tailwind entrypoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TailwindEntry {
+    /// Input CSS file path
+    pub input: PathBuf,
+
+    /// Output CSS path, relative to the site output directory.
+    /// Defaults to the input's asset path.
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+}
+
+/// `[build.css.sass]` section
+#[derive(Debug, Clone, Educe, Serialize, Deserialize)]
+#[educe(Default)]
+#[serde(deny_unknown_fields)]
+pub struct SassConfig {
+    /// Enable Sass/SCSS compilation
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub enable: bool,
+
+    /// Input `.scss`/`.sass` file path
+    #[serde(default = "defaults::build::css::sass::input")]
+    #[educe(Default = defaults::build::css::sass::input())]
+    pub input: Option<PathBuf>,
+
+    /// Output CSS path, relative to the site output directory.
+    /// Defaults to the input's asset path with a `.css` extension.
+    #[serde(default = "defaults::build::css::sass::output")]
+    #[educe(Default = defaults::build::css::sass::output())]
+    pub output: Option<PathBuf>,
+
+    /// Sass command and arguments
+    #[serde(default = "defaults::build::css::sass::command")]
+    #[educe(Default = defaults::build::css::sass::command())]
+    pub command: Vec<String>,
+}
+
+/// `[build.css.postcss]` section
+///
+/// Runs after Tailwind/Sass, rewriting every stylesheet in the output
+/// directory in place (`postcss <files> --replace`).
+#[derive(Debug, Clone, Educe, Serialize, Deserialize)]
+#[educe(Default)]
+#[serde(deny_unknown_fields)]
+pub struct PostcssConfig {
+    /// Enable PostCSS post-processing
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub enable: bool,
+
+    /// PostCSS command and arguments
+    #[serde(default = "defaults::build::css::postcss::command")]
+    #[educe(Default = defaults::build::css::postcss::command())]
+    pub command: Vec<String>,
+
+    /// PostCSS config path, passed as `--config`
+    #[serde(default = "defaults::build::css::postcss::config")]
+    #[educe(Default = defaults::build::css::postcss::config())]
+    pub config: Option<PathBuf>,
+}
+
 /// `[build.head]` section for custom head elements
 #[derive(Debug, Clone, Educe, Serialize, Deserialize)]
 #[educe(Default)]
@@ -352,8 +817,37 @@ pub struct HeadConfig {
     pub scripts: Vec<ScriptEntry>,
 
     /// Raw HTML elements to insert into head (e.g., `<meta name="darkreader-lock">`)
+    ///
+    /// `{{ extra.key }}` and `{{ base.key }}` are replaced with the escaped
+    /// config value, e.g. `<script data-id="{{ extra.analytics_id }}" ...>`.
     #[serde(default)]
     pub elements: Vec<String>,
+
+    /// Emit `<base href="...">` as the first element of `<head>`.
+    ///
+    /// Browsers resolve every relative URL against it, including relative
+    /// links tola rewrites with `../` for the page's output depth, so those
+    /// only stay correct if the base is the page's own directory. Prefer
+    /// absolute links (`/posts/`) when setting this.
+    #[serde(default)]
+    pub base_href: Option<String>,
+
+    /// Add `integrity` (SHA-384) and `crossorigin` to local styles and scripts.
+    /// Stylesheets rewritten at build time (Sass, PostCSS) are left without.
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub sri: bool,
+
+    /// Emit schema.org `BlogPosting` JSON-LD for pages with a title,
+    /// built from their metadata (title, date, authors, summary).
+    #[serde(default = "defaults::r#false")]
+    #[educe(Default = false)]
+    pub json_ld: bool,
+
+    /// Emit `<meta name="generator" content="tola-ssg <version>">`.
+    #[serde(default = "defaults::r#true")]
+    #[educe(Default = true)]
+    pub generator_meta: bool,
 }
 
 /// Script entry for `[build.head.scripts]`.
@@ -588,7 +1082,16 @@ mod tests {
         assert!(config.build.css.tailwind.enable);
         assert_eq!(
             config.build.css.tailwind.input,
-            Some(PathBuf::from("assets/styles/main.css"))
+            Some(TailwindInput::Single(PathBuf::from(
+                "assets/styles/main.css"
+            )))
+        );
+        assert_eq!(
+            config.build.css.tailwind.entries(),
+            vec![TailwindEntry {
+                input: "assets/styles/main.css".into(),
+                output: None,
+            }]
         );
         assert_eq!(
             config.build.css.tailwind.command,
@@ -596,6 +1099,92 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tailwind_multiple_entries() {
+        let config = r#"
+            [build.css.tailwind]
+            enable = true
+            input = [
+                { input = "assets/app.css" },
+                { input = "assets/admin.css", output = "admin/admin.css" },
+            ]
+        "#;
+        let config: SiteConfig = toml::from_str(config).unwrap();
+
+        assert_eq!(
+            config.build.css.tailwind.entries(),
+            vec![
+                TailwindEntry {
+                    input: "assets/app.css".into(),
+                    output: None,
+                },
+                TailwindEntry {
+                    input: "assets/admin.css".into(),
+                    output: Some("admin/admin.css".into()),
+                },
+            ]
+        );
+
+        let config = r#"
+            [build.css.tailwind]
+            input = [{ input = "assets/app.css", unknown = 1 }]
+        "#;
+        assert!(toml::from_str::<SiteConfig>(config).is_err());
+    }
+
+    #[test]
+    fn test_sass_config() {
+        let config = r#"
+            [base]
+            title = "Test"
+            description = "Test blog"
+
+            [build.css.sass]
+            enable = true
+            input = "assets/main.scss"
+            output = "css/site.css"
+        "#;
+        let config: SiteConfig = toml::from_str(config).unwrap();
+
+        assert!(config.build.css.sass.enable);
+        assert_eq!(
+            config.build.css.sass.input,
+            Some(PathBuf::from("assets/main.scss"))
+        );
+        assert_eq!(
+            config.build.css.sass.output,
+            Some(PathBuf::from("css/site.css"))
+        );
+        assert_eq!(config.build.css.sass.command, vec!["sass".to_string()]);
+
+        let config: SiteConfig = toml::from_str("[build.css.sass]\nenable = false").unwrap();
+        assert!(config.build.css.sass.output.is_none());
+    }
+
+    #[test]
+    fn test_postcss_config() {
+        let config: SiteConfig = toml::from_str("[build.css.postcss]\nenable = true").unwrap();
+        assert!(config.build.css.postcss.enable);
+        assert_eq!(
+            config.build.css.postcss.command,
+            vec!["postcss".to_string()]
+        );
+        assert!(config.build.css.postcss.config.is_none());
+
+        let config = r#"
+            [build.css.postcss]
+            enable = true
+            command = ["npx", "postcss"]
+            config = "postcss.config.js"
+        "#;
+        let config: SiteConfig = toml::from_str(config).unwrap();
+        assert_eq!(config.build.css.postcss.command, vec!["npx", "postcss"]);
+        assert_eq!(
+            config.build.css.postcss.config,
+            Some(PathBuf::from("postcss.config.js"))
+        );
+    }
+
     #[test]
     fn test_head_config_icon() {
         let config = r#"
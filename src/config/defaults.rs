@@ -67,12 +67,32 @@ pub mod build {
         "_data".into()
     }
 
+    pub fn ignore_prefix() -> String {
+        "_".into()
+    }
+
+    pub const fn index_names() -> Vec<String> {
+        Vec::new()
+    }
+
+    pub fn page_extension() -> String {
+        "html".into()
+    }
+
+    pub const fn summary_length() -> usize {
+        200
+    }
+
     pub mod rss {
         use std::path::PathBuf;
 
         pub fn path() -> PathBuf {
             "feed.xml".into()
         }
+
+        pub fn generator() -> String {
+            format!("tola-ssg {}", env!("CARGO_PKG_VERSION"))
+        }
     }
 
     pub mod sitemap {
@@ -119,6 +139,10 @@ pub mod build {
             vec!["typst".into()]
         }
 
+        pub const fn timeout_secs() -> u64 {
+            0
+        }
+
         pub mod svg {
             use super::ExtractSvgType;
 
@@ -133,19 +157,55 @@ pub mod build {
             pub const fn dpi() -> f32 {
                 96.
             }
+
+            pub const fn optimize_level() -> u8 {
+                1
+            }
+
+            pub fn convert_jobs() -> usize {
+                std::thread::available_parallelism().map_or(1, std::num::NonZero::get)
+            }
         }
     }
 
     pub mod css {
         pub mod tailwind {
+            use crate::config::build::TailwindInput;
+
+            pub const fn input() -> Option<TailwindInput> {
+                None
+            }
+
+            pub fn command() -> Vec<String> {
+                vec!["tailwindcss".into()]
+            }
+        }
+
+        pub mod postcss {
+            use std::path::PathBuf;
+
+            pub const fn config() -> Option<PathBuf> {
+                None
+            }
+
+            pub fn command() -> Vec<String> {
+                vec!["postcss".into()]
+            }
+        }
+
+        pub mod sass {
             use std::path::PathBuf;
 
             pub const fn input() -> Option<PathBuf> {
                 None
             }
 
+            pub const fn output() -> Option<PathBuf> {
+                None
+            }
+
             pub fn command() -> Vec<String> {
-                vec!["tailwindcss".into()]
+                vec!["sass".into()]
             }
         }
     }
@@ -163,6 +223,10 @@ pub mod serve {
     pub const fn port() -> u16 {
         5277
     }
+
+    pub const fn poll_interval() -> u64 {
+        1000
+    }
 }
 
 // ============================================================================
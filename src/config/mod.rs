@@ -46,29 +46,75 @@ mod serve;
 pub use paths::PathResolver;
 
 // Re-export public types used by other modules
-pub use build::{BuildConfig, ExtractSvgType, SlugCase, SlugMode, SlugSeparator};
+pub use base::TextDirection;
+use build::TailwindInput;
+pub use build::{
+    ArchiveFormat, BuildConfig, CopyEntry, ExtractSvgType, LastmodSource, RssAuthorMode, SlugCase,
+    SlugMode, SlugSeparator, TailwindEntry,
+};
 pub use deploy::DeployConfig;
 pub use error::ConfigError;
 pub use handle::{cfg, init_config, reload_config};
+pub use serve::{ProxyRule, WatchMode};
 
 // Internal imports used in this module
-use base::BaseConfig;
+use base::{BaseConfig, is_plausible_language, normalize_language};
 use serve::ServeConfig;
 
-use crate::cli::{BuildArgs, Cli, Commands};
+use crate::cli::{BuildArgs, Cli, Commands, ConfigCommand};
+use crate::log;
 use anyhow::{Context, Result, bail};
 use educe::Educe;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     fs,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
 };
 
 // ============================================================================
 // helper functions
 // ============================================================================
 
+/// Deserialize a config, removing unknown keys until it succeeds.
+///
+/// Returns the config and the dotted paths of the removed keys.
+fn deserialize_lenient(mut value: toml::Value) -> Result<(SiteConfig, Vec<String>)> {
+    let mut unknown = Vec::new();
+    loop {
+        let err = match serde_path_to_error::deserialize(value.clone()) {
+            Ok(config) => return Ok((config, unknown)),
+            Err(err) => err,
+        };
+
+        let is_unknown = err.inner().to_string().starts_with("unknown field");
+        if !is_unknown || remove_key(&mut value, err.path()).is_none() {
+            return Err(err.into_inner().into());
+        }
+        unknown.push(err.path().to_string());
+    }
+}
+
+/// Remove the key at `path` from a TOML value.
+fn remove_key(value: &mut toml::Value, path: &serde_path_to_error::Path) -> Option<toml::Value> {
+    use serde_path_to_error::Segment;
+
+    let segments: Vec<_> = path.iter().collect();
+    let (Segment::Map { key }, parents) = segments.split_last()? else {
+        return None;
+    };
+
+    let mut current = value;
+    for segment in parents {
+        current = match (segment, current) {
+            (Segment::Map { key }, toml::Value::Table(table)) => table.get_mut(key)?,
+            (Segment::Seq { index }, toml::Value::Array(array)) => array.get_mut(*index)?,
+            _ => return None,
+        };
+    }
+    current.as_table_mut()?.remove(key)
+}
+
 /// Parse a human-readable size string into bytes.
 ///
 /// Supports suffixes: B (bytes), KB (kilobytes), MB (megabytes).
@@ -124,10 +170,31 @@ fn extract_url_path(url: &str) -> Option<String> {
     Some(path.trim_matches('/').to_string())
 }
 
+/// Check that a path relative to the output directory can't escape it.
+///
+/// Checked lexically since outputs may not exist yet: absolute paths and `..`
+/// components that climb above the start are rejected.
+fn stays_inside(rel: &Path) -> bool {
+    let mut depth = 0usize;
+    rel.components().all(|component| match component {
+        Component::Normal(_) => {
+            depth += 1;
+            true
+        }
+        Component::CurDir => true,
+        Component::ParentDir if depth > 0 => {
+            depth -= 1;
+            true
+        }
+        Component::ParentDir | Component::RootDir | Component::Prefix(_) => false,
+    })
+}
+
 /// Find config file by searching upward from current directory.
 ///
-/// Starts from cwd and walks up parent directories until finding `config_name`.
-/// Returns the absolute path to the config file if found.
+/// Starts from cwd and walks up parent directories until finding `config_name`,
+/// which is whatever `--config` names (`tola.toml` by default, or e.g.
+/// `tola.ci.toml`). Returns the absolute path to the config file if found.
 ///
 /// # Example
 /// ```text
@@ -136,14 +203,18 @@ fn extract_url_path(url: &str) -> Option<String> {
 /// ```
 pub fn find_config_file(config_name: &Path) -> Option<PathBuf> {
     let cwd = std::env::current_dir().ok()?;
+    find_config_file_from(&cwd, config_name)
+}
 
-    // First check if config_name is an absolute path or exists in cwd
-    if config_name.is_absolute() && config_name.exists() {
-        return Some(config_name.to_path_buf());
+/// Search for `config_name` in `start` and its ancestors.
+fn find_config_file_from(start: &Path, config_name: &Path) -> Option<PathBuf> {
+    // An absolute path is used as-is
+    if config_name.is_absolute() {
+        return config_name.exists().then(|| config_name.to_path_buf());
     }
 
-    // Walk up from cwd looking for config file
-    let mut current = cwd.as_path();
+    // Walk up from start looking for config file
+    let mut current = start;
     loop {
         let candidate = current.join(config_name);
         if candidate.exists() {
@@ -222,7 +293,7 @@ impl SiteConfig {
 
         // Load or create default config
         let mut config = if exists {
-            Self::from_path(&config_path)?
+            Self::from_path(&config_path, cli)?
         } else {
             Self::default()
         };
@@ -232,8 +303,8 @@ impl SiteConfig {
         config.cli = Some(cli);
         config.finalize(cli);
 
-        // Validate (skip for init)
-        if !cli.is_init() {
+        // Validate (skip for init, doctor reports problems itself)
+        if !cli.is_init() && !cli.is_doctor() {
             config.validate()?;
         }
 
@@ -288,7 +359,13 @@ impl SiteConfig {
         // This ensures path_prefix works for both:
         // 1. CLI: --base-url "https://example.github.io/my-project"
         // 2. Config: url = "https://example.github.io/my-project"
-        self.sync_path_prefix_from_url();
+        // An explicit --path-prefix is final, even an empty one ("/")
+        if cli
+            .build_args()
+            .is_none_or(|args| args.path_prefix.is_none())
+        {
+            self.sync_path_prefix_from_url();
+        }
     }
 
     /// Sync path_prefix from base.url if not already set.
@@ -303,26 +380,70 @@ impl SiteConfig {
         }
 
         // Extract path from base.url
-        if let Some(ref url) = self.base.url {
-            if let Some(path) = extract_url_path(url) {
-                if !path.is_empty() {
-                    self.build.path_prefix = PathBuf::from(path);
-                }
-            }
+        if let Some(ref url) = self.base.url
+            && let Some(path) = extract_url_path(url)
+            && !path.is_empty()
+        {
+            self.build.path_prefix = PathBuf::from(path);
         }
     }
 
     /// Parse configuration from TOML string
+    #[cfg(test)]
     pub fn from_str(content: &str) -> Result<Self> {
-        let config: Self = toml::from_str(content)?;
-        Ok(config)
+        Self::parse(content, false).map(|(config, _)| config)
     }
 
-    /// Load configuration from file path
-    fn from_path(path: &Path) -> Result<Self> {
+    /// Parse configuration, dropping unknown keys if `lenient` or
+    /// `[build].strict_config = false`.
+    ///
+    /// Returns the config and the dotted paths of dropped keys.
+    fn parse(content: &str, lenient: bool) -> Result<(Self, Vec<String>)> {
+        let value: toml::Value = toml::from_str(content)?;
+        let strict = value
+            .get("build")
+            .and_then(|build| build.get("strict_config"))
+            .and_then(toml::Value::as_bool)
+            .unwrap_or(true);
+
+        if strict && !lenient {
+            // Parse the source again so errors point at a line
+            let config: Self = toml::from_str(content)?;
+            return Ok((config, Vec::new()));
+        }
+
+        deserialize_lenient(value)
+    }
+
+    /// Load configuration from file path.
+    ///
+    /// Dropped unknown keys are a warning, or an error under `--strict`.
+    fn from_path(path: &Path, cli: &Cli) -> Result<Self> {
         let content =
             fs::read_to_string(path).map_err(|err| ConfigError::Io(path.to_path_buf(), err))?;
-        Self::from_str(&content)
+        let (config, unknown) = Self::parse(&content, cli.lenient_config)?;
+
+        if !unknown.is_empty() {
+            let keys = unknown.join(", ");
+            if cli.strict {
+                bail!(ConfigError::Validation(format!(
+                    "unknown config keys: {keys}"
+                )));
+            }
+            log!("warn"; "ignoring unknown config keys: {keys}");
+        }
+        Ok(config)
+    }
+
+    /// Serialize the effective config for `tola config print`.
+    ///
+    /// Only the token *path* is printed; token files are never read here.
+    pub fn to_pretty_string(&self, json: bool) -> Result<String> {
+        Ok(if json {
+            serde_json::to_string_pretty(self)?
+        } else {
+            toml::to_string_pretty(self)?
+        })
     }
 
     /// Get the root directory path
@@ -352,6 +473,12 @@ impl SiteConfig {
         parse_size_string(&self.build.typst.svg.inline_max_size)
     }
 
+    /// Parse `raster_min_size` to bytes, defaulting to `inline_max_size`.
+    pub fn get_raster_min_size(&self) -> usize {
+        let svg = &self.build.typst.svg;
+        parse_size_string(svg.raster_min_size.as_ref().unwrap_or(&svg.inline_max_size))
+    }
+
     /// Get DPI scale factor (relative to standard 96 DPI).
     ///
     /// Used for SVG rendering resolution calculation.
@@ -374,30 +501,73 @@ impl SiteConfig {
         PathResolver::new(&self.build.output, &self.build.path_prefix)
     }
 
+    /// Scheme and host of `[base].url`, without its path component.
+    ///
+    /// The path is carried by `path_prefix` (which may be overridden via
+    /// `--path-prefix`), so absolute URLs are built as `origin + url_path`.
+    ///
+    /// # Examples
+    /// - `"https://example.com/blog/"` → `"https://example.com"`
+    /// - `"https://example.com"` → `"https://example.com"`
+    pub fn base_origin(&self) -> &str {
+        let url = self.base.url.as_deref().unwrap_or_default();
+        let host_start = url.find("://").map_or(0, |i| i + 3);
+        url[host_start..]
+            .find('/')
+            .map_or(url, |i| &url[..host_start + i])
+    }
+
+    /// Absolute URL of the site root, including `path_prefix`.
+    pub fn site_url(&self) -> String {
+        format!(
+            "{}{}",
+            self.base_origin(),
+            self.paths().url_for_rel_path("")
+        )
+    }
+
     // ========================================================================
     // cli configuration updates
     // ========================================================================
 
     /// Apply command-specific configuration options.
     fn apply_command_options(&mut self, cli: &Cli) {
+        self.build.strict = cli.strict;
         match &cli.command {
-            Commands::Build { build_args } => {
+            Commands::Build {
+                build_args,
+                archive,
+                ..
+            } => {
                 self.apply_build_args(build_args, false);
+                if let Some(path) = archive {
+                    self.build.archive = Some(Self::normalize_cli_output(path));
+                }
             }
             Commands::Serve {
                 build_args,
                 interface,
                 port,
                 watch,
-                ..
+                watch_poll,
+                no_build,
             } => {
                 self.apply_build_args(build_args, true);
                 self.apply_serve_options(interface.as_ref(), *port, *watch);
+                if *watch_poll {
+                    self.serve.watch_mode = WatchMode::Poll;
+                }
+                self.serve.no_build = *no_build;
             }
             Commands::Deploy { force } => {
                 Self::update_option(&mut self.deploy.force, force.as_ref());
             }
-            Commands::Init { .. } => {}
+            Commands::Config {
+                command: ConfigCommand::Print { build_args, .. },
+            } => {
+                self.apply_build_args(build_args, false);
+            }
+            Commands::Init { .. } | Commands::Doctor | Commands::Version { .. } => {}
         }
     }
 
@@ -407,7 +577,15 @@ impl SiteConfig {
     fn apply_build_args(&mut self, args: &BuildArgs, is_serve: bool) {
         Self::update_option(&mut self.build.minify, args.minify.as_ref());
         Self::update_option(&mut self.build.css.tailwind.enable, args.tailwind.as_ref());
+        Self::update_option(&mut self.build.future, args.future.as_ref());
+        Self::update_option(&mut self.build.expired, args.expired.as_ref());
         self.build.clean = args.clean;
+        self.build.force_rebuild = args.force_rebuild;
+        self.build.verbose = args.verbose;
+        self.build.incremental = args.incremental;
+        self.build.only = args.only.clone();
+        self.build.fail_on_empty |= args.fail_on_empty;
+        self.build.typst.inputs.extend(args.inputs.iter().cloned());
 
         // Override base URL if provided via CLI
         if let Some(ref url) = args.base_url {
@@ -422,8 +600,13 @@ impl SiteConfig {
             }
         }
 
-        if is_serve {
-            // Serve: disable rss/sitemap by default, enable only if explicitly requested
+        // Explicit prefix wins over the one derived from base URL
+        if let Some(ref prefix) = args.path_prefix {
+            self.build.path_prefix = Self::normalize_path_prefix(prefix);
+        }
+
+        if is_serve || args.only.is_some() {
+            // Serve and `--only`: disable rss/sitemap by default, enable only if explicitly requested
             self.build.rss.enable = args.rss.unwrap_or(false);
             self.build.sitemap.enable = args.sitemap.unwrap_or(false);
         } else {
@@ -471,7 +654,6 @@ impl SiteConfig {
         // Apply CLI path overrides first
         Self::update_option(&mut self.build.content, cli.content.as_ref());
         Self::update_option(&mut self.build.assets, cli.assets.as_ref());
-        Self::update_option(&mut self.build.output, cli.output.as_ref());
 
         // Normalize root to absolute path
         let root = Self::normalize_path(root);
@@ -483,13 +665,22 @@ impl SiteConfig {
         // Normalize build directories
         self.build.content = Self::normalize_path(&root.join(&self.build.content));
         self.build.assets = Self::normalize_path(&root.join(&self.build.assets));
-        self.build.output = Self::normalize_path(&root.join(&self.build.output));
+        self.build.output = match &cli.output {
+            Some(output) => Self::normalize_cli_output(output),
+            None => Self::normalize_path(&root.join(&self.build.output)),
+        };
         self.build.deps = self
             .build
             .deps
             .iter()
             .map(|p| Self::normalize_path(&root.join(p)))
             .collect();
+        self.build.copy = self
+            .build
+            .copy
+            .iter()
+            .map(|entry| entry.resolve(&root))
+            .collect();
         // Note: rss.path and sitemap.path are kept as relative filenames.
         // They are resolved to output_dir() at write time to include path_prefix.
 
@@ -497,13 +688,56 @@ impl SiteConfig {
         self.normalize_optional_paths(&root);
 
         // Normalize misc settings
-        self.build.typst.svg.inline_max_size = self.build.typst.svg.inline_max_size.to_uppercase();
+        self.base.language = normalize_language(&self.base.language);
+        self.build.page_extension = self
+            .build
+            .page_extension
+            .trim_start_matches('.')
+            .to_ascii_lowercase();
+        let svg = &mut self.build.typst.svg;
+        svg.inline_max_size = svg.inline_max_size.to_uppercase();
+        if let Some(size) = &mut svg.raster_min_size {
+            *size = size.to_uppercase();
+        }
     }
 
-    /// Normalize optional paths (tailwind input, deploy token).
+    /// Normalize optional paths (tailwind/sass input, postcss config, deploy token).
     fn normalize_optional_paths(&mut self, root: &Path) {
         if let Some(input) = self.build.css.tailwind.input.take() {
-            self.build.css.tailwind.input = Some(Self::normalize_path(&root.join(input)));
+            self.build.css.tailwind.input = Some(match input {
+                TailwindInput::Single(path) => {
+                    TailwindInput::Single(Self::normalize_path(&root.join(path)))
+                }
+                TailwindInput::Multiple(entries) => TailwindInput::Multiple(
+                    entries
+                        .into_iter()
+                        .map(|entry| TailwindEntry {
+                            input: Self::normalize_path(&root.join(&entry.input)),
+                            ..entry
+                        })
+                        .collect(),
+                ),
+            });
+        }
+
+        if let Some(input) = self.build.css.sass.input.take() {
+            self.build.css.sass.input = Some(Self::normalize_path(&root.join(input)));
+        }
+
+        if let Some(path) = self.build.css.postcss.config.take() {
+            self.build.css.postcss.config = Some(Self::normalize_path(&root.join(path)));
+        }
+
+        if let Some(path) = self.build.taxonomy_template.take() {
+            self.build.taxonomy_template = Some(Self::normalize_path(&root.join(path)));
+        }
+
+        if let Some(path) = self.build.author_template.take() {
+            self.build.author_template = Some(Self::normalize_path(&root.join(path)));
+        }
+
+        if let Some(path) = self.build.archive.take() {
+            self.build.archive = Some(Self::normalize_path(&root.join(path)));
         }
 
         if let Some(token_path) = self.deploy.github.token_path.take() {
@@ -523,6 +757,27 @@ impl SiteConfig {
         Self::normalize_path(&full_path)
     }
 
+    /// Resolve `--output` exactly as typed, relative to the current directory.
+    ///
+    /// Not canonicalized: on case-insensitive filesystems that would replace
+    /// the user's casing (`./Public`) with an existing directory's (`public`).
+    fn normalize_cli_output(path: &Path) -> PathBuf {
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir().map_or_else(|_| path.to_path_buf(), |cwd| cwd.join(path))
+        };
+        // Drop `.` components (`cwd/./MyOutput` → `cwd/MyOutput`)
+        path.components()
+            .filter(|c| !matches!(c, std::path::Component::CurDir))
+            .collect()
+    }
+
+    /// Normalize a URL path prefix: `"/pr-123/"` → `"pr-123"`.
+    fn normalize_path_prefix(prefix: &str) -> PathBuf {
+        PathBuf::from(prefix.trim().trim_matches('/'))
+    }
+
     /// Normalize a path to absolute, using canonicalize if the path exists.
     fn normalize_path(path: &Path) -> PathBuf {
         path.canonicalize().unwrap_or_else(|_| {
@@ -561,13 +816,126 @@ impl SiteConfig {
                 "[base.url] must be a valid URL with scheme (e.g., https://example.com)".into()
             ));
         }
+
+        let language = &self.base.language;
+        if language.is_empty() {
+            bail!(ConfigError::Validation(
+                "[base.language] must not be empty (e.g., \"en-US\")".into()
+            ));
+        }
         Ok(())
     }
 
+    /// Warning for a `[base.language]` that doesn't look like a BCP 47 tag.
+    ///
+    /// Unusual tags still work in `lang`, so this isn't a validation error.
+    /// Builds report it once they've reset their stats.
+    pub fn language_warning(&self) -> Option<String> {
+        let language = &self.base.language;
+        (!language.is_empty() && !is_plausible_language(language))
+            .then(|| format!("[base.language] doesn't look like a BCP 47 tag: {language}"))
+    }
+
     fn validate_build(&self) -> Result<()> {
+        self.validate_dirs_disjoint()?;
+        self.validate_copy()?;
+        self.validate_output_paths()?;
         self.validate_typst()?;
         self.validate_tailwind()?;
+        self.validate_sass()?;
+        self.validate_postcss()?;
+        self.validate_taxonomy_template(
+            "taxonomy_template",
+            self.build.taxonomy_template.as_deref(),
+        )?;
+        self.validate_taxonomy_template("author_template", self.build.author_template.as_deref())?;
+        self.validate_rss()?;
+        self.validate_archive()?;
         self.validate_inline_max_size()?;
+        self.validate_svg_optimize_level()?;
+        self.validate_svg_convert_jobs()?;
+        self.validate_page_extension()?;
+        Ok(())
+    }
+
+    /// Ensure content/assets/deps/output don't equal or contain one another,
+    /// e.g. `output = "content"` would make the build read its own output.
+    fn validate_dirs_disjoint(&self) -> Result<()> {
+        let build = &self.build;
+        let mut dirs = vec![
+            ("[build.content]", &build.content),
+            ("[build.assets]", &build.assets),
+            ("[build.output]", &build.output),
+        ];
+        dirs.extend(build.deps.iter().map(|dep| ("[build.deps]", dep)));
+
+        let dirs: Vec<_> = dirs
+            .into_iter()
+            .map(|(name, path)| (name, path.canonicalize().unwrap_or_else(|_| path.clone())))
+            .collect();
+
+        for (i, (name_a, a)) in dirs.iter().enumerate() {
+            for (name_b, b) in &dirs[i + 1..] {
+                let msg = if a == b {
+                    format!(
+                        "{name_a} and {name_b} are the same directory: {}",
+                        a.display()
+                    )
+                } else if a.starts_with(b) {
+                    format!(
+                        "{name_a} ({}) is inside {name_b} ({})",
+                        a.display(),
+                        b.display()
+                    )
+                } else if b.starts_with(a) {
+                    format!(
+                        "{name_b} ({}) is inside {name_a} ({})",
+                        b.display(),
+                        a.display()
+                    )
+                } else {
+                    continue;
+                };
+                bail!(ConfigError::Validation(msg));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_copy(&self) -> Result<()> {
+        for entry in &self.build.copy {
+            if !entry.from.exists() {
+                bail!(ConfigError::Validation(format!(
+                    "[build.copy] source not found: {}",
+                    entry.from.display()
+                )));
+            }
+            // `to` is joined onto the output directory and must stay inside it
+            if !stays_inside(&entry.to) {
+                bail!(ConfigError::Validation(format!(
+                    "[build.copy] destination must stay inside the output directory: {}",
+                    entry.to.display()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensure feed and sitemap paths stay inside the output directory
+    /// (e.g. reject `path = "../escape.xml"`).
+    fn validate_output_paths(&self) -> Result<()> {
+        let build = &self.build;
+        let paths = [
+            ("build.rss.path", &build.rss.path),
+            ("build.sitemap.path", &build.sitemap.path),
+        ];
+
+        if let Some((field, path)) = paths.into_iter().find(|(_, path)| !stays_inside(path)) {
+            bail!(ConfigError::Validation(format!(
+                "[{field}] must stay inside the output directory: {}",
+                path.display()
+            )));
+        }
         Ok(())
     }
 
@@ -588,48 +956,246 @@ impl SiteConfig {
             &self.build.css.tailwind.command,
         )?;
 
-        match &self.build.css.tailwind.input {
-            None => bail!(
+        let entries = self.build.css.tailwind.entries();
+        if entries.is_empty() {
+            bail!(
                 "[build.css.tailwind.enable] = true requires [build.css.tailwind.input] to be set"
-            ),
+            );
+        }
+
+        for entry in &entries {
+            let path = &entry.input;
+            if !path.exists() {
+                bail!(ConfigError::Validation(format!(
+                    "[build.css.tailwind.input] not found: {}",
+                    path.display()
+                )));
+            }
+            if !path.is_file() {
+                bail!(ConfigError::Validation(format!(
+                    "[build.css.tailwind.input] is not a file: {}",
+                    path.display()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_sass(&self) -> Result<()> {
+        let sass = &self.build.css.sass;
+        if !sass.enable {
+            return Ok(());
+        }
+
+        Self::check_command_installed("[build.css.sass.command]", &sass.command)?;
+
+        match &sass.input {
+            None => {
+                bail!("[build.css.sass.enable] = true requires [build.css.sass.input] to be set")
+            }
             Some(path) if !path.exists() => {
                 bail!(ConfigError::Validation(
-                    "[build.css.tailwind.input] not found".into()
+                    "[build.css.sass.input] not found".into()
                 ))
             }
             Some(path) if !path.is_file() => {
                 bail!(ConfigError::Validation(
-                    "[build.css.tailwind.input] is not a file".into()
+                    "[build.css.sass.input] is not a file".into()
+                ))
+            }
+            Some(path) if sass.output.is_none() && !path.starts_with(&self.build.assets) => {
+                bail!(ConfigError::Validation(
+                    "[build.css.sass.input] outside [build].assets requires [build.css.sass.output]"
+                        .into()
                 ))
             }
             _ => Ok(()),
         }
     }
 
+    fn validate_rss(&self) -> Result<()> {
+        let Some(image) = &self.build.rss.image else {
+            return Ok(());
+        };
+
+        // RSS 2.0 readers only support these image formats
+        let ext = image
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        if !matches!(ext.as_deref(), Some("gif" | "jpg" | "jpeg" | "png")) {
+            bail!(ConfigError::Validation(format!(
+                "[build.rss.image] must be a GIF, JPEG or PNG image: {}",
+                image.display()
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_archive(&self) -> Result<()> {
+        let Some(path) = &self.build.archive else {
+            return Ok(());
+        };
+
+        if ArchiveFormat::from_path(path).is_none() {
+            bail!(ConfigError::Validation(format!(
+                "[build.archive] must end in .zip, .tar.gz or .tgz: {}",
+                path.display()
+            )));
+        }
+        // Packaging the output into itself would never finish
+        if path.starts_with(&self.build.output) {
+            bail!(ConfigError::Validation(format!(
+                "[build.archive] must be outside the output directory: {}",
+                path.display()
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_taxonomy_template(&self, key: &str, template: Option<&Path>) -> Result<()> {
+        let Some(path) = template else {
+            return Ok(());
+        };
+
+        if !path.is_file() {
+            bail!(ConfigError::Validation(format!(
+                "[build.{key}] not found: {}",
+                path.display()
+            )));
+        }
+        if !self.build.typst.use_lib {
+            // Virtual data files are only visible to the embedded compiler
+            bail!(ConfigError::Validation(format!(
+                "[build.{key}] requires [build.typst].use_lib = true"
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_postcss(&self) -> Result<()> {
+        let postcss = &self.build.css.postcss;
+        if !postcss.enable {
+            return Ok(());
+        }
+
+        Self::check_command_installed("[build.css.postcss.command]", &postcss.command)?;
+
+        if let Some(path) = &postcss.config
+            && !path.exists()
+        {
+            bail!(ConfigError::Validation(
+                "[build.css.postcss.config] not found".into()
+            ));
+        }
+        Ok(())
+    }
+
     fn validate_inline_max_size(&self) -> Result<()> {
         const VALID_SUFFIXES: [&str; 3] = ["B", "KB", "MB"];
-        if !VALID_SUFFIXES
-            .iter()
-            .any(|s| self.build.typst.svg.inline_max_size.ends_with(s))
-        {
+        let svg = &self.build.typst.svg;
+        let sizes = [
+            ("inline_max_size", Some(&svg.inline_max_size)),
+            ("raster_min_size", svg.raster_min_size.as_ref()),
+        ];
+        for (name, size) in sizes {
+            if let Some(size) = size
+                && !VALID_SUFFIXES.iter().any(|s| size.ends_with(s))
+            {
+                bail!(ConfigError::Validation(format!(
+                    "[build.typst.svg.{name}] must end with B, KB, or MB"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_svg_optimize_level(&self) -> Result<()> {
+        if self.build.typst.svg.optimize_level > 2 {
+            bail!(ConfigError::Validation(
+                "[build.typst.svg.optimize_level] must be 0, 1, or 2".into()
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_svg_convert_jobs(&self) -> Result<()> {
+        if self.build.typst.svg.convert_jobs == 0 {
             bail!(ConfigError::Validation(
-                "[build.typst.svg.inline_max_size] must end with B, KB, or MB".into()
+                "[build.typst.svg.convert_jobs] must be at least 1".into()
             ));
         }
         Ok(())
     }
 
+    fn validate_page_extension(&self) -> Result<()> {
+        let ext = &self.build.page_extension;
+        if ext.is_empty() || !ext.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!(ConfigError::Validation(format!(
+                "[build.page_extension] must be a file extension like \"html\", got \"{ext}\""
+            )));
+        }
+        Ok(())
+    }
+
     fn validate_command_specific(&self) -> Result<()> {
         match &self.get_cli().command {
             Commands::Init { .. } if self.get_root().exists() => {
                 bail!("Path already exists");
             }
             Commands::Deploy { .. } => self.validate_deploy()?,
+            Commands::Serve { .. } => self.validate_serve()?,
             _ => {}
         }
         Ok(())
     }
 
+    fn validate_serve(&self) -> Result<()> {
+        // A zero interval would make the poll watcher rescan in a busy loop
+        if self.serve.poll_interval == 0 {
+            bail!(ConfigError::Validation(
+                "[serve.poll_interval] must be greater than 0".into()
+            ));
+        }
+        // Sent verbatim as header values by the dev server
+        let headers = [
+            ("mime_types", &self.serve.mime_types),
+            ("cache_control", &self.serve.cache_control),
+        ];
+        for (section, values) in headers {
+            if let Some((ext, value)) = values
+                .iter()
+                .find(|(_, v)| !v.bytes().all(|b| b.is_ascii() && !b.is_ascii_control()))
+            {
+                bail!(ConfigError::Validation(format!(
+                    "[serve.{section}] {ext}: header value must be printable ASCII: {value:?}"
+                )));
+            }
+        }
+        for rule in &self.serve.proxy {
+            if !rule.prefix.starts_with('/') {
+                bail!(ConfigError::Validation(format!(
+                    "[serve.proxy] prefix must start with '/': {}",
+                    rule.prefix
+                )));
+            }
+            if rule.backend().is_none() {
+                bail!(ConfigError::Validation(format!(
+                    "[serve.proxy] target must be an http:// URL: {}",
+                    rule.target
+                )));
+            }
+        }
+        // Nothing would be served until the first change triggers a rebuild
+        if self.serve.no_build && !self.paths().output_dir().is_dir() {
+            bail!(ConfigError::Validation(format!(
+                "--no-build needs an existing output directory, run `tola build` first: {}",
+                self.paths().output_dir().display()
+            )));
+        }
+        Ok(())
+    }
+
     fn validate_deploy(&self) -> Result<()> {
         if let Some(path) = &self.deploy.github.token_path {
             if !path.exists() {
@@ -646,6 +1212,59 @@ impl SiteConfig {
         Ok(())
     }
 
+    /// Run each environment check independently, for `tola doctor`.
+    ///
+    /// Unlike [`validate`](Self::validate), which stops at the first error,
+    /// every check is reported. Checks for disabled features are omitted.
+    pub fn diagnose(&self) -> Vec<(&'static str, Result<()>)> {
+        let mut checks = vec![
+            ("[base].url", self.validate_base()),
+            ("[build].content", self.validate_content_dir()),
+        ];
+        if !self.build.typst.use_lib {
+            checks.push(("typst", self.validate_typst()));
+        }
+        if let Some(tool) = self.svg_tool() {
+            checks.push((
+                "[build.typst.svg].extract_type",
+                Self::check_command_installed("[build.typst.svg].extract_type", &[tool.into()]),
+            ));
+        }
+        if self.build.css.tailwind.enable {
+            checks.push(("tailwind", self.validate_tailwind()));
+        }
+        if self.build.css.sass.enable {
+            checks.push(("sass", self.validate_sass()));
+        }
+        if self.build.css.postcss.enable {
+            checks.push(("postcss", self.validate_postcss()));
+        }
+        if self.deploy.github.token_path.is_some() {
+            checks.push(("[deploy.github].token_path", self.validate_deploy()));
+        }
+        checks
+    }
+
+    fn validate_content_dir(&self) -> Result<()> {
+        let content = &self.build.content;
+        if !content.is_dir() {
+            bail!(ConfigError::Validation(format!(
+                "[build.content] not found: {}",
+                content.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// External program used to rasterize extracted SVGs, if any.
+    const fn svg_tool(&self) -> Option<&'static str> {
+        match self.build.typst.svg.extract_type {
+            ExtractSvgType::Magick => Some("magick"),
+            ExtractSvgType::Ffmpeg => Some("ffmpeg"),
+            _ => None,
+        }
+    }
+
     /// Check if a command is installed and available.
     fn check_command_installed(field: &str, command: &[String]) -> Result<()> {
         if command.is_empty() {
@@ -837,6 +1456,194 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_lenient_config_drops_unknown_keys() {
+        let config_str = r#"
+            unknown_top = 1
+
+            [base]
+            title = "My Blog"
+            description = "A test blog"
+            tilte = "typo"
+
+            [build]
+            strict_config = false
+            minify = false
+
+            [build.rss]
+            enable = true
+            future_option = "x"
+        "#;
+
+        let config = SiteConfig::from_str(config_str).unwrap();
+        assert_eq!(config.base.title, "My Blog");
+        assert!(!config.build.minify);
+        assert!(config.build.rss.enable);
+
+        let (_, unknown) = SiteConfig::parse(config_str, false).unwrap();
+        assert_eq!(
+            unknown,
+            ["base.tilte", "build.rss.future_option", "unknown_top"]
+        );
+    }
+
+    #[test]
+    fn test_strict_config_rejects_unknown_keys() {
+        let config_str = "[base]\ntitle = \"T\"\ndescription = \"D\"\ntilte = \"typo\"";
+        assert!(SiteConfig::from_str(config_str).is_err());
+        let (_, unknown) = SiteConfig::parse(config_str, true).unwrap();
+        assert_eq!(unknown, ["base.tilte"]);
+
+        // Type errors stay fatal in lenient mode
+        assert!(SiteConfig::parse("[build]\nminify = \"yes\"", true).is_err());
+    }
+
+    #[test]
+    fn test_find_named_config_in_parent() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let nested = dir.path().join("content/posts");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.path().join("tola.toml"), "").unwrap();
+        fs::write(dir.path().join("tola.ci.toml"), "").unwrap();
+
+        let found = find_config_file_from(&nested, Path::new("tola.ci.toml"));
+        assert_eq!(found, Some(dir.path().join("tola.ci.toml")));
+        let found = find_config_file_from(&nested, Path::new("tola.toml"));
+        assert_eq!(found, Some(dir.path().join("tola.toml")));
+        assert_eq!(
+            find_config_file_from(&nested, Path::new("tola.prod.toml")),
+            None
+        );
+
+        let absolute = dir.path().join("tola.ci.toml");
+        assert_eq!(
+            find_config_file_from(Path::new("/"), &absolute),
+            Some(absolute)
+        );
+    }
+
+    #[test]
+    fn test_page_extension_normalized_and_validated() {
+        use clap::Parser;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = r#"
+            [base]
+            title = "Test"
+            description = "Test"
+
+            [build]
+            page_extension = ".HTM"
+        "#;
+        let mut config: SiteConfig = toml::from_str(config).unwrap();
+        let cli: &'static Cli = Box::leak(Box::new(Cli::parse_from(["tola", "build"])));
+        config.cli = Some(cli);
+        config.normalize_paths(dir.path());
+        assert_eq!(config.build.page_extension, "htm");
+        assert_eq!(config.build.index_filename(), "index.htm");
+        config.validate_page_extension().unwrap();
+
+        config.build.page_extension = "ht/m".into();
+        assert!(config.validate_page_extension().is_err());
+        config.build.page_extension = String::new();
+        assert!(config.validate_page_extension().is_err());
+    }
+
+    #[test]
+    fn test_language_normalized_and_validated() {
+        use crate::utils::xml::processor::process_html;
+        use clap::Parser;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = r#"
+            [base]
+            title = "Test"
+            description = "Test"
+            language = "zh_Hans"
+        "#;
+        let mut config: SiteConfig = toml::from_str(config).unwrap();
+        let cli: &'static Cli = Box::leak(Box::new(Cli::parse_from(["tola", "build"])));
+        config.cli = Some(cli);
+        config.normalize_paths(dir.path());
+        assert_eq!(config.base.language, "zh-Hans");
+        config.validate_base().unwrap();
+
+        let html = b"<html><head></head><body></body></html>";
+        let out = process_html(
+            Path::new("/content/index.typ"),
+            Path::new("/out/index.html"),
+            html,
+            &config,
+            0,
+        )
+        .unwrap();
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .contains(r#"<html lang="zh-Hans">"#)
+        );
+
+        assert_eq!(config.language_warning(), None);
+
+        config.base.language = "not a tag".into();
+        config.validate_base().unwrap();
+        let warning = config.language_warning().unwrap();
+        assert!(warning.contains("BCP 47"), "{warning}");
+
+        config.base.language = String::new();
+        let err = config.validate_base().unwrap_err().to_string();
+        assert!(err.contains("[base.language]"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_svg_optimize_level() {
+        let mut config = SiteConfig::default();
+        assert_eq!(config.build.typst.svg.optimize_level, 1);
+        config.build.typst.svg.optimize_level = 2;
+        assert!(config.validate_svg_optimize_level().is_ok());
+        config.build.typst.svg.optimize_level = 3;
+        let err = config
+            .validate_svg_optimize_level()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("optimize_level"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_output_paths() {
+        // Defaults (`feed.xml`, `sitemap.xml`) are fine
+        assert!(SiteConfig::default().validate_output_paths().is_ok());
+
+        let config: SiteConfig = toml::from_str(
+            r#"
+            [build.rss]
+            path = "feeds/../feed.xml"
+        "#,
+        )
+        .unwrap();
+        assert!(config.validate_output_paths().is_ok());
+
+        let config: SiteConfig = toml::from_str(
+            r#"
+            [build.rss]
+            path = "../escape.xml"
+        "#,
+        )
+        .unwrap();
+        let err = config.validate_output_paths().unwrap_err().to_string();
+        assert!(err.contains("build.rss.path"), "{err}");
+
+        let config: SiteConfig = toml::from_str(
+            r#"
+            [build.sitemap]
+            path = "/etc/sitemap.xml"
+        "#,
+        )
+        .unwrap();
+        let err = config.validate_output_paths().unwrap_err().to_string();
+        assert!(err.contains("build.sitemap.path"), "{err}");
+    }
+
     #[test]
     fn test_get_root_default() {
         let config = SiteConfig::default();
@@ -1039,4 +1846,320 @@ mod tests {
         let result: Result<SiteConfig, _> = toml::from_str(config);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_base_origin() {
+        let mut config = SiteConfig::default();
+        assert_eq!(config.base_origin(), "");
+
+        config.base.url = Some("https://example.com".into());
+        assert_eq!(config.base_origin(), "https://example.com");
+        assert_eq!(config.site_url(), "https://example.com/");
+
+        config.base.url = Some("https://example.github.io/my-project/".into());
+        config.sync_path_prefix_from_url();
+        assert_eq!(config.base_origin(), "https://example.github.io");
+        assert_eq!(config.site_url(), "https://example.github.io/my-project/");
+    }
+
+    fn make_dirs_config(dir: &Path) -> SiteConfig {
+        let mut config = SiteConfig::default();
+        config.build.content = dir.join("content");
+        config.build.assets = dir.join("assets");
+        config.build.output = dir.join("public");
+        config.build.deps = vec![dir.join("templates"), dir.join("utils")];
+        config
+    }
+
+    #[test]
+    fn test_validate_dirs_disjoint() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(make_dirs_config(dir.path()).validate_build().is_ok());
+    }
+
+    #[test]
+    fn test_validate_output_inside_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = make_dirs_config(dir.path());
+        config.build.output = dir.path().join("content/public");
+
+        let err = config.validate_build().unwrap_err().to_string();
+        assert!(err.contains("[build.output]"), "{err}");
+        assert!(err.contains("is inside [build.content]"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_content_equals_assets() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("content")).unwrap();
+        let mut config = make_dirs_config(dir.path());
+        // Different spelling, same directory after canonicalization
+        config.build.assets = dir.path().join("content/.");
+
+        let err = config.validate_build().unwrap_err().to_string();
+        assert!(
+            err.contains("[build.content] and [build.assets] are the same directory"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_copy_entry_resolve() {
+        let root = Path::new("/site");
+        let entry = CopyEntry {
+            from: "extra/humans.txt".into(),
+            to: PathBuf::new(),
+        };
+        assert_eq!(
+            entry.resolve(root),
+            CopyEntry {
+                from: "/site/extra/humans.txt".into(),
+                to: "extra/humans.txt".into(),
+            }
+        );
+
+        let entry = CopyEntry {
+            from: "/etc/ads.txt".into(),
+            to: PathBuf::new(),
+        };
+        assert_eq!(entry.resolve(root).to, PathBuf::from("ads.txt"));
+    }
+
+    #[test]
+    fn test_validate_copy_source_missing() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = make_dirs_config(dir.path());
+        config.build.copy = vec![CopyEntry {
+            from: dir.path().join("missing.txt"),
+            to: "missing.txt".into(),
+        }];
+
+        let err = config.validate_build().unwrap_err().to_string();
+        assert!(err.contains("[build.copy] source not found"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_copy_destination_outside_output() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = make_dirs_config(dir.path());
+        fs::write(dir.path().join("humans.txt"), "").unwrap();
+
+        for to in ["../humans.txt", "/etc/humans.txt", "a/../../humans.txt"] {
+            config.build.copy = vec![CopyEntry {
+                from: dir.path().join("humans.txt"),
+                to: to.into(),
+            }];
+            let err = config.validate_build().unwrap_err().to_string();
+            assert!(err.contains("[build.copy] destination"), "{to}: {err}");
+        }
+
+        for to in ["meta/humans.txt", "meta/../humans.txt"] {
+            config.build.copy[0].to = to.into();
+            config.validate_build().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_diagnose_reports_missing_tool() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.content = dir.path().to_path_buf();
+        config.build.css.tailwind.enable = true;
+        config.build.css.tailwind.command = vec!["tola-no-such-tailwind".into()];
+
+        let checks = config.diagnose();
+        let (_, result) = checks.iter().find(|(name, _)| *name == "tailwind").unwrap();
+        let err = result.as_ref().unwrap_err().to_string();
+        assert!(err.contains("`tola-no-such-tailwind` not found"), "{err}");
+
+        // Other checks still run
+        let (_, content) = checks
+            .iter()
+            .find(|(name, _)| *name == "[build].content")
+            .unwrap();
+        assert!(content.is_ok());
+    }
+
+    #[test]
+    fn test_validate_rss_image_type() {
+        let mut config = SiteConfig::default();
+        config.build.rss.image = Some("images/logo.PNG".into());
+        assert!(config.validate_rss().is_ok());
+
+        config.build.rss.image = Some("images/logo.svg".into());
+        let err = config.validate_rss().unwrap_err().to_string();
+        assert!(
+            err.contains("[build.rss.image] must be a GIF, JPEG or PNG"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_cli_output_preserves_case() {
+        use clap::Parser;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        // An existing lowercase directory must not capture the typed casing
+        fs::create_dir(dir.path().join("myoutput")).unwrap();
+
+        let cli: &'static Cli = Box::leak(Box::new(
+            Cli::try_parse_from(["tola", "-o", "./MyOutput", "build"]).unwrap(),
+        ));
+        let mut config = SiteConfig {
+            config_path: dir.path().join("tola.toml"),
+            cli: Some(cli),
+            ..Default::default()
+        };
+        config.finalize(cli);
+
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(config.build.output, cwd.join("MyOutput"));
+        assert_eq!(config.build.output.file_name().unwrap(), "MyOutput");
+    }
+
+    #[test]
+    fn test_config_print_reflects_output_override() {
+        use clap::Parser;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let cli: &'static Cli = Box::leak(Box::new(
+            Cli::try_parse_from([
+                "tola",
+                "-o",
+                "./MyOutput",
+                "config",
+                "print",
+                "--minify=false",
+            ])
+            .unwrap(),
+        ));
+        let mut config = SiteConfig {
+            config_path: dir.path().join("tola.toml"),
+            cli: Some(cli),
+            ..Default::default()
+        };
+        config.finalize(cli);
+
+        let cwd = std::env::current_dir().unwrap();
+        let toml: toml::Value = toml::from_str(&config.to_pretty_string(false).unwrap()).unwrap();
+        assert_eq!(
+            toml["build"]["output"].as_str(),
+            cwd.join("MyOutput").to_str()
+        );
+        assert_eq!(toml["build"]["minify"].as_bool(), Some(false));
+        assert_eq!(
+            toml["build"]["content"].as_str(),
+            dir.path().join("content").to_str()
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&config.to_pretty_string(true).unwrap()).unwrap();
+        assert_eq!(
+            json["build"]["output"].as_str(),
+            cwd.join("MyOutput").to_str()
+        );
+    }
+
+    #[test]
+    fn test_input_flags_merge_over_config() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["tola", "build", "--input", "foo=bar", "--input", "eq=a=b"])
+            .unwrap();
+        assert!(Cli::try_parse_from(["tola", "build", "--input", "noequals"]).is_err());
+
+        let mut config = SiteConfig::default();
+        config.build.typst.inputs.insert("foo".into(), "old".into());
+        config.build.typst.inputs.insert("keep".into(), "1".into());
+        config.apply_command_options(&cli);
+
+        let inputs = &config.build.typst.inputs;
+        assert_eq!(inputs["foo"], "bar");
+        assert_eq!(inputs["eq"], "a=b");
+        assert_eq!(inputs["keep"], "1");
+    }
+
+    #[test]
+    fn test_serve_no_build_requires_existing_output() {
+        use clap::Parser;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let cli = Cli::try_parse_from(["tola", "serve", "--no-build"]).unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().join("public");
+        config.apply_command_options(&cli);
+        assert!(config.serve.no_build);
+
+        let err = config.validate_serve().unwrap_err();
+        assert!(err.to_string().contains("--no-build"), "{err}");
+
+        // Pre-existing output is accepted and served as is
+        fs::create_dir_all(&config.build.output).unwrap();
+        config.validate_serve().unwrap();
+    }
+
+    #[test]
+    fn test_empty_path_prefix_flag_clears_base_url_path() {
+        use clap::Parser;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let cli: &'static Cli = Box::leak(Box::new(
+            Cli::try_parse_from(["tola", "build", "--path-prefix", "/"]).unwrap(),
+        ));
+        let mut config = SiteConfig {
+            config_path: dir.path().join("tola.toml"),
+            cli: Some(cli),
+            ..Default::default()
+        };
+        config.base.url = Some("https://example.com/blog".into());
+        config.finalize(cli);
+
+        assert_eq!(config.build.path_prefix, PathBuf::new());
+        assert_eq!(config.paths().url_for_filename("feed.xml"), "/feed.xml");
+    }
+
+    #[test]
+    fn test_path_prefix_flag_overrides_base_url() {
+        use crate::compiler::meta::PageMeta;
+        use crate::utils::xml::link::process_absolute_link;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from([
+            "tola",
+            "build",
+            "--base-url",
+            "https://example.com/blog",
+            "--path-prefix",
+            "/pr-123/",
+        ])
+        .unwrap();
+
+        let mut config = SiteConfig::default();
+        config.build.content = PathBuf::from("/site/content");
+        config.build.output = PathBuf::from("/site/public");
+        config.apply_command_options(&cli);
+        config.sync_path_prefix_from_url();
+
+        assert_eq!(config.build.path_prefix, PathBuf::from("pr-123"));
+        assert_eq!(
+            config.paths().url_for_filename("feed.xml"),
+            "/pr-123/feed.xml"
+        );
+        assert_eq!(
+            process_absolute_link("/posts/hello", &config).unwrap(),
+            "/pr-123/posts/hello"
+        );
+
+        let page = PageMeta::from_paths("/site/content/posts/hello.typ".into(), &config).unwrap();
+        assert_eq!(page.paths.url_path, "/pr-123/posts/hello/");
+        assert_eq!(
+            page.paths.full_url,
+            "https://example.com/pr-123/posts/hello/"
+        );
+        assert_eq!(config.site_url(), "https://example.com/pr-123/");
+        assert_eq!(
+            page.paths.html,
+            PathBuf::from("/site/public/pr-123/posts/hello/index.html")
+        );
+    }
 }
@@ -5,6 +5,18 @@
 use super::defaults;
 use educe::Educe;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// File watcher backend.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchMode {
+    /// OS file events (inotify, FSEvents, ...) (default).
+    #[default]
+    Native,
+    /// Periodic mtime scans, for filesystems without events (NFS, some VMs).
+    Poll,
+}
 
 /// `[serve]` section in tola.toml - development server settings.
 ///
@@ -14,6 +26,18 @@ use serde::{Deserialize, Serialize};
 /// interface = "0.0.0.0"  # Listen on all interfaces
 /// port = 3000
 /// watch = true           # Auto-rebuild on file changes
+/// watch_mode = "poll"    # Scan for changes instead of OS events
+/// poll_interval = 1000   # Scan interval in milliseconds
+///
+/// [serve.mime_types]
+/// glb = "model/gltf-binary"
+///
+/// [serve.cache_control]
+/// js = "no-store"
+///
+/// [[serve.proxy]]
+/// prefix = "/api"
+/// target = "http://localhost:8000"
 /// ```
 #[derive(Debug, Clone, Educe, Serialize, Deserialize)]
 #[educe(Default)]
@@ -22,6 +46,8 @@ pub struct ServeConfig {
     /// Network interface to bind.
     /// - `127.0.0.1` (default): localhost only
     /// - `0.0.0.0`: all interfaces (LAN accessible)
+    /// - `::`: all interfaces over IPv6 and IPv4
+    /// - `localhost`: both `127.0.0.1` and `::1`
     #[serde(default = "defaults::serve::interface")]
     #[educe(Default = defaults::serve::interface())]
     pub interface: String,
@@ -35,11 +61,80 @@ pub struct ServeConfig {
     #[serde(default = "defaults::r#true")]
     #[educe(Default = true)]
     pub watch: bool,
+
+    /// File watcher backend: `"native"` or `"poll"`.
+    #[serde(default)]
+    pub watch_mode: WatchMode,
+
+    /// Interval between scans in `poll` mode, in milliseconds (default: 1000).
+    #[serde(default = "defaults::serve::poll_interval")]
+    #[educe(Default = defaults::serve::poll_interval())]
+    pub poll_interval: u64,
+
+    /// Content-Type overrides by file extension (e.g. `wasm = "application/wasm"`).
+    #[serde(default)]
+    pub mime_types: HashMap<String, String>,
+
+    /// `Cache-Control` overrides by file extension.
+    /// Defaults: `no-cache` (HTML and plain assets), one year `immutable` for
+    /// fingerprinted assets like `.enhance-{hash}.css`.
+    #[serde(default)]
+    pub cache_control: HashMap<String, String>,
+
+    /// Backends that requests under a path prefix are forwarded to,
+    /// instead of being served from the output directory.
+    #[serde(default)]
+    pub proxy: Vec<ProxyRule>,
+
+    /// Skip the initial build and serve the existing output (`tola serve --no-build`);
+    /// page metadata is still collected for `/_data` and watch rebuilds.
+    #[serde(skip)]
+    pub no_build: bool,
+}
+
+/// `[[serve.proxy]]` entry: forward requests under `prefix` to `target`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyRule {
+    /// URL path prefix, matched on whole segments (`/api` matches `/api/x`
+    /// but not `/apis`). The prefix is kept in the forwarded path.
+    pub prefix: String,
+
+    /// Backend origin, optionally with a base path (`http://localhost:8000`).
+    /// Only plain `http://` is supported.
+    pub target: String,
+}
+
+impl ProxyRule {
+    /// Split `target` into its `host:port` and base path (without trailing `/`).
+    ///
+    /// Returns `None` for anything but an `http://` URL with a host.
+    pub fn backend(&self) -> Option<(String, &str)> {
+        let rest = self.target.strip_prefix("http://")?;
+        let (host, path) = rest.find('/').map_or((rest, ""), |i| rest.split_at(i));
+        if host.is_empty() {
+            return None;
+        }
+        let authority = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{host}:80")
+        };
+        Some((authority, path.trim_end_matches('/')))
+    }
+
+    /// Check if a request URL (path with optional query) falls under `prefix`.
+    pub fn matches(&self, url: &str) -> bool {
+        let prefix = self.prefix.trim_end_matches('/');
+        url.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::SiteConfig;
+    use super::WatchMode;
 
     #[test]
     fn test_serve_config() {
@@ -72,6 +167,72 @@ mod tests {
         assert_eq!(config.serve.interface, "127.0.0.1");
         assert_eq!(config.serve.port, 5277);
         assert!(config.serve.watch);
+        assert_eq!(config.serve.watch_mode, WatchMode::Native);
+        assert_eq!(config.serve.poll_interval, 1000);
+    }
+
+    #[test]
+    fn test_serve_config_watch_poll() {
+        let config = r#"
+            [base]
+            title = "Test"
+            description = "Test"
+            [serve]
+            watch_mode = "poll"
+            poll_interval = 250
+        "#;
+        let mut config: SiteConfig = toml::from_str(config).unwrap();
+        assert_eq!(config.serve.watch_mode, WatchMode::Poll);
+        assert_eq!(config.serve.poll_interval, 250);
+        config.validate_serve().unwrap();
+
+        config.serve.poll_interval = 0;
+        let err = config.validate_serve().unwrap_err().to_string();
+        assert!(err.contains("[serve.poll_interval]"), "{err}");
+    }
+
+    #[test]
+    fn test_serve_header_values_validated() {
+        let mut config = SiteConfig::default();
+        config
+            .serve
+            .cache_control
+            .insert("js".into(), "no-store".into());
+        config.validate_serve().unwrap();
+
+        config
+            .serve
+            .cache_control
+            .insert("css".into(), "no-cache\r\nX-Injected: 1".into());
+        let err = config.validate_serve().unwrap_err().to_string();
+        assert!(err.contains("[serve.cache_control] css"), "{err}");
+
+        config.serve.cache_control.clear();
+        config
+            .serve
+            .mime_types
+            .insert("txt".into(), "text/plain; charset=ütf-8".into());
+        let err = config.validate_serve().unwrap_err().to_string();
+        assert!(err.contains("[serve.mime_types] txt"), "{err}");
+    }
+
+    #[test]
+    fn test_serve_config_proxy() {
+        let config = r#"
+            [base]
+            title = "Test"
+            description = "Test"
+            [[serve.proxy]]
+            prefix = "/api"
+            target = "http://localhost:8000/v1/"
+        "#;
+        let config: SiteConfig = toml::from_str(config).unwrap();
+        let rule = &config.serve.proxy[0];
+        assert_eq!(rule.backend(), Some(("localhost:8000".to_string(), "/v1")));
+        assert!(rule.matches("/api"));
+        assert!(rule.matches("/api/x?y=1"));
+        assert!(!rule.matches("/apis"));
+        assert!(!rule.matches("/posts/"));
     }
 
     #[test]
@@ -38,12 +38,17 @@
 //! |------|-------------|
 //! | `/_data/pages.json` | All pages with metadata |
 //! | `/_data/tags.json` | Pages grouped by tag |
+//! | `/_data/sections.json` | Pages grouped by section |
+//! | `/_data/authors.json` | Pages grouped by author |
+//! | `/_data/git.json` | HEAD commit of the project repo (`{}` if none) |
+//! | `/_data/current_taxonomy.json` | Term rendered by `[build].taxonomy_template` or `[build].author_template` (`{}` otherwise) |
 //!
 //! # Usage in Typst
 //!
 //! ```typst
 //! #let pages = json("/_data/pages.json")
 //! #let tags = json("/_data/tags.json")
+//! #let git = json("/_data/git.json")
 //!
 //! // List all posts
 //! #for page in pages.sorted(key: p => p.date).rev() {
@@ -55,6 +60,9 @@
 //!     [== #tag]
 //!     #for post in posts { [- #link(post.url)[#post.title]] }
 //! }
+//!
+//! // Footer: "built from abc1234"
+//! #if "short_hash" in git [built from #git.short_hash]
 //! ```
 
 mod store;
@@ -62,5 +70,5 @@ mod types;
 pub mod virtual_fs;
 
 pub use store::GLOBAL_SITE_DATA;
-pub use types::PageData;
+pub use types::{PageData, TaggedPage, TaxonomyTerm};
 pub use virtual_fs::{is_virtual_data_path, read_virtual_data};
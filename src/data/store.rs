@@ -13,7 +13,10 @@ use std::sync::LazyLock;
 
 use parking_lot::RwLock;
 
-use super::types::{PageData, TaggedPage, TagsIndex};
+use super::types::{
+    AuthorsIndex, PageData, PageLink, SectionsIndex, TaggedPage, TagsIndex, TaxonomyTerm,
+};
+use crate::utils::git::GitInfo;
 
 /// Cached JSON strings for virtual data files.
 ///
@@ -22,6 +25,8 @@ use super::types::{PageData, TaggedPage, TagsIndex};
 struct JsonCache {
     pages: Option<String>,
     tags: Option<String>,
+    sections: Option<String>,
+    authors: Option<String>,
 }
 
 /// Compare two date strings for sorting (newest first).
@@ -43,6 +48,25 @@ fn compare_by_date<T: AsRef<str>>(
     }
 }
 
+/// Set `prev` (older) / `next` (newer) on dated pages sorted newest first.
+///
+/// Undated pages sort last and stay out of the chain.
+fn link_neighbors(pages: &mut [PageData]) {
+    let dated = pages.iter().take_while(|p| p.date.is_some()).count();
+    let links: Vec<_> = pages[..dated]
+        .iter()
+        .map(|p| PageLink {
+            url: p.url.clone(),
+            title: p.title.clone(),
+        })
+        .collect();
+
+    for (i, page) in pages[..dated].iter_mut().enumerate() {
+        page.next = i.checked_sub(1).map(|j| links[j].clone());
+        page.prev = links.get(i + 1).cloned();
+    }
+}
+
 /// Global site data store, accessible from anywhere in the compilation process.
 ///
 /// This is initialized lazily and can be reset between builds (e.g., in watch mode).
@@ -65,6 +89,10 @@ pub struct SiteDataStore {
     pages: RwLock<BTreeMap<String, PageData>>,
     /// Cached JSON output. Invalidated on any write operation.
     json_cache: RwLock<JsonCache>,
+    /// Serialized git info, set once per build (not affected by `clear()`).
+    git: RwLock<Option<String>>,
+    /// Serialized taxonomy term being rendered (not affected by `clear()`).
+    current_taxonomy: RwLock<Option<String>>,
 }
 
 impl SiteDataStore {
@@ -95,10 +123,12 @@ impl SiteDataStore {
     ///
     /// Pages are sorted by date (newest first), then by title.
     /// Draft pages are excluded from the output.
+    /// Dated pages are linked to their chronological neighbors via `prev`/`next`.
     pub fn get_pages(&self) -> Vec<PageData> {
         let pages = self.pages.read();
         let mut result: Vec<_> = pages.values().filter(|p| !p.draft).cloned().collect();
         result.sort_by(|a, b| compare_by_date(&a.date, &b.date, &a.title, &b.title));
+        link_neighbors(&mut result);
         result
     }
 
@@ -132,6 +162,59 @@ impl SiteDataStore {
         tags
     }
 
+    /// Build the sections index from stored pages.
+    ///
+    /// Returns a map from section name to list of pages in that section.
+    pub fn get_sections_index(&self) -> SectionsIndex {
+        let pages = self.pages.read();
+        let mut sections: SectionsIndex = BTreeMap::new();
+
+        for page in pages.values().filter(|p| !p.draft) {
+            if let Some(section) = &page.section {
+                sections
+                    .entry(section.clone())
+                    .or_default()
+                    .push(TaggedPage {
+                        url: page.url.clone(),
+                        title: page.title.clone(),
+                        date: page.date.clone(),
+                    });
+            }
+        }
+
+        // Sort pages within each section by date (newest first)
+        for pages in sections.values_mut() {
+            pages.sort_by(|a, b| compare_by_date(&a.date, &b.date, &a.title, &b.title));
+        }
+
+        sections
+    }
+
+    /// Build the authors index from stored pages.
+    ///
+    /// Returns a map from author name to list of pages they wrote.
+    pub fn get_authors_index(&self) -> AuthorsIndex {
+        let pages = self.pages.read();
+        let mut authors: AuthorsIndex = BTreeMap::new();
+
+        for page in pages.values().filter(|p| !p.draft) {
+            for author in &page.authors {
+                authors.entry(author.clone()).or_default().push(TaggedPage {
+                    url: page.url.clone(),
+                    title: page.title.clone(),
+                    date: page.date.clone(),
+                });
+            }
+        }
+
+        // Sort pages within each author by date (newest first)
+        for pages in authors.values_mut() {
+            pages.sort_by(|a, b| compare_by_date(&a.date, &b.date, &a.title, &b.title));
+        }
+
+        authors
+    }
+
     /// Serialize pages to JSON with caching.
     ///
     /// First call generates JSON, subsequent calls return cached value.
@@ -184,6 +267,74 @@ impl SiteDataStore {
         json
     }
 
+    /// Serialize sections index to JSON with caching.
+    ///
+    /// Cache is invalidated by `insert_page()` or `clear()`.
+    pub fn sections_to_json(&self) -> String {
+        {
+            let cache = self.json_cache.read();
+            if let Some(ref json) = cache.sections {
+                return json.clone();
+            }
+        }
+
+        let mut cache = self.json_cache.write();
+        if let Some(ref json) = cache.sections {
+            return json.clone();
+        }
+
+        let sections = self.get_sections_index();
+        let json = serde_json::to_string_pretty(&sections).unwrap_or_else(|_| "{}".to_string());
+        cache.sections = Some(json.clone());
+        json
+    }
+
+    /// Serialize authors index to JSON with caching.
+    ///
+    /// Cache is invalidated by `insert_page()` or `clear()`.
+    pub fn authors_to_json(&self) -> String {
+        {
+            let cache = self.json_cache.read();
+            if let Some(ref json) = cache.authors {
+                return json.clone();
+            }
+        }
+
+        let mut cache = self.json_cache.write();
+        if let Some(ref json) = cache.authors {
+            return json.clone();
+        }
+
+        let authors = self.get_authors_index();
+        let json = serde_json::to_string_pretty(&authors).unwrap_or_else(|_| "{}".to_string());
+        cache.authors = Some(json.clone());
+        json
+    }
+
+    /// Set the project's git info, or `None` outside a git repository.
+    pub fn set_git(&self, info: Option<&GitInfo>) {
+        *self.git.write() = info.and_then(|info| serde_json::to_string_pretty(info).ok());
+    }
+
+    /// Serialize git info to JSON (empty object if unavailable).
+    pub fn git_to_json(&self) -> String {
+        self.git.read().clone().unwrap_or_else(|| "{}".to_string())
+    }
+
+    /// Set the taxonomy term being rendered, or `None` once done.
+    pub fn set_current_taxonomy(&self, term: Option<&TaxonomyTerm>) {
+        *self.current_taxonomy.write() =
+            term.and_then(|term| serde_json::to_string_pretty(term).ok());
+    }
+
+    /// Serialize the current taxonomy term to JSON (empty object if unset).
+    pub fn current_taxonomy_to_json(&self) -> String {
+        self.current_taxonomy
+            .read()
+            .clone()
+            .unwrap_or_else(|| "{}".to_string())
+    }
+
     /// Check if the store has any data.
     #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
@@ -209,22 +360,36 @@ mod tests {
             url: "/posts/first/".to_string(),
             title: "First Post".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: Some("2024-01-15".to_string()),
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec!["rust".to_string()],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         store.insert_page(PageData {
             url: "/posts/second/".to_string(),
             title: "Second Post".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: Some("2024-01-20".to_string()),
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec!["rust".to_string(), "web".to_string()],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         let pages = store.get_pages();
@@ -242,22 +407,36 @@ mod tests {
             url: "/a/".to_string(),
             title: "A".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: Some("2024-01-10".to_string()),
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec!["rust".to_string()],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         store.insert_page(PageData {
             url: "/b/".to_string(),
             title: "B".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: Some("2024-01-20".to_string()),
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec!["rust".to_string(), "web".to_string()],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         let tags = store.get_tags_index();
@@ -278,11 +457,18 @@ mod tests {
             url: "/draft/".to_string(),
             title: "Draft".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: None,
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec!["test".to_string()],
+            section: None,
             draft: true,
+            prev: None,
+            next: None,
         });
 
         let tags = store.get_tags_index();
@@ -297,22 +483,36 @@ mod tests {
             url: "/published/".to_string(),
             title: "Published".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: Some("2024-01-15".to_string()),
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec![],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         store.insert_page(PageData {
             url: "/draft/".to_string(),
             title: "Draft".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: Some("2024-01-20".to_string()),
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec![],
+            section: None,
             draft: true,
+            prev: None,
+            next: None,
         });
 
         let pages = store.get_pages();
@@ -328,22 +528,36 @@ mod tests {
             url: "/b/".to_string(),
             title: "Beta".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: None,
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec![],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         store.insert_page(PageData {
             url: "/a/".to_string(),
             title: "Alpha".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: None,
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec![],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         let pages = store.get_pages();
@@ -361,22 +575,36 @@ mod tests {
             url: "/no-date/".to_string(),
             title: "No Date".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: None,
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec![],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         store.insert_page(PageData {
             url: "/has-date/".to_string(),
             title: "Has Date".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: Some("2024-01-15".to_string()),
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec![],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         let pages = store.get_pages();
@@ -386,6 +614,134 @@ mod tests {
         assert_eq!(pages[1].title, "No Date");
     }
 
+    #[test]
+    fn test_sections_index() {
+        let store = SiteDataStore::new();
+
+        for (url, title, date, section) in [
+            ("/posts/a/", "A", "2024-01-10", Some("posts")),
+            ("/posts/b/", "B", "2024-01-20", Some("posts")),
+            ("/notes/c/", "C", "2024-01-15", Some("journal")),
+            ("/about/", "About", "2024-01-01", None),
+        ] {
+            store.insert_page(PageData {
+                url: url.to_string(),
+                title: title.to_string(),
+                summary: None,
+                summary_html: None,
+                has_more: false,
+                date: Some(date.to_string()),
+                date_display: None,
+                update: None,
+                author: None,
+                authors: Vec::new(),
+                tags: vec![],
+                section: section.map(str::to_string),
+                draft: false,
+                prev: None,
+                next: None,
+            });
+        }
+
+        let sections = store.get_sections_index();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections["posts"].len(), 2);
+        assert_eq!(sections["posts"][0].title, "B");
+        assert_eq!(sections["journal"][0].url, "/notes/c/");
+    }
+
+    #[test]
+    fn test_authors_index() {
+        let store = SiteDataStore::new();
+
+        for (url, date, authors, draft) in [
+            ("/a/", "2024-01-10", vec!["Alice"], false),
+            ("/b/", "2024-01-20", vec!["Alice", "Bob"], false),
+            ("/c/", "2024-01-30", vec!["Carol"], true),
+        ] {
+            store.insert_page(PageData {
+                url: url.to_string(),
+                title: url.trim_matches('/').to_uppercase(),
+                summary: None,
+                summary_html: None,
+                has_more: false,
+                date: Some(date.to_string()),
+                date_display: None,
+                update: None,
+                author: authors.first().map(|a| a.to_string()),
+                authors: authors.iter().map(|a| a.to_string()).collect(),
+                tags: vec![],
+                section: None,
+                draft,
+                prev: None,
+                next: None,
+            });
+        }
+
+        let authors = store.get_authors_index();
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors["Alice"].len(), 2);
+        assert_eq!(authors["Alice"][0].title, "B");
+        assert_eq!(authors["Bob"][0].url, "/b/");
+        assert!(!authors.contains_key("Carol"));
+    }
+
+    #[test]
+    fn test_prev_next_links() {
+        let store = SiteDataStore::new();
+
+        for (url, date) in [
+            ("/a/", Some("2024-01-10")),
+            ("/b/", Some("2024-01-20")),
+            ("/c/", Some("2024-01-30")),
+            ("/about/", None),
+        ] {
+            store.insert_page(PageData {
+                url: url.to_string(),
+                title: url.trim_matches('/').to_uppercase(),
+                summary: None,
+                summary_html: None,
+                has_more: false,
+                date: date.map(str::to_string),
+                date_display: None,
+                update: None,
+                author: None,
+                authors: Vec::new(),
+                tags: vec![],
+                section: None,
+                draft: false,
+                prev: None,
+                next: None,
+            });
+        }
+
+        let link = |url: &str| {
+            Some(PageLink {
+                url: url.to_string(),
+                title: url.trim_matches('/').to_uppercase(),
+            })
+        };
+        let pages = store.get_pages();
+        let urls: Vec<_> = pages.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(urls, ["/c/", "/b/", "/a/", "/about/"]);
+
+        // Newest has only an older neighbor, oldest only a newer one
+        assert_eq!(
+            (pages[0].prev.clone(), pages[0].next.clone()),
+            (link("/b/"), None)
+        );
+        assert_eq!(
+            (pages[1].prev.clone(), pages[1].next.clone()),
+            (link("/a/"), link("/c/"))
+        );
+        assert_eq!(
+            (pages[2].prev.clone(), pages[2].next.clone()),
+            (None, link("/b/"))
+        );
+        // Undated pages are not in the chain
+        assert_eq!((pages[3].prev.clone(), pages[3].next.clone()), (None, None));
+    }
+
     #[test]
     fn test_clear() {
         let store = SiteDataStore::new();
@@ -394,15 +750,45 @@ mod tests {
             url: "/test/".to_string(),
             title: "Test".to_string(),
             summary: None,
+            summary_html: None,
+            has_more: false,
             date: None,
+            date_display: None,
             update: None,
             author: None,
+            authors: Vec::new(),
             tags: vec![],
+            section: None,
             draft: false,
+            prev: None,
+            next: None,
         });
 
         assert!(!store.is_empty());
         store.clear();
         assert!(store.is_empty());
     }
+
+    #[test]
+    fn test_git_json() {
+        let store = SiteDataStore::new();
+        assert_eq!(store.git_to_json(), "{}");
+
+        store.set_git(Some(&GitInfo {
+            hash: "abc1234def".to_string(),
+            short_hash: "abc1234".to_string(),
+            date: None,
+            branch: Some("main".to_string()),
+        }));
+        // Survives per-build page clearing
+        store.clear();
+
+        let json: serde_json::Value = serde_json::from_str(&store.git_to_json()).unwrap();
+        assert_eq!(json["short_hash"], "abc1234");
+        assert_eq!(json["branch"], "main");
+        assert!(json.get("date").is_none());
+
+        store.set_git(None);
+        assert_eq!(store.git_to_json(), "{}");
+    }
 }
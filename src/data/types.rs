@@ -19,25 +19,63 @@ pub struct PageData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub summary: Option<String>,
 
+    /// Body HTML before the "read more" marker, if the page has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_html: Option<String>,
+
+    /// Whether the body continues past a "read more" marker
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub has_more: bool,
+
     /// Publication date as ISO 8601 string (e.g., "2024-01-15")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub date: Option<String>,
 
+    /// `date` formatted with `[build].date_format` (e.g., "January 15, 2024")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_display: Option<String>,
+
     /// Last update date as ISO 8601 string
     #[serde(skip_serializing_if = "Option::is_none")]
     pub update: Option<String>,
 
-    /// Author name
+    /// First author's name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<String>,
 
+    /// All author names, in metadata order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+
     /// Tags associated with this page
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 
+    /// Section the page belongs to (e.g., "posts")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+
     /// Whether this is a draft (not published)
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub draft: bool,
+
+    /// Next older dated page (set when listing pages)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<PageLink>,
+
+    /// Next newer dated page (set when listing pages)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<PageLink>,
+}
+
+/// A chronological neighbor of a page, see [`PageData::prev`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PageLink {
+    /// Page URL path
+    pub url: String,
+
+    /// Page title
+    pub title: String,
 }
 
 /// Tags index, exposed in `/_data/tags.json`.
@@ -46,9 +84,21 @@ pub struct PageData {
 /// Sorted alphabetically by tag name.
 pub type TagsIndex = std::collections::BTreeMap<String, Vec<TaggedPage>>;
 
-/// A page reference within a tag index.
+/// Sections index, exposed in `/_data/sections.json`.
+///
+/// Maps section names to lists of pages in that section.
+/// Sorted alphabetically by section name.
+pub type SectionsIndex = std::collections::BTreeMap<String, Vec<TaggedPage>>;
+
+/// Authors index, exposed in `/_data/authors.json`.
+///
+/// Maps author names to lists of pages they wrote.
+/// Sorted alphabetically by author name.
+pub type AuthorsIndex = std::collections::BTreeMap<String, Vec<TaggedPage>>;
+
+/// A page reference within a tag, section or author index.
 ///
-/// Contains minimal information for listing pages by tag.
+/// Contains minimal information for listing pages by tag, section or author.
 #[derive(Debug, Clone, Serialize)]
 pub struct TaggedPage {
     /// Page URL path
@@ -61,3 +111,22 @@ pub struct TaggedPage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub date: Option<String>,
 }
+
+/// The taxonomy term being rendered, exposed in `/_data/current_taxonomy.json`.
+///
+/// Set while `[build].taxonomy_template` is compiled for each tag or section,
+/// and while `[build].author_template` is compiled for each author.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxonomyTerm {
+    /// Taxonomy kind ("tags", "sections" or "authors")
+    pub kind: String,
+
+    /// Term name as written in page metadata
+    pub name: String,
+
+    /// URL path of the term's index page (e.g., "/tags/rust/")
+    pub url: String,
+
+    /// Pages with this term, newest first
+    pub pages: Vec<TaggedPage>,
+}
@@ -10,13 +10,18 @@ use super::store::GLOBAL_SITE_DATA;
 /// Canonical virtual data directory path (used for dependency tracking).
 pub const VIRTUAL_DATA_DIR: &str = "/_data";
 
-/// Known virtual files and their generators.
 /// Known virtual files and their generators.
 type VirtualFileGenerator = fn() -> String;
 
 const VIRTUAL_FILES: &[(&str, VirtualFileGenerator)] = &[
     ("pages.json", || GLOBAL_SITE_DATA.pages_to_json()),
     ("tags.json", || GLOBAL_SITE_DATA.tags_to_json()),
+    ("sections.json", || GLOBAL_SITE_DATA.sections_to_json()),
+    ("authors.json", || GLOBAL_SITE_DATA.authors_to_json()),
+    ("git.json", || GLOBAL_SITE_DATA.git_to_json()),
+    ("current_taxonomy.json", || {
+        GLOBAL_SITE_DATA.current_taxonomy_to_json()
+    }),
 ];
 
 /// Check if a path refers to a virtual data file.
@@ -101,6 +106,8 @@ mod tests {
     fn test_is_virtual_data_path() {
         assert!(is_virtual_data_path(Path::new("/_data/pages.json")));
         assert!(is_virtual_data_path(Path::new("/_data/tags.json")));
+        assert!(is_virtual_data_path(Path::new("/_data/git.json")));
+        assert!(is_virtual_data_path(Path::new("/_data/sections.json")));
         assert!(is_virtual_data_path(Path::new("/project/_data/pages.json")));
         assert!(is_virtual_data_path(Path::new(
             "/some/path/_data/tags.json"
@@ -135,9 +142,13 @@ mod tests {
     #[test]
     fn test_virtual_data_paths() {
         let paths = virtual_data_paths();
-        assert_eq!(paths.len(), 2);
+        assert_eq!(paths.len(), 6);
         assert!(paths.contains(&PathBuf::from("/_data/pages.json")));
         assert!(paths.contains(&PathBuf::from("/_data/tags.json")));
+        assert!(paths.contains(&PathBuf::from("/_data/git.json")));
+        assert!(paths.contains(&PathBuf::from("/_data/sections.json")));
+        assert!(paths.contains(&PathBuf::from("/_data/current_taxonomy.json")));
+        assert!(paths.contains(&PathBuf::from("/_data/authors.json")));
     }
 
     #[test]
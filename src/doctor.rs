@@ -0,0 +1,29 @@
+//! `tola doctor`: environment diagnostics.
+//!
+//! Runs every check from [`SiteConfig::diagnose`] (external tools, configured
+//! paths, `[base].url`) and prints a checklist instead of failing on the
+//! first problem like a build does.
+
+use crate::config::SiteConfig;
+use anyhow::{Result, bail};
+use colored::Colorize;
+
+/// Print the environment checklist, failing if any check failed.
+pub fn run_doctor(config: &SiteConfig) -> Result<()> {
+    let checks = config.diagnose();
+    let mut failed = 0;
+    for (name, result) in &checks {
+        match result {
+            Ok(()) => println!("{} {name}", "✓".green()),
+            Err(e) => {
+                failed += 1;
+                println!("{} {name}: {e:#}", "✗".red());
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {} checks failed", checks.len());
+    }
+    Ok(())
+}
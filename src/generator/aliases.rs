@@ -0,0 +1,273 @@
+//! Redirect stubs for page aliases.
+//!
+//! A page's `aliases` metadata lists former URL paths. Each alias gets a small
+//! HTML page that redirects to the current URL:
+//!
+//! ```html
+//! <!DOCTYPE html>
+//! <html>
+//! <head>
+//!   <meta charset="utf-8">
+//!   <title>Redirecting…</title>
+//!   <link rel="canonical" href="https://example.com/posts/new/">
+//!   <meta http-equiv="refresh" content="0; url=https://example.com/posts/new/">
+//! </head>
+//! ...
+//! ```
+//!
+//! Aliases are relative to the site root (inside `path_prefix`). A path ending
+//! in `.html` (or `[build].page_extension`) is written as is; anything else
+//! becomes `<alias>/index.html`.
+
+use crate::{
+    compiler::meta::{PageMeta, Pages},
+    compiler::orphans::planned_outputs,
+    config::{BuildConfig, SiteConfig},
+    log,
+    utils::typst_element::html_escape,
+};
+use anyhow::{Context, Result, bail};
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Write a redirect stub for every alias of every page.
+///
+/// Fails if an alias escapes the output directory, would overwrite another
+/// output (a page, taxonomy page, copied file, ...) or another alias.
+pub fn build_aliases(config: &SiteConfig, pages: &Pages) -> Result<()> {
+    let planned = planned_outputs(config, pages);
+    let mut stubs: FxHashMap<PathBuf, &PageMeta> = FxHashMap::default();
+    let mut count = 0;
+    for (page, path) in alias_targets(config, pages)? {
+        if planned.contains(&path) {
+            bail!(
+                "alias of {} would overwrite {}",
+                page.paths.url_path,
+                path.display()
+            );
+        }
+        if let Some(other) = stubs.insert(path.clone(), page) {
+            bail!(
+                "aliases of {} and {} both write {}",
+                other.paths.url_path,
+                page.paths.url_path,
+                path.display()
+            );
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, redirect_html(&page.paths.full_url))
+            .with_context(|| format!("Failed to write alias {}", path.display()))?;
+        count += 1;
+    }
+
+    if count > 0 {
+        log!("aliases"; "wrote {count} redirects");
+    }
+    Ok(())
+}
+
+/// Output paths of all alias redirect stubs, for orphan detection.
+pub fn alias_outputs(config: &SiteConfig, pages: &Pages) -> Vec<PathBuf> {
+    alias_targets(config, pages)
+        .map(|targets| targets.into_iter().map(|(_, path)| path).collect())
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Pair each page alias with the output file it is written to.
+fn alias_targets<'a>(
+    config: &SiteConfig,
+    pages: &'a Pages,
+) -> Result<Vec<(&'a PageMeta, PathBuf)>> {
+    let output_dir = config.paths().output_dir();
+    let mut targets = Vec::new();
+    for page in pages.iter() {
+        let Some(content) = &page.content_meta else {
+            continue;
+        };
+        for alias in &content.aliases {
+            let rel = alias_rel_path(alias, &config.build).with_context(|| {
+                format!(
+                    "invalid alias {} of {}",
+                    alias.display(),
+                    page.paths.url_path
+                )
+            })?;
+            targets.push((page, output_dir.join(rel)));
+        }
+    }
+    Ok(targets)
+}
+
+/// Map an alias URL path to its stub file relative to the output dir.
+///
+/// `/old/post/` → `old/post/index.html`, `/old.html` → `old.html`.
+fn alias_rel_path(alias: &Path, build: &BuildConfig) -> Result<PathBuf> {
+    let mut rel = PathBuf::new();
+    for component in alias.components() {
+        match component {
+            Component::Normal(part) => rel.push(part),
+            Component::RootDir | Component::CurDir => {}
+            _ => bail!("alias must stay inside the site root"),
+        }
+    }
+    if rel.as_os_str().is_empty() {
+        bail!("alias must not be the site root");
+    }
+
+    if rel.extension().is_some_and(|ext| ext == "html") || build.is_page_file(&rel) {
+        Ok(rel)
+    } else {
+        Ok(rel.join(build.index_filename()))
+    }
+}
+
+/// Minimal HTML page redirecting to `url`.
+fn redirect_html(url: &str) -> String {
+    let url = html_escape(url);
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Redirecting…</title>\n\
+         <link rel=\"canonical\" href=\"{url}\">\n\
+         <meta name=\"robots\" content=\"noindex\">\n\
+         <meta http-equiv=\"refresh\" content=\"0; url={url}\">\n\
+         </head>\n\
+         <body><a href=\"{url}\">{url}</a></body>\n\
+         </html>\n"
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::meta::{ContentMeta, PagePaths};
+    use tempfile::TempDir;
+
+    fn make_page(output: &Path, aliases: &[&str]) -> PageMeta {
+        PageMeta {
+            paths: PagePaths {
+                source: PathBuf::from("content/posts/new.typ"),
+                html: output.join("posts/new/index.html"),
+                relative: "posts/new".to_string(),
+                url_path: "/posts/new/".to_string(),
+                full_url: "https://example.com/posts/new/".to_string(),
+            },
+            lastmod: None,
+            content_meta: Some(ContentMeta {
+                aliases: aliases.iter().map(PathBuf::from).collect(),
+                ..Default::default()
+            }),
+            compiled_html: None,
+        }
+    }
+
+    #[test]
+    fn test_alias_rel_path() {
+        let mut build = BuildConfig::default();
+        assert_eq!(
+            alias_rel_path(Path::new("/old/post/"), &build).unwrap(),
+            Path::new("old/post/index.html")
+        );
+        assert_eq!(
+            alias_rel_path(Path::new("old.html"), &build).unwrap(),
+            Path::new("old.html")
+        );
+        assert!(alias_rel_path(Path::new("/../escape/"), &build).is_err());
+        assert!(alias_rel_path(Path::new("/"), &build).is_err());
+
+        build.page_extension = "htm".into();
+        assert_eq!(
+            alias_rel_path(Path::new("/old/post/"), &build).unwrap(),
+            Path::new("old/post/index.htm")
+        );
+        assert_eq!(
+            alias_rel_path(Path::new("old.htm"), &build).unwrap(),
+            Path::new("old.htm")
+        );
+    }
+
+    #[test]
+    fn test_alias_writes_redirect_stub() {
+        let dir = TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().join("public");
+        config.set_root(dir.path());
+
+        let output = config.paths().output_dir();
+        let pages = Pages {
+            items: vec![make_page(&output, &["/2023/old-post/"])],
+        };
+        build_aliases(&config, &pages).unwrap();
+
+        let stub = fs::read_to_string(output.join("2023/old-post/index.html")).unwrap();
+        assert!(stub.contains(
+            r#"<meta http-equiv="refresh" content="0; url=https://example.com/posts/new/">"#
+        ));
+        assert!(stub.contains(r#"<link rel="canonical" href="https://example.com/posts/new/">"#));
+        assert_eq!(
+            alias_outputs(&config, &pages),
+            vec![output.join("2023/old-post/index.html")]
+        );
+    }
+
+    #[test]
+    fn test_alias_cannot_overwrite_page() {
+        let dir = TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().join("public");
+        config.set_root(dir.path());
+
+        let output = config.paths().output_dir();
+        let pages = Pages {
+            items: vec![make_page(&output, &["/posts/new/"])],
+        };
+        assert!(build_aliases(&config, &pages).is_err());
+    }
+
+    #[test]
+    fn test_alias_cannot_overwrite_other_outputs() {
+        let dir = TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().join("public");
+        config.set_root(dir.path());
+        let output = config.paths().output_dir();
+        let build = |config: &SiteConfig, aliases: &[&[&str]]| {
+            let pages = Pages {
+                items: aliases.iter().map(|a| make_page(&output, a)).collect(),
+            };
+            build_aliases(config, &pages).map_err(|e| e.to_string())
+        };
+
+        assert!(build(&config, &[&["/go/"]]).is_ok());
+        // A `[build.copy]` file
+        config.build.copy = vec![crate::config::CopyEntry {
+            from: dir.path().join("go.html"),
+            to: "go/index.html".into(),
+        }];
+        let err = build(&config, &[&["/go/"]]).unwrap_err();
+        assert!(err.contains("would overwrite"), "{err}");
+
+        // Two aliases of different pages, or repeated on one page
+        let err = build(&config, &[&["/old/"], &["/old/"]]).unwrap_err();
+        assert!(err.contains("both write"), "{err}");
+        let err = build(&config, &[&["/dup/", "/dup/"]]).unwrap_err();
+        assert!(err.contains("both write"), "{err}");
+    }
+}
@@ -0,0 +1,170 @@
+//! Deployable archive of the built site.
+//!
+//! With `[build].archive` (or `tola build --archive`), the output directory is
+//! packaged after the build as a `.zip` or `.tar.gz`, chosen by extension.
+//! Entries keep their paths relative to the output directory and their file
+//! modes; the deploy `.git` directory is left out.
+
+use crate::{
+    config::{ArchiveFormat, SiteConfig},
+    log,
+    report::output_files,
+};
+use anyhow::{Context, Result, anyhow};
+use flate2::{Compression, write::GzEncoder};
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Write `[build].archive`, if configured, from the output directory.
+pub fn build_archive(config: &SiteConfig) -> Result<()> {
+    let Some(archive) = &config.build.archive else {
+        return Ok(());
+    };
+    let format = ArchiveFormat::from_path(archive)
+        .ok_or_else(|| anyhow!("unsupported archive type: {}", archive.display()))?;
+
+    let output = &config.build.output;
+    let mut files: Vec<PathBuf> = output_files(output).map(|(path, _)| path).collect();
+    files.sort();
+
+    if let Some(parent) = archive.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = BufWriter::new(
+        File::create(archive)
+            .with_context(|| format!("Failed to create archive {}", archive.display()))?,
+    );
+    match format {
+        ArchiveFormat::Zip => write_zip(file, output, &files)?,
+        ArchiveFormat::TarGz => write_tar_gz(file, output, &files)?,
+    }
+
+    log!("archive"; "{} ({} files)", archive.display(), files.len());
+    Ok(())
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Archive entry name of `path`: relative to `output`, `/`-separated.
+fn entry_name(path: &Path, output: &Path) -> String {
+    let rel = path.strip_prefix(output).unwrap_or(path);
+    rel.to_string_lossy().replace('\\', "/")
+}
+
+fn write_zip(file: BufWriter<File>, output: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut zip = ZipWriter::new(file);
+    for path in files {
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(file_mode(&fs::metadata(path)?));
+        zip.start_file(entry_name(path, output), options)?;
+        io::copy(&mut File::open(path)?, &mut zip)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+fn write_tar_gz(file: BufWriter<File>, output: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    for path in files {
+        // Headers are filled from the file's metadata, mode included
+        tar.append_path_with_name(path, entry_name(path, output))?;
+    }
+    tar.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Permission bits stored for an archived file.
+#[cfg(unix)]
+fn file_mode(meta: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o777
+}
+
+/// Permission bits stored for an archived file.
+#[cfg(not(unix))]
+fn file_mode(meta: &fs::Metadata) -> u32 {
+    if meta.permissions().readonly() {
+        0o444
+    } else {
+        0o644
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn make_site(dir: &Path, archive: &str) -> SiteConfig {
+        let mut config = SiteConfig::default();
+        config.build.output = dir.join("public");
+        config.build.archive = Some(dir.join(archive));
+        let output = &config.build.output;
+        fs::create_dir_all(output.join("posts/hello")).unwrap();
+        fs::create_dir_all(output.join(".git")).unwrap();
+        fs::write(output.join("index.html"), "<p>home</p>").unwrap();
+        fs::write(output.join("posts/hello/index.html"), "<p>hello</p>").unwrap();
+        fs::write(output.join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let perms = fs::Permissions::from_mode(0o640);
+            fs::set_permissions(output.join("posts/hello/index.html"), perms).unwrap();
+        }
+        config
+    }
+
+    const EXPECTED: [&str; 2] = ["index.html", "posts/hello/index.html"];
+
+    #[test]
+    fn test_zip_archive_contents() {
+        let dir = TempDir::new().unwrap();
+        let config = make_site(dir.path(), "site.zip");
+        build_archive(&config).unwrap();
+
+        let file = File::open(dir.path().join("site.zip")).unwrap();
+        let mut zip = zip::ZipArchive::new(file).unwrap();
+        let mut names: Vec<String> = zip.file_names().map(|n| n.unwrap().into_owned()).collect();
+        names.sort();
+        assert_eq!(names, EXPECTED);
+
+        let mut entry = zip.by_name("posts/hello/index.html").unwrap();
+        let mut body = String::new();
+        entry.read_to_string(&mut body).unwrap();
+        assert_eq!(body, "<p>hello</p>");
+        #[cfg(unix)]
+        assert_eq!(entry.unix_mode().map(|m| m & 0o777), Some(0o640));
+    }
+
+    #[test]
+    fn test_tar_gz_archive_contents() {
+        let dir = TempDir::new().unwrap();
+        let config = make_site(dir.path(), "site.tar.gz");
+        build_archive(&config).unwrap();
+
+        let file = File::open(dir.path().join("site.tar.gz")).unwrap();
+        let mut tar = tar::Archive::new(GzDecoder::new(file));
+        let mut names: Vec<String> = tar
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, EXPECTED);
+    }
+}
@@ -4,9 +4,13 @@
 //!
 //! - **RSS**: Feed for blog readers (`rss.xml`)
 //! - **Sitemap**: Search engine indexing (`sitemap.xml`)
+//! - **Aliases**: Redirect stubs at former page URLs
+//! - **Archive**: `.zip`/`.tar.gz` of the finished output (`[build].archive`)
 //!
-//! Both generators use pre-collected `PageMeta` from the build pipeline,
-//! avoiding redundant filesystem scans or re-compilation.
+//! All page-based generators use pre-collected `PageMeta` from the build
+//! pipeline, avoiding redundant filesystem scans or re-compilation.
 
+pub mod aliases;
+pub mod archive;
 pub mod rss;
 pub mod sitemap;
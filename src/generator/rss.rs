@@ -3,18 +3,35 @@
 //! Parses post metadata and generates rss/atom feeds.
 
 use crate::{
-    compiler::meta::{PageMeta, Pages},
-    config::SiteConfig,
+    compiler::meta::{ContentMeta, PageMeta, Pages},
+    config::{RssAuthorMode, SiteConfig, defaults},
     log,
     utils::{
         date::DateTimeUtc,
+        emoji::replace_shortcodes,
         minify::{MinifyType, minify},
+        slug::slugify_fragment,
+        typst_element::absolutize_links,
+        xml::{
+            assets::{compute_asset_href, is_remote_url},
+            summary::truncate_html,
+        },
     },
 };
 use anyhow::{Ok, Result, anyhow};
 use regex::Regex;
-use rss::{ChannelBuilder, GuidBuilder, ItemBuilder, validation::Validate};
-use std::{fs, sync::LazyLock};
+use rss::{
+    CategoryBuilder, ChannelBuilder, GuidBuilder, ImageBuilder, ItemBuilder,
+    extension::{ExtensionBuilder, ExtensionMap, dublincore::DublinCoreExtensionBuilder},
+    validation::Validate,
+};
+use std::{borrow::Cow, collections::BTreeMap, fs, sync::LazyLock};
+
+/// Namespace of item `<atom:updated>` elements.
+const ATOM_NS: &str = "http://www.w3.org/2005/Atom";
+
+/// Channel `<docs>`: the format specification the feed follows.
+const RSS_DOCS: &str = "https://www.rssboard.org/rss-specification";
 
 // ============================================================================
 // Public API
@@ -36,16 +53,25 @@ pub fn build_rss(config: &SiteConfig, pages: &Pages) -> Result<()> {
 struct RssFeed<'a> {
     config: &'a SiteConfig,
     pages: Vec<&'a PageMeta>,
+    /// `<lastBuildDate>` unless the feed is deterministic
+    build_time: DateTimeUtc,
 }
 
 impl<'a> RssFeed<'a> {
     /// Build rss feed using pre-collected page metadata.
     ///
-    /// Pages without content metadata are silently skipped.
+    /// Pages without content metadata, or with `rss = false`, are silently skipped.
     fn build(config: &'a SiteConfig, pages: &'a Pages) -> Result<Self> {
-        let pages: Vec<_> = pages.iter().filter(|p| p.content_meta.is_some()).collect();
+        let pages: Vec<_> = pages
+            .iter()
+            .filter(|p| p.content_meta.as_ref().is_some_and(ContentMeta::in_feed))
+            .collect();
 
-        Ok(Self { config, pages })
+        Ok(Self {
+            config,
+            pages,
+            build_time: DateTimeUtc::build_time(),
+        })
     }
 
     /// Generate rss xml string
@@ -55,13 +81,35 @@ impl<'a> RssFeed<'a> {
             .iter()
             .filter_map(|page| page_to_rss_item(page, self.config))
             .collect();
+        let mut namespaces = BTreeMap::new();
+        if items
+            .iter()
+            .any(|item| item.extensions().contains_key("atom"))
+        {
+            namespaces.insert("atom".to_string(), ATOM_NS.to_string());
+        }
 
+        let rss_config = &self.config.build.rss;
+        let latest_post = latest_post_date(&self.pages);
+        let last_build = if rss_config.deterministic {
+            latest_post
+        } else {
+            Some(self.build_time)
+        };
         let channel = ChannelBuilder::default()
+            .namespaces(namespaces)
             .title(&self.config.base.title)
-            .link(self.config.base.url.as_deref().unwrap_or_default())
+            .link(self.config.site_url())
             .description(&self.config.base.description)
             .language(self.config.base.language.clone())
-            .generator("tola-ssg".to_string())
+            .generator(Some(rss_config.generator.clone()).filter(|g| !g.is_empty()))
+            .docs(RSS_DOCS.to_string())
+            .managing_editor(rss_config.managing_editor.clone())
+            .webmaster(rss_config.webmaster.clone())
+            .image(channel_image(self.config)?)
+            .ttl(rss_config.ttl.map(|ttl| ttl.to_string()))
+            .pub_date(latest_post.map(DateTimeUtc::to_rfc2822))
+            .last_build_date(last_build.map(DateTimeUtc::to_rfc2822))
             .items(items)
             .build();
 
@@ -92,24 +140,97 @@ impl<'a> RssFeed<'a> {
 // Helper Functions
 // ============================================================================
 
+/// Most recent `date` among the feed's pages, for the channel `<pubDate>`.
+fn latest_post_date(pages: &[&PageMeta]) -> Option<DateTimeUtc> {
+    pages
+        .iter()
+        .filter_map(|page| page.content_meta.as_ref()?.date.as_deref())
+        .filter_map(DateTimeUtc::parse)
+        .max()
+}
+
+/// Build the channel `<image>` from `[build.rss].image`, with an absolute url.
+fn channel_image(config: &SiteConfig) -> Result<Option<rss::Image>> {
+    let Some(image) = &config.build.rss.image else {
+        return Ok(None);
+    };
+
+    let url = if is_remote_url(image) {
+        let url = image.to_string_lossy();
+        // Protocol-relative URLs are not valid in feeds
+        url.strip_prefix("//")
+            .map_or_else(|| url.to_string(), |rest| format!("https://{rest}"))
+    } else {
+        format!(
+            "{}{}",
+            config.base_origin(),
+            compute_asset_href(image, config)?
+        )
+    };
+
+    Ok(Some(
+        ImageBuilder::default()
+            .url(url)
+            .title(&config.base.title)
+            .link(config.site_url())
+            .build(),
+    ))
+}
+
 /// Convert `PageMeta` to rss item.
 /// Returns None if required fields (title, date) are missing.
 fn page_to_rss_item(page: &PageMeta, config: &SiteConfig) -> Option<rss::Item> {
     let content = page.content_meta.as_ref()?;
-    let title = content.title.clone()?;
+    let emoji = |text: &str| {
+        if config.build.emoji_shortcodes {
+            replace_shortcodes(text).into_owned()
+        } else {
+            text.to_string()
+        }
+    };
+    let title = emoji(content.title.as_deref()?);
     let date = content.date.as_deref()?;
     let pub_date = DateTimeUtc::parse(date).map(DateTimeUtc::to_rfc2822)?;
+    let updated = updated_extension(content.update.as_deref());
     let link = page.paths.full_url.clone();
-    let author = normalize_rss_author(content.author.as_ref(), config);
+    let (author, creator) = match normalize_rss_author(content.authors.first(), config) {
+        Some(RssAuthor::Email(author)) => (Some(author), None),
+        Some(RssAuthor::Name(name)) => (
+            None,
+            Some(DublinCoreExtensionBuilder::default().creator(name).build()),
+        ),
+        None => (None, None),
+    };
+    let categories = content
+        .tags
+        .iter()
+        .map(|tag| {
+            let name = if config.build.rss.category_slug {
+                slugify_fragment(tag, config)
+            } else {
+                tag.clone()
+            };
+            CategoryBuilder::default().name(name).build()
+        })
+        .collect::<Vec<_>>();
 
     Some(
         ItemBuilder::default()
             .title(title)
             .link(Some(link.clone()))
             .guid(GuidBuilder::default().permalink(true).value(link).build())
-            .description(content.summary.clone())
+            .description(content.summary.as_deref().map(|summary| {
+                let summary = match config.build.rss.summary_max_chars {
+                    Some(max_chars) => Cow::Owned(truncate_html(summary, max_chars)),
+                    None => Cow::Borrowed(summary),
+                };
+                absolutize_links(&emoji(&summary), config.base_origin()).into_owned()
+            }))
             .pub_date(pub_date)
             .author(author)
+            .dublin_core_ext(creator)
+            .extensions(updated)
+            .categories(categories)
             .build(),
     )
 }
@@ -118,32 +239,78 @@ fn page_to_rss_item(page: &PageMeta, config: &SiteConfig) -> Option<rss::Item> {
 // Helper Functions
 // ============================================================================
 
+/// `<atom:updated>` for the metadata `update` date, if it parses.
+fn updated_extension(update: Option<&str>) -> ExtensionMap {
+    let Some(update) = update.and_then(DateTimeUtc::parse) else {
+        return ExtensionMap::new();
+    };
+    let updated = ExtensionBuilder::default()
+        .name("atom:updated")
+        .value(Some(update.to_rfc3339()))
+        .build();
+    BTreeMap::from([(
+        "atom".to_string(),
+        BTreeMap::from([("updated".to_string(), vec![updated])]),
+    )])
+}
+
+/// Item author: `<author>` or a name-only `<dc:creator>`.
+#[derive(Debug, PartialEq, Eq)]
+enum RssAuthor {
+    /// "email@example.com (Name)"
+    Email(String),
+    /// "Name"
+    Name(String),
+}
+
 /// Normalize author field to rss format: "email@example.com (Name)"
 ///
 /// Priority:
 /// 1. Post meta author if already in valid format
 /// 2. Site config author if in valid format
 /// 3. Combine site config email and author
-fn normalize_rss_author(author: Option<&String>, config: &SiteConfig) -> Option<String> {
+///
+/// In `name` mode, or `auto` mode when no real email is known (`base.email`
+/// unset or the `user@noreply.tola` placeholder), only the name is kept.
+fn normalize_rss_author(author: Option<&String>, config: &SiteConfig) -> Option<RssAuthor> {
     static RE_VALID_AUTHOR: LazyLock<Regex> = LazyLock::new(|| {
-        Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}[ \t]*\([^)]+\)$").unwrap()
+        Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}[ \t]*\(([^)]+)\)$").unwrap()
     });
+    static RE_VALID_EMAIL: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$").unwrap());
 
     let author = author?;
+    let mode = config.build.rss.author_mode;
+
+    if mode == RssAuthorMode::Name {
+        // Keep the name of "email (Name)", or the whole value
+        let name = RE_VALID_AUTHOR
+            .captures(author)
+            .map_or(author.as_str(), |caps| {
+                caps.get(1).map_or("", |m| m.as_str())
+            });
+        return Some(RssAuthor::Name(name.trim().to_string()));
+    }
 
     // Check if post author is already valid
     if RE_VALID_AUTHOR.is_match(author) {
-        return Some(author.clone());
+        return Some(RssAuthor::Email(author.clone()));
     }
 
     // Try site config author
     let site_author = &config.base.author;
     if RE_VALID_AUTHOR.is_match(site_author) {
-        return Some(site_author.clone());
+        return Some(RssAuthor::Email(site_author.clone()));
+    }
+
+    let email = &config.base.email;
+    let real_email = RE_VALID_EMAIL.is_match(email) && *email != defaults::base::email();
+    if mode == RssAuthorMode::Auto && !real_email {
+        return Some(RssAuthor::Name(author.trim().to_string()));
     }
 
     // Combine email and author name
-    Some(format!("{} ({})", config.base.email, site_author))
+    Some(RssAuthor::Email(format!("{email} ({site_author})")))
 }
 
 #[cfg(test)]
@@ -176,9 +343,18 @@ mod tests {
                 summary: summary.map(String::from),
                 date: Some(date.to_string()),
                 update: None,
-                author: author.map(String::from),
+                authors: author.map(String::from).into_iter().collect(),
                 draft: false,
+                expiry_date: None,
                 tags: vec![],
+                section: None,
+                aliases: vec![],
+                direction: None,
+                raw: false,
+                noindex: false,
+                rss: None,
+                sitemap: None,
+                summary_html: None,
             }),
             compiled_html: None,
         }
@@ -192,14 +368,16 @@ mod tests {
         let post_author = "post@example.com (Post Author)".to_string();
         assert_eq!(
             normalize_rss_author(Some(&post_author), &config),
-            Some(post_author)
+            Some(RssAuthor::Email(post_author))
         );
 
         // Case 2: Post author is invalid (just name), fallback to site config (combined)
         let post_author_invalid = "Post Author".to_string();
         assert_eq!(
             normalize_rss_author(Some(&post_author_invalid), &config),
-            Some("site@example.com (Site Author)".to_string())
+            Some(RssAuthor::Email(
+                "site@example.com (Site Author)".to_string()
+            ))
         );
 
         // Case 3: Post author None, returns None (current behavior)
@@ -209,8 +387,127 @@ mod tests {
         let config_valid = make_config("site@example.com (Site Author)", "");
         assert_eq!(
             normalize_rss_author(Some(&post_author_invalid), &config_valid),
-            Some("site@example.com (Site Author)".to_string())
+            Some(RssAuthor::Email(
+                "site@example.com (Site Author)".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rss_author_modes() {
+        let name_only = "Post Author".to_string();
+        let with_email = "post@example.com (Post Author)".to_string();
+        let name = Some(RssAuthor::Name("Post Author".to_string()));
+
+        // Auto: placeholder or missing email omits it
+        for email in ["user@noreply.tola", "", "not-an-email"] {
+            let config = make_config("Site Author", email);
+            assert_eq!(normalize_rss_author(Some(&name_only), &config), name);
+        }
+        let config = make_config("Site Author", "user@noreply.tola");
+        assert_eq!(
+            normalize_rss_author(Some(&with_email), &config),
+            Some(RssAuthor::Email(with_email.clone()))
+        );
+
+        // Name: never an email
+        let mut config = make_config("Site Author", "site@example.com");
+        config.build.rss.author_mode = RssAuthorMode::Name;
+        assert_eq!(normalize_rss_author(Some(&name_only), &config), name);
+        assert_eq!(normalize_rss_author(Some(&with_email), &config), name);
+
+        // Email: always combined, even with the placeholder
+        let mut config = make_config("Site Author", "user@noreply.tola");
+        config.build.rss.author_mode = RssAuthorMode::Email;
+        assert_eq!(
+            normalize_rss_author(Some(&name_only), &config),
+            Some(RssAuthor::Email(
+                "user@noreply.tola (Site Author)".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_update_in_feed() {
+        let mut config = make_config("Site Author", "site@example.com");
+        config.base.title = "Site".into();
+        config.base.description = "Desc".into();
+        let mut page = make_page("Post", "2024-01-01", None, None);
+        let xml = |pages: &[PageMeta]| {
+            RssFeed {
+                config: &config,
+                pages: pages.iter().collect(),
+                build_time: DateTimeUtc::now(),
+            }
+            .into_xml()
+            .unwrap()
+        };
+
+        let plain = xml(std::slice::from_ref(&page));
+        assert!(!plain.contains("atom"), "{plain}");
+
+        page.content_meta.as_mut().unwrap().update = Some("2024-03-02".into());
+        let updated = xml(&[page]);
+        assert!(
+            updated.contains(&format!("xmlns:atom=\"{ATOM_NS}\"")),
+            "{updated}"
+        );
+        assert!(
+            updated.contains("<atom:updated>2024-03-02T00:00:00Z</atom:updated>"),
+            "{updated}"
+        );
+    }
+
+    #[test]
+    fn test_rss_false_excluded_from_feed() {
+        let mut config = make_config("Site Author", "site@example.com");
+        config.base.title = "Site".into();
+        config.base.description = "Desc".into();
+        let post = make_page("Post", "2024-01-01", None, None);
+        let mut about = make_page("About", "2024-01-02", None, None);
+        about.content_meta.as_mut().unwrap().rss = Some(false);
+        let pages = Pages {
+            items: vec![post, about],
+        };
+
+        let xml = RssFeed::build(&config, &pages).unwrap().into_xml().unwrap();
+        assert!(xml.contains("<title>Post</title>"), "{xml}");
+        assert!(!xml.contains("About"), "{xml}");
+
+        // `in_feed` is an alias
+        let meta: ContentMeta = serde_json::from_str(r#"{"in_feed": false}"#).unwrap();
+        assert!(!meta.in_feed());
+    }
+
+    #[test]
+    fn test_name_only_author_in_feed() {
+        let mut config = make_config("Site Author", "user@noreply.tola");
+        config.base.title = "Site".into();
+        config.base.description = "Desc".into();
+        let page = make_page("Post", "2024-01-01", None, Some("Post Author"));
+
+        let item = page_to_rss_item(&page, &config).unwrap();
+        assert_eq!(item.author(), None);
+        let dc = item.dublin_core_ext().unwrap();
+        assert_eq!(dc.creators(), ["Post Author"]);
+
+        let pages = [page];
+        let xml = RssFeed {
+            config: &config,
+            pages: pages.iter().collect(),
+            build_time: DateTimeUtc::now(),
+        }
+        .into_xml()
+        .unwrap();
+        assert!(
+            xml.contains("xmlns:dc=\"http://purl.org/dc/elements/1.1/\""),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<dc:creator>Post Author</dc:creator>"),
+            "{xml}"
         );
+        assert!(!xml.contains("<author>"), "{xml}");
     }
 
     #[test]
@@ -249,4 +546,204 @@ mod tests {
 
         assert!(page_to_rss_item(&page, &config).is_none());
     }
+
+    #[test]
+    fn test_channel_image_and_ttl() {
+        let mut config = make_config("Site Author", "site@example.com");
+        config.build.rss.image = Some(PathBuf::from("images/logo.png"));
+        config.build.rss.ttl = Some(60);
+
+        let feed = RssFeed {
+            config: &config,
+            pages: vec![],
+            build_time: DateTimeUtc::now(),
+        };
+        let xml = feed.into_xml().unwrap();
+        assert!(
+            xml.contains("<url>https://example.com/images/logo.png</url>"),
+            "{xml}"
+        );
+        assert!(xml.contains("<ttl>60</ttl>"), "{xml}");
+
+        config.build.rss.image = Some(PathBuf::from("//cdn.example.com/logo.png"));
+        let image = channel_image(&config).unwrap().unwrap();
+        assert_eq!(image.url(), "https://cdn.example.com/logo.png");
+    }
+
+    #[test]
+    fn test_channel_generator_and_docs() {
+        let mut config = make_config("Site Author", "site@example.com");
+        let feed = RssFeed {
+            config: &config,
+            pages: vec![],
+            build_time: DateTimeUtc::now(),
+        };
+        let xml = feed.into_xml().unwrap();
+        let generator = format!(
+            "<generator>tola-ssg {}</generator>",
+            env!("CARGO_PKG_VERSION")
+        );
+        assert!(xml.contains(&generator), "{xml}");
+        assert!(
+            xml.contains("<docs>https://www.rssboard.org/rss-specification</docs>"),
+            "{xml}"
+        );
+        assert!(!xml.contains("<managingEditor>"), "{xml}");
+
+        config.build.rss.generator = "My Site Engine".to_string();
+        config.build.rss.managing_editor = Some("editor@example.com (Ed)".to_string());
+        config.build.rss.webmaster = Some("ops@example.com".to_string());
+        let feed = RssFeed {
+            config: &config,
+            pages: vec![],
+            build_time: DateTimeUtc::now(),
+        };
+        let xml = feed.into_xml().unwrap();
+        assert!(
+            xml.contains("<generator>My Site Engine</generator>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<managingEditor>editor@example.com (Ed)</managingEditor>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<webMaster>ops@example.com</webMaster>"),
+            "{xml}"
+        );
+    }
+
+    #[test]
+    fn test_channel_build_and_pub_dates() {
+        let mut config = make_config("Site Author", "site@example.com");
+        let pages = [
+            make_page("Old", "2024-01-01", None, None),
+            make_page("New", "2024-03-15", None, None),
+        ];
+        let channel_date = |xml: &str, tag: &str| {
+            let start = xml.find(&format!("<{tag}>")).unwrap() + tag.len() + 2;
+            let end = xml[start..].find('<').unwrap() + start;
+            xml[start..end].to_string()
+        };
+
+        let feed = RssFeed {
+            config: &config,
+            pages: pages.iter().collect(),
+            build_time: DateTimeUtc::now(),
+        };
+        let xml = feed.into_xml().unwrap();
+        let last_build = channel_date(&xml, "lastBuildDate");
+        assert!(
+            chrono::DateTime::parse_from_rfc2822(&last_build).is_ok(),
+            "{last_build}"
+        );
+        let pub_date = DateTimeUtc::parse("2024-03-15").unwrap().to_rfc2822();
+        // Channel elements precede the items
+        assert_eq!(channel_date(&xml, "pubDate"), pub_date);
+
+        config.build.rss.deterministic = true;
+        let feed = RssFeed {
+            config: &config,
+            pages: pages.iter().collect(),
+            build_time: DateTimeUtc::now(),
+        };
+        let xml = feed.into_xml().unwrap();
+        assert_eq!(channel_date(&xml, "lastBuildDate"), pub_date);
+    }
+
+    #[test]
+    fn test_last_build_date_from_source_date_epoch() {
+        let config = make_config("Site Author", "site@example.com");
+        let pages = [make_page("Post", "2024-01-01", None, None)];
+        let feed = RssFeed {
+            config: &config,
+            pages: pages.iter().collect(),
+            build_time: DateTimeUtc::from_epoch("1700000000").unwrap(),
+        };
+
+        let xml = feed.into_xml().unwrap();
+        assert!(
+            xml.contains("<lastBuildDate>Tue, 14 Nov 2023 22:13:20 GMT</lastBuildDate>"),
+            "{xml}"
+        );
+    }
+
+    #[test]
+    fn test_rss_item_categories_from_tags() {
+        let mut config = make_config("Site Author", "site@example.com");
+        let mut page = make_page("Title", "2024-01-01", None, None);
+        page.content_meta.as_mut().unwrap().tags = vec!["Rust Lang".into(), "web".into()];
+
+        let feed = RssFeed {
+            config: &config,
+            pages: vec![&page],
+            build_time: DateTimeUtc::now(),
+        };
+        let xml = feed.into_xml().unwrap();
+        assert_eq!(xml.matches("<category>").count(), 2, "{xml}");
+        assert!(xml.contains("<category>Rust Lang</category>"), "{xml}");
+        assert!(xml.contains("<category>web</category>"), "{xml}");
+
+        config.build.rss.category_slug = true;
+        let item = page_to_rss_item(&page, &config).unwrap();
+        let names: Vec<_> = item.categories().iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["rust-lang", "web"]);
+    }
+
+    #[test]
+    fn test_emoji_shortcodes_in_feed_title() {
+        let mut config = make_config("Site Author", "site@example.com");
+        let page = make_page(":tada: Launch", "2024-01-01", Some("Out :rocket:"), None);
+
+        let item = page_to_rss_item(&page, &config).unwrap();
+        assert_eq!(item.title(), Some(":tada: Launch"));
+
+        config.build.emoji_shortcodes = true;
+        let item = page_to_rss_item(&page, &config).unwrap();
+        assert_eq!(item.title(), Some("🎉 Launch"));
+        assert_eq!(item.description(), Some("Out 🚀"));
+    }
+
+    #[test]
+    fn test_styled_summary_in_rss_description() {
+        // Same rendering as `pages.json`, with site-root links made absolute
+        let json = r#"{"summary": {"func": "sequence", "children": [
+            {"func": "emph", "body": {"func": "text", "text": "New"}},
+            {"func": "space"},
+            {"func": "link", "dest": "/posts/a/", "body":
+                {"func": "strong", "body": {"func": "text", "text": "post"}}},
+            {"func": "space"},
+            {"block": false, "func": "raw", "text": "x<y"}
+        ]}}"#;
+        let meta: ContentMeta = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            meta.summary.as_deref(),
+            Some(
+                r#"<em>New</em> <a href="/posts/a/"><strong>post</strong></a> <code>x&lt;y</code>"#
+            )
+        );
+
+        let config = make_config("Site Author", "site@example.com");
+        let page = make_page("Title", "2024-01-01", meta.summary.as_deref(), None);
+        let item = page_to_rss_item(&page, &config).unwrap();
+        assert_eq!(
+            item.description(),
+            Some(
+                r#"<em>New</em> <a href="https://example.com/posts/a/"><strong>post</strong></a> <code>x&lt;y</code>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_summary_max_chars_truncates_description() {
+        let summary = r#"A <a href="/posts/long/">very long summary</a> that keeps going."#;
+        let mut config = make_config("Site Author", "site@example.com");
+        config.build.rss.summary_max_chars = Some(11);
+        let page = make_page("Title", "2024-01-01", Some(summary), None);
+        let item = page_to_rss_item(&page, &config).unwrap();
+        assert_eq!(
+            item.description(),
+            Some(r#"A <a href="https://example.com/posts/long/">very long…</a>"#)
+        );
+    }
 }
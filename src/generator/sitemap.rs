@@ -13,9 +13,13 @@
 //!   </url>
 //! </urlset>
 //! ```
+//!
+//! `<lastmod>` is the page's `update` date from its metadata if set,
+//! otherwise the source file's mtime, or its last commit date with
+//! `[build.sitemap] lastmod = "git"`.
 
 use crate::{
-    compiler::meta::Pages,
+    compiler::meta::{ContentMeta, Pages},
     config::SiteConfig,
     log,
     utils::minify::{MinifyType, minify},
@@ -69,8 +73,14 @@ impl Sitemap {
     fn from_pages(pages: &Pages) -> Self {
         // log!("sitemap"; "generating from {} pages", pages.len());
 
+        // `noindex`/`sitemap = false` pages are built but not advertised
         let urls: Vec<UrlEntry> = pages
             .iter()
+            .filter(|page| {
+                page.content_meta
+                    .as_ref()
+                    .is_none_or(ContentMeta::in_sitemap)
+            })
             .map(|page| UrlEntry {
                 loc: page.paths.full_url.clone(),
                 lastmod: page.lastmod_ymd(),
@@ -149,7 +159,7 @@ fn escape_xml(s: &str) -> Cow<'_, str> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::compiler::meta::{PageMeta, PagePaths};
+    use crate::compiler::meta::{ContentMeta, PageMeta, PagePaths};
     use std::path::PathBuf;
     use std::time::{Duration, UNIX_EPOCH};
 
@@ -230,6 +240,57 @@ mod tests {
         assert_eq!(xml.matches("</url>").count(), 3);
     }
 
+    #[test]
+    fn test_sitemap_prefers_update_date() {
+        let mut page = make_page("https://example.com/", Some(20089)); // 2025-01-01
+        page.content_meta = Some(ContentMeta {
+            date: Some("2024-06-01".to_string()),
+            update: Some("2025-03-15T10:00:00Z".to_string()),
+            ..Default::default()
+        });
+        let pages = Pages { items: vec![page] };
+        let xml = Sitemap::from_pages(&pages).into_xml();
+
+        assert!(xml.contains("<lastmod>2025-03-15</lastmod>"), "{xml}");
+        assert!(!xml.contains("2025-01-01"));
+    }
+
+    #[test]
+    fn test_sitemap_skips_noindex_pages() {
+        let mut hidden = make_page("https://example.com/thanks/", Some(20089));
+        hidden.content_meta = Some(ContentMeta {
+            noindex: true,
+            ..Default::default()
+        });
+        let pages = Pages {
+            items: vec![make_page("https://example.com/", Some(20089)), hidden],
+        };
+        let xml = Sitemap::from_pages(&pages).into_xml();
+
+        assert!(xml.contains("<loc>https://example.com/</loc>"));
+        assert!(!xml.contains("thanks"));
+        assert_eq!(xml.matches("<url>").count(), 1);
+    }
+
+    #[test]
+    fn test_sitemap_toggle_independent_of_rss() {
+        let mut about = make_page("https://example.com/about/", None);
+        about.content_meta = Some(ContentMeta {
+            rss: Some(false),
+            ..Default::default()
+        });
+        let pages = Pages {
+            items: vec![about.clone()],
+        };
+        let xml = Sitemap::from_pages(&pages).into_xml();
+        assert!(xml.contains("<loc>https://example.com/about/</loc>"));
+
+        about.content_meta.as_mut().unwrap().sitemap = Some(false);
+        let pages = Pages { items: vec![about] };
+        let xml = Sitemap::from_pages(&pages).into_xml();
+        assert!(!xml.contains("<url>"));
+    }
+
     #[test]
     fn test_sitemap_without_lastmod() {
         let pages = Pages {
@@ -2,7 +2,7 @@
 //!
 //! Creates new site structure with default configuration.
 
-use crate::{config::SiteConfig, utils::git};
+use crate::{compiler::manifest::CACHE_DIR, config::SiteConfig, utils::git};
 use anyhow::{Context, Result, bail};
 use std::{fs, path::Path};
 
@@ -41,7 +41,11 @@ pub fn new_site(config: &SiteConfig, has_name: bool) -> Result<()> {
     init_default_config(root)?;
     init_ignored_files(
         root,
-        &[config.build.output.as_path(), Path::new("/assets/images/")],
+        &[
+            config.build.output.as_path(),
+            Path::new("/assets/images/"),
+            Path::new(CACHE_DIR),
+        ],
     )?;
     git::commit_all(&repo, "initial commit")?;
 
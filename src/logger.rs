@@ -224,11 +224,7 @@ impl ProgressBars {
         let bar_width = available.clamp(MIN_BAR_WIDTH, MAX_BAR_WIDTH);
 
         // Calculate filled/empty portions
-        let filled = if bar.total > 0 {
-            (current * bar_width) / bar.total
-        } else {
-            0
-        };
+        let filled = (current * bar_width).checked_div(bar.total).unwrap_or(0);
         let empty = bar_width.saturating_sub(filled);
 
         let progress_bar: String = "█".repeat(filled) + &"░".repeat(empty);
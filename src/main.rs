@@ -6,41 +6,99 @@ mod compiler;
 mod config;
 mod data;
 mod deploy;
+mod doctor;
 mod generator;
 mod init;
 mod logger;
+mod report;
 mod serve;
 mod typst_lib;
 mod utils;
+mod version;
 mod watch;
 
 use anyhow::Result;
-use build::build_site;
+use build::{build_atomic, build_file, build_site, collect_site_data};
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, ConfigCommand};
+use compiler::{check_links, reconcile_output};
 use config::{SiteConfig, cfg, init_config};
 use deploy::deploy_site;
-use generator::{rss::build_rss, sitemap::build_sitemap};
+use doctor::run_doctor;
+use generator::{archive::build_archive, rss::build_rss, sitemap::build_sitemap};
 use gix::ThreadSafeRepository;
 use init::new_site;
+use report::{BUILD_STATS, BuildReport, HtmlReport, Stage};
 use serve::serve_site;
+use std::path::Path;
+use std::time::Instant;
+use utils::git;
+use version::version_info;
 
 fn main() -> Result<()> {
     let cli: &'static Cli = Box::leak(Box::new(Cli::parse()));
+    // Works outside a site, without `tola.toml`
+    if let Commands::Version { verbose } = cli.command {
+        println!("{}", version_info(verbose));
+        return Ok(());
+    }
     init_config(SiteConfig::load(cli)?);
 
     match &cli.command {
         Commands::Init { name } => new_site(&cfg(), name.is_some()),
-        Commands::Build { .. } => build_all().map(|_| ()),
+        Commands::Build {
+            list: true, json, ..
+        } => list_pages(*json),
+        Commands::Build { file, .. } => {
+            let result = match file {
+                Some(file) => build_one(file),
+                None => build_all().map(|_| ()),
+            };
+            // Written even when the build failed, that's when editors need it
+            if let Some(path) = cli.errors_json_path() {
+                BUILD_STATS.write_diagnostics(path)?;
+            }
+            result
+        }
         Commands::Deploy { .. } => {
             let repo = build_all()?;
             deploy_site(&repo, &cfg())
         }
         Commands::Serve { .. } => {
-            build_all()?;
+            // `--no-build` serves the existing output, watch rebuilds on change
+            if cfg().serve.no_build {
+                collect_site_data(&cfg())?;
+            } else {
+                build_all()?;
+            }
             serve_site()
         }
+        Commands::Doctor => run_doctor(&cfg()),
+        Commands::Version { .. } => unreachable!("handled before loading the config"),
+        Commands::Config {
+            command: ConfigCommand::Print { json, .. },
+        } => {
+            print!("{}", cfg().to_pretty_string(*json)?);
+            Ok(())
+        }
+    }
+}
+
+/// Build a single content file (`tola build <FILE>`).
+fn build_one(file: &Path) -> Result<()> {
+    let c = cfg();
+    build_file(file, &c)?;
+    if c.build.strict {
+        BUILD_STATS.fail_on_warnings()?;
     }
+    Ok(())
+}
+
+/// Print every page with its output path and URL (`tola build --list`).
+fn list_pages(json: bool) -> Result<()> {
+    let pages = compiler::list::list_pages(&cfg())?;
+    print!("{}", compiler::list::render_page_list(&pages, json)?);
+    Ok(())
 }
 
 /// Build site and optionally generate rss/sitemap in parallel.
@@ -48,16 +106,103 @@ fn main() -> Result<()> {
 /// rss generation is controlled by `config.build.rss.enable`.
 /// Sitemap generation is controlled by `config.build.sitemap.enable`.
 /// Output cleanup is controlled by `config.build.clean`.
+/// Orphaned output files are reported, or deleted with `config.build.prune_orphans`.
+/// A JSON report is written when `tola build --report <PATH>` is given,
+/// an HTML one with `--report-html`/`--open-report`.
+/// The output is packaged into `config.build.archive` last, if set.
+/// With `config.build.atomic`, all of this runs against a staging directory
+/// that replaces the output only once everything has succeeded.
 fn build_all() -> Result<ThreadSafeRepository> {
     let c = cfg();
+    if c.build.atomic {
+        build_atomic(&c, build_pipeline)?;
+        // The staged repo handle points at the moved staging directory
+        return git::open_repo(&c.build.output);
+    }
+    build_pipeline(&c)
+}
+
+/// Build the site into `c.build.output`, see [`build_all`].
+fn build_pipeline(c: &SiteConfig) -> Result<ThreadSafeRepository> {
+    let start = Instant::now();
     // Build site first, collecting page metadata
-    let (repo, pages) = build_site(&c, false)?;
+    let (repo, pages) = build_site(c, false)?;
 
     // Generate rss and sitemap in parallel using collected pages
-    let (rss_result, sitemap_result) =
-        rayon::join(|| build_rss(&c, &pages), || build_sitemap(&c, &pages));
+    let (rss_result, sitemap_result) = rayon::join(
+        || {
+            let start = Instant::now();
+            let result = build_rss(c, &pages);
+            BUILD_STATS.record(Stage::Rss, start.elapsed());
+            result
+        },
+        || {
+            let start = Instant::now();
+            let result = build_sitemap(c, &pages);
+            BUILD_STATS.record(Stage::Sitemap, start.elapsed());
+            result
+        },
+    );
 
     rss_result?;
     sitemap_result?;
+    reconcile_output(c, &pages)?;
+    check_links(c);
+    BUILD_STATS.record(Stage::Total, start.elapsed());
+
+    if c.build.verbose {
+        report::log_output_size(&c.build.output);
+    }
+
+    if let Some(path) = c.cli.and_then(Cli::report_path) {
+        BuildReport::collect(&c.build.output, &pages).write(path)?;
+    }
+    if let Some(cli) = c.cli
+        && let Some(path) = cli.report_html_path()
+    {
+        HtmlReport::collect(&c.build.output, &pages).write(&path)?;
+        log!("report"; "{}", path.display());
+        if cli.open_report()
+            && let Err(e) = report::open_in_browser(&path)
+        {
+            log!("warn"; "{e:#}");
+            BUILD_STATS.warn(format!("{e:#}"));
+        }
+    }
+    if c.build.strict {
+        BUILD_STATS.fail_on_warnings()?;
+    }
+    build_archive(c)?;
     Ok(repo)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_strict_build_fails_on_warning() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        fs::write(content.join("index.typ"), "#link(\"/missing/\")[Gone]").unwrap();
+
+        let mut config = SiteConfig::default();
+        config.set_root(dir.path());
+        config.build.content = content;
+        config.build.output = dir.path().join("public");
+        config.build.assets = dir.path().join("assets");
+        config.build.typst.use_lib = true;
+
+        // The broken link is only a warning without `--strict`
+        config.build.check_links = true;
+        build_pipeline(&config).unwrap();
+
+        config.build.strict = true;
+        let err = build_pipeline(&config).unwrap_err().to_string();
+        assert!(err.starts_with("--strict: build produced"), "{err}");
+        assert!(err.contains("broken link `/missing`"), "{err}");
+    }
+}
@@ -0,0 +1,581 @@
+//! Machine-readable build report.
+//!
+//! Collects counts, sizes and stage timings during a build and serializes
+//! them as JSON (`tola build --report build.json`) for CI dashboards.
+//!
+//! # Report Format
+//!
+//! ```json
+//! {
+//!   "version": "0.6.5",
+//!   "page_count": 12,
+//!   "asset_count": 34,
+//!   "total_bytes": 567890,
+//!   "stages": {
+//!     "metadata_ms": 120, "compile_ms": 340, "assets_ms": 15,
+//!     "svg_ms": 80, "rss_ms": 2, "sitemap_ms": 1, "total_ms": 480
+//!   },
+//!   "warnings": []
+//! }
+//! ```
+//!
+//! All keys are always present, so consumers can rely on a fixed structure.
+//! Collection is additive: human-readable logging is unaffected.
+//!
+//! With `--verbose`, the build also logs the total output size and the
+//! largest output files.
+//!
+//! `tola build --report-html [PATH]` renders the same figures, plus the
+//! largest files and slowest compiles, as a standalone HTML page;
+//! `--open-report` also opens it in the browser.
+
+use crate::compiler::meta::Pages;
+use crate::log;
+use crate::typst_lib::Diagnostic;
+use crate::utils::typst_element::html_escape;
+use anyhow::{Context, Result, bail};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        LazyLock,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+/// Global build statistics, reset at the start of each build.
+pub static BUILD_STATS: LazyLock<BuildStats> = LazyLock::new(BuildStats::default);
+
+// ============================================================================
+// Stats Collection
+// ============================================================================
+
+/// Build stage with a recorded duration.
+#[derive(Debug, Clone, Copy)]
+pub enum Stage {
+    Metadata,
+    Compile,
+    Assets,
+    Svg,
+    Rss,
+    Sitemap,
+    Total,
+}
+
+impl Stage {
+    const COUNT: usize = 7;
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Thread-safe collector for build statistics.
+#[derive(Debug, Default)]
+pub struct BuildStats {
+    /// Stage durations in microseconds, indexed by [`Stage`]
+    stages: [AtomicU64; Stage::COUNT],
+    /// Number of asset files processed
+    assets: AtomicUsize,
+    /// Warnings emitted during the build
+    warnings: Mutex<Vec<String>>,
+    /// Per-page compile durations (content-relative path)
+    page_times: Mutex<Vec<(String, Duration)>>,
+    /// Typst errors and warnings with positions, for `--errors-json`
+    diagnostics: Mutex<Vec<Diagnostic>>,
+}
+
+impl BuildStats {
+    /// Clear all collected statistics.
+    pub fn reset(&self) {
+        for stage in &self.stages {
+            stage.store(0, Ordering::Relaxed);
+        }
+        self.assets.store(0, Ordering::Relaxed);
+        self.warnings.lock().clear();
+        self.page_times.lock().clear();
+        self.diagnostics.lock().clear();
+    }
+
+    /// Add elapsed time to a stage.
+    ///
+    /// Durations accumulate, so stages running per-page (e.g. SVG) report
+    /// the summed time across all worker threads.
+    pub fn record(&self, stage: Stage, elapsed: Duration) {
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        self.stages[stage.index()].fetch_add(micros, Ordering::Relaxed);
+    }
+
+    /// Count one processed asset file.
+    pub fn inc_assets(&self) {
+        self.assets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a warning message (once, repeats are ignored).
+    ///
+    /// Returns whether the message is new, for warnings repeated per page.
+    pub fn warn(&self, message: impl Into<String>) -> bool {
+        let message = message.into();
+        let mut warnings = self.warnings.lock();
+        if warnings.contains(&message) {
+            return false;
+        }
+        warnings.push(message);
+        true
+    }
+
+    /// Fail with every recorded warning, for `--strict`.
+    pub fn fail_on_warnings(&self) -> Result<()> {
+        let warnings = self.warnings.lock();
+        if warnings.is_empty() {
+            return Ok(());
+        }
+        let list: String = warnings.iter().map(|w| format!("\n  - {w}")).collect();
+        bail!(
+            "--strict: build produced {} warnings:{list}",
+            warnings.len()
+        )
+    }
+
+    /// Record Typst diagnostics (repeats from the second compile pass are ignored).
+    pub fn record_diagnostics(&self, new: Vec<Diagnostic>) {
+        let mut diagnostics = self.diagnostics.lock();
+        for diag in new {
+            if !diagnostics.contains(&diag) {
+                diagnostics.push(diag);
+            }
+        }
+    }
+
+    /// Write recorded diagnostics to `path` as a JSON array of
+    /// `{file, line, col, severity, message}` (`--errors-json`).
+    pub fn write_diagnostics(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(&*self.diagnostics.lock())?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write diagnostics to {}", path.display()))
+    }
+
+    /// Record how long a page took to compile.
+    pub fn record_page(&self, path: impl Into<String>, elapsed: Duration) {
+        self.page_times.lock().push((path.into(), elapsed));
+    }
+
+    /// The `n` slowest pages compiled so far, slowest first.
+    pub fn slowest_pages(&self, n: usize) -> Vec<(String, Duration)> {
+        slowest(self.page_times.lock().clone(), n)
+    }
+
+    fn millis(&self, stage: Stage) -> u64 {
+        self.stages[stage.index()].load(Ordering::Relaxed) / 1000
+    }
+}
+
+/// Sort `(path, duration)` pairs slowest first (ties by path) and keep `n`.
+fn slowest(mut times: Vec<(String, Duration)>, n: usize) -> Vec<(String, Duration)> {
+    times.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    times.truncate(n);
+    times
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// Summary of a completed build.
+#[derive(Debug, Serialize)]
+pub struct BuildReport {
+    pub version: &'static str,
+    pub page_count: usize,
+    pub asset_count: usize,
+    /// Total size of all files in the output directory (excluding `.git`)
+    pub total_bytes: u64,
+    pub stages: StageDurations,
+    pub warnings: Vec<String>,
+}
+
+/// Per-stage durations in milliseconds.
+#[derive(Debug, Default, Serialize)]
+pub struct StageDurations {
+    pub metadata_ms: u64,
+    pub compile_ms: u64,
+    pub assets_ms: u64,
+    pub svg_ms: u64,
+    pub rss_ms: u64,
+    pub sitemap_ms: u64,
+    pub total_ms: u64,
+}
+
+impl BuildReport {
+    /// Build a report from the global stats and the collected pages.
+    pub fn collect(output: &Path, pages: &Pages) -> Self {
+        let stats = &*BUILD_STATS;
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            page_count: pages.len(),
+            asset_count: stats.assets.load(Ordering::Relaxed),
+            total_bytes: output_size(output),
+            stages: StageDurations {
+                metadata_ms: stats.millis(Stage::Metadata),
+                compile_ms: stats.millis(Stage::Compile),
+                assets_ms: stats.millis(Stage::Assets),
+                svg_ms: stats.millis(Stage::Svg),
+                rss_ms: stats.millis(Stage::Rss),
+                sitemap_ms: stats.millis(Stage::Sitemap),
+                total_ms: stats.millis(Stage::Total),
+            },
+            warnings: stats.warnings.lock().clone(),
+        }
+    }
+
+    /// Serialize the report as pretty-printed JSON and write it to `path`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write build report to {}", path.display()))
+    }
+}
+
+/// Rows listed in each table of the HTML report.
+const HTML_REPORT_ROWS: usize = 10;
+
+/// [`BuildReport`] with the largest files and slowest compiles, as HTML.
+pub struct HtmlReport {
+    report: BuildReport,
+    largest: Vec<(PathBuf, u64)>,
+    slowest: Vec<(String, Duration)>,
+}
+
+impl HtmlReport {
+    /// Collect the report, see [`BuildReport::collect`].
+    pub fn collect(output: &Path, pages: &Pages) -> Self {
+        let files = output_files(output)
+            .map(|(path, len)| {
+                let rel = path.strip_prefix(output).unwrap_or(&path).to_path_buf();
+                (rel, len)
+            })
+            .collect();
+        Self {
+            report: BuildReport::collect(output, pages),
+            largest: largest(files, HTML_REPORT_ROWS),
+            slowest: BUILD_STATS.slowest_pages(HTML_REPORT_ROWS),
+        }
+    }
+
+    /// Render a self-contained HTML page.
+    pub fn render(&self) -> String {
+        let report = &self.report;
+        let stages = &report.stages;
+        let mut html = String::with_capacity(4096);
+        html.push_str(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>tola build report</title>\n<style>\n\
+             body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem}\n\
+             table{border-collapse:collapse;margin-bottom:2rem}\n\
+             td,th{padding:.25rem .75rem;text-align:left;border-bottom:1px solid #ddd}\n\
+             td.num{text-align:right;font-variant-numeric:tabular-nums}\n\
+             </style>\n</head>\n<body>\n",
+        );
+        html.push_str(&format!("<h1>tola {} build report</h1>\n", report.version));
+
+        html.push_str("<table>\n");
+        let summary = [
+            ("Pages", report.page_count.to_string()),
+            ("Assets", report.asset_count.to_string()),
+            ("Output size", format_size(report.total_bytes)),
+            ("Total time", format!("{} ms", stages.total_ms)),
+        ];
+        for (name, value) in summary {
+            html.push_str(&format!(
+                "<tr><th>{name}</th><td class=\"num\">{value}</td></tr>\n"
+            ));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Stages</h2>\n<table>\n");
+        let stage_rows = [
+            ("Metadata", stages.metadata_ms),
+            ("Compile", stages.compile_ms),
+            ("Assets", stages.assets_ms),
+            ("SVG", stages.svg_ms),
+            ("RSS", stages.rss_ms),
+            ("Sitemap", stages.sitemap_ms),
+        ];
+        for (name, ms) in stage_rows {
+            html.push_str(&format!(
+                "<tr><th>{name}</th><td class=\"num\">{ms} ms</td></tr>\n"
+            ));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Largest files</h2>\n<table>\n");
+        for (path, len) in &self.largest {
+            html.push_str(&format!(
+                "<tr><td class=\"num\">{}</td><td>{}</td></tr>\n",
+                format_size(*len),
+                html_escape(&path.to_string_lossy())
+            ));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Slowest compiles</h2>\n<table>\n");
+        for (path, elapsed) in &self.slowest {
+            html.push_str(&format!(
+                "<tr><td class=\"num\">{} ms</td><td>{}</td></tr>\n",
+                elapsed.as_millis(),
+                html_escape(path)
+            ));
+        }
+        html.push_str("</table>\n");
+
+        // Broken links are recorded as warnings too
+        html.push_str(&format!(
+            "<h2>Warnings ({})</h2>\n<ul>\n",
+            report.warnings.len()
+        ));
+        for warning in &report.warnings {
+            html.push_str(&format!("<li>{}</li>\n", html_escape(warning)));
+        }
+        html.push_str("</ul>\n</body>\n</html>\n");
+        html
+    }
+
+    /// Render the report and write it to `path`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.render())
+            .with_context(|| format!("Failed to write HTML report to {}", path.display()))
+    }
+}
+
+/// Open `path` with the platform's default handler (the browser for HTML).
+pub fn open_in_browser(path: &Path) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command
+        .arg(path)
+        .spawn()
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    Ok(())
+}
+
+/// Sum file sizes under `output`, skipping the deploy `.git` directory.
+fn output_size(output: &Path) -> u64 {
+    output_files(output).map(|(_, len)| len).sum()
+}
+
+/// Files under `output` with their sizes, skipping the deploy `.git` directory.
+pub fn output_files(output: &Path) -> impl Iterator<Item = (PathBuf, u64)> {
+    walkdir::WalkDir::new(output)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| Some((e.path().to_path_buf(), e.metadata().ok()?.len())))
+}
+
+/// Number of files listed by [`log_output_size`].
+const LARGEST_FILES: usize = 10;
+
+/// Log the total output size and the largest files (`--verbose`).
+pub fn log_output_size(output: &Path) {
+    let files: Vec<_> = output_files(output)
+        .map(|(path, len)| {
+            let rel = path.strip_prefix(output).unwrap_or(&path).to_path_buf();
+            (rel, len)
+        })
+        .collect();
+    let total = files.iter().map(|(_, len)| len).sum();
+
+    log!("build"; "output: {} in {} files, largest:", format_size(total), files.len());
+    for (path, len) in largest(files, LARGEST_FILES) {
+        log!("build"; "{:>8}  {}", format_size(len), path.display());
+    }
+}
+
+/// Sort `(path, size)` pairs largest first (ties by path) and keep `n`.
+fn largest(mut files: Vec<(PathBuf, u64)>, n: usize) -> Vec<(PathBuf, u64)> {
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    files.truncate(n);
+    files
+}
+
+/// Format a byte count like `inline_max_size` values: `"100B"`, `"1.5KB"`, `"20MB"`.
+#[allow(clippy::cast_precision_loss)] // Display only
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    let value = format!("{value:.1}");
+    format!("{}{unit}", value.trim_end_matches(".0"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SiteConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_report_for_fixture_site() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        fs::write(content.join("index.typ"), "= Home").unwrap();
+        fs::write(content.join("about.typ"), "= About").unwrap();
+
+        let mut config = SiteConfig::default();
+        config.set_root(dir.path());
+        config.build.content = content;
+        config.build.output = dir.path().join("public");
+        config.build.assets = dir.path().join("assets");
+        config.build.typst.use_lib = true;
+
+        let (_, pages) = crate::build::build_site(&config, true).unwrap();
+        let report = BuildReport::collect(&config.build.output, &pages);
+        let json = serde_json::to_value(&report).unwrap();
+
+        for key in [
+            "version",
+            "page_count",
+            "asset_count",
+            "total_bytes",
+            "stages",
+            "warnings",
+        ] {
+            assert!(json.get(key).is_some(), "missing key: {key}");
+        }
+        for key in [
+            "metadata_ms",
+            "compile_ms",
+            "assets_ms",
+            "svg_ms",
+            "rss_ms",
+            "sitemap_ms",
+            "total_ms",
+        ] {
+            assert!(json["stages"].get(key).is_some(), "missing stage: {key}");
+        }
+        assert_eq!(json["page_count"], 2);
+        assert!(report.total_bytes > 0);
+    }
+
+    #[test]
+    fn test_html_report_for_fixture_site() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        fs::write(content.join("index.typ"), "= Home").unwrap();
+        fs::write(content.join("about.typ"), "= About").unwrap();
+        fs::write(content.join("contact.typ"), "= Contact").unwrap();
+
+        let mut config = SiteConfig::default();
+        config.set_root(dir.path());
+        config.build.content = content;
+        config.build.output = dir.path().join("public");
+        config.build.assets = dir.path().join("assets");
+        config.build.typst.use_lib = true;
+
+        let (_, pages) = crate::build::build_site(&config, true).unwrap();
+        let path = dir.path().join("build-report.html");
+        HtmlReport::collect(&config.build.output, &pages)
+            .write(&path)
+            .unwrap();
+
+        let html = fs::read_to_string(path).unwrap();
+        assert!(html.contains(r#"<tr><th>Pages</th><td class="num">3</td></tr>"#));
+        assert!(html.contains("<h2>Largest files</h2>"));
+        assert!(html.contains("index.html"));
+    }
+
+    #[test]
+    fn test_stats_reset() {
+        let stats = BuildStats::default();
+        stats.record(Stage::Rss, Duration::from_millis(5));
+        stats.inc_assets();
+        stats.warn("something");
+
+        stats.reset();
+        assert_eq!(stats.millis(Stage::Rss), 0);
+        assert_eq!(stats.assets.load(Ordering::Relaxed), 0);
+        assert!(stats.warnings.lock().is_empty());
+    }
+
+    #[test]
+    fn test_fail_on_warnings_lists_all() {
+        let stats = BuildStats::default();
+        assert!(stats.fail_on_warnings().is_ok());
+
+        stats.warn("broken link `/a/` in index.html");
+        stats.warn("3 orphaned files in output");
+        stats.warn("3 orphaned files in output");
+
+        let err = stats.fail_on_warnings().unwrap_err().to_string();
+        assert!(err.contains("2 warnings"), "{err}");
+        assert!(err.contains("/a/") && err.contains("orphaned"), "{err}");
+    }
+
+    #[test]
+    fn test_slowest_pages_sorted_and_truncated() {
+        let ms = Duration::from_millis;
+        let times = vec![
+            ("a.typ".to_string(), ms(10)),
+            ("posts/hello.typ".to_string(), ms(142)),
+            ("c.typ".to_string(), ms(50)),
+            ("b.typ".to_string(), ms(50)),
+            ("d.typ".to_string(), ms(1)),
+        ];
+
+        let top = slowest(times.clone(), 3);
+        let names: Vec<&str> = top.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(names, ["posts/hello.typ", "b.typ", "c.typ"]);
+        assert_eq!(top[0].1, ms(142));
+
+        assert_eq!(slowest(times, 10).len(), 5);
+        assert!(slowest(Vec::new(), 10).is_empty());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0B");
+        assert_eq!(format_size(1023), "1023B");
+        assert_eq!(format_size(1024), "1KB");
+        assert_eq!(format_size(1536), "1.5KB");
+        assert_eq!(format_size(20 * 1024 * 1024), "20MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3GB");
+    }
+
+    #[test]
+    fn test_largest_files_sorted_and_truncated() {
+        let files = vec![
+            (PathBuf::from("a.html"), 10),
+            (PathBuf::from("img/big.avif"), 9000),
+            (PathBuf::from("c.css"), 500),
+            (PathBuf::from("b.js"), 500),
+        ];
+
+        let top = largest(files.clone(), 3);
+        let names: Vec<_> = top.iter().map(|(p, _)| p.to_str().unwrap()).collect();
+        assert_eq!(names, ["img/big.avif", "b.js", "c.css"]);
+        assert_eq!(largest(files, 10).len(), 4);
+    }
+}
@@ -5,6 +5,10 @@
 //!
 //! - Static file serving from the build output directory
 //! - Automatic `index.html` resolution for directories
+//! - `path_prefix` mounting (`/` redirects to `/<prefix>/`)
+//! - Precompressed `.br`/`.gz` sidecars honoring `Accept-Encoding`
+//! - Single byte-range requests (`206 Partial Content`) for media seeking
+//! - `[[serve.proxy]]` forwarding of path prefixes to a backend
 //! - Directory listing with a clean HTML interface
 //! - File watching and auto-rebuild (via `watch` module)
 //! - Graceful shutdown on Ctrl+C
@@ -28,15 +32,15 @@
 //! ```
 
 use crate::{
-    config::{SiteConfig, cfg},
+    config::{ProxyRule, SiteConfig, cfg},
     log,
     watch::watch_for_changes_blocking,
 };
 use anyhow::{Context, Result};
 use std::{
     fs,
-    io::Cursor,
-    net::SocketAddr,
+    io::{self, BufRead, BufReader, Cursor, Read, Seek, SeekFrom, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -69,21 +73,22 @@ const MAX_PORT_RETRIES: u16 = 10;
 /// The server blocks until Ctrl+C is received.
 pub fn serve_site() -> Result<()> {
     let c = cfg();
-    let interface: std::net::IpAddr = c.serve.interface.parse()?;
-    let base_port = c.serve.port;
-
-    let (server, addr) = try_bind_port(interface, base_port, MAX_PORT_RETRIES)?;
-    let server = Arc::new(server);
+    let listeners = bind_servers(&c.serve.interface, c.serve.port)?;
+    let servers: Vec<_> = listeners.servers.into_iter().map(Arc::new).collect();
 
     // Set up Ctrl+C handler for graceful shutdown
-    let server_for_signal = Arc::clone(&server);
+    let servers_for_signal = servers.clone();
     ctrlc::set_handler(move || {
         log!("serve"; "shutting down...");
-        server_for_signal.unblock();
+        for server in &servers_for_signal {
+            server.unblock();
+        }
     })
     .context("Failed to set Ctrl+C handler")?;
 
-    log!("serve"; "http://{}", addr);
+    for addr in &listeners.addrs {
+        log!("serve"; "http://{}{}", addr, c.paths().url_for_filename(""));
+    }
 
     // Spawn file watcher thread
     if c.serve.watch {
@@ -94,20 +99,94 @@ pub fn serve_site() -> Result<()> {
         });
     }
 
+    // Extra listeners (the other address family) get their own threads
+    let (server, rest) = servers.split_first().context("No address to listen on")?;
+    for extra in rest {
+        let extra = Arc::clone(extra);
+        std::thread::spawn(move || serve_requests(&extra));
+    }
+
     // Handle requests in main thread (blocks until Ctrl+C)
+    serve_requests(server);
+
+    Ok(())
+}
+
+/// Handle requests on `server` until it is unblocked.
+fn serve_requests(server: &Server) {
     for request in server.incoming_requests() {
         // Re-load config on each request to pick up hot-reloaded changes
         if let Err(e) = handle_request(request, &cfg()) {
             log!("serve"; "request error: {e}");
         }
     }
+}
 
-    Ok(())
+/// Addresses to listen on for `[serve].interface`, primary first.
+///
+/// - `localhost`: both loopbacks, `127.0.0.1` first so hosts without IPv6
+///   still get a server
+/// - `::`: also `0.0.0.0`, for systems where IPv6 sockets are IPv6-only
+/// - anything else: that single IP
+fn bind_addresses(interface: &str) -> Result<Vec<IpAddr>> {
+    if interface.eq_ignore_ascii_case("localhost") {
+        return Ok(vec![Ipv4Addr::LOCALHOST.into(), Ipv6Addr::LOCALHOST.into()]);
+    }
+    let ip: IpAddr = interface
+        .parse()
+        .with_context(|| format!("Invalid [serve].interface: {interface}"))?;
+    if ip == Ipv6Addr::UNSPECIFIED {
+        Ok(vec![ip, Ipv4Addr::UNSPECIFIED.into()])
+    } else {
+        Ok(vec![ip])
+    }
+}
+
+/// Servers bound for `[serve].interface`.
+struct Listeners {
+    /// Bound servers, primary first
+    servers: Vec<Server>,
+    /// Addresses accepting connections, including ones served by a
+    /// dual-stack socket bound for another address
+    addrs: Vec<SocketAddr>,
+}
+
+/// Bind a server for each address of `interface`, all on the same port.
+///
+/// Only the primary address may move to another port or fail the bind.
+/// Secondary addresses are best effort. On a dual-stack system `0.0.0.0`
+/// is already covered by `[::]` and reports "address in use"; it is still
+/// listed as reachable.
+fn bind_servers(interface: &str, base_port: u16) -> Result<Listeners> {
+    let mut addresses = bind_addresses(interface)?.into_iter();
+    let primary = addresses.next().context("No address to listen on")?;
+    let (server, addr) = try_bind_port(primary, base_port, MAX_PORT_RETRIES)?;
+
+    let mut listeners = Listeners {
+        servers: vec![server],
+        addrs: vec![addr],
+    };
+    for ip in addresses {
+        let extra = SocketAddr::new(ip, addr.port());
+        match Server::http(extra) {
+            Ok(server) => listeners.servers.push(server),
+            Err(e)
+                if primary.is_unspecified()
+                    && e.downcast_ref::<io::Error>()
+                        .is_some_and(|e| e.kind() == io::ErrorKind::AddrInUse) => {}
+            Err(e) => {
+                log!("serve"; "not listening on {extra}: {e}");
+                continue;
+            }
+        }
+        listeners.addrs.push(extra);
+    }
+    Ok(listeners)
 }
 
 /// Try to bind to a port, retrying with incremented port numbers if in use.
 fn try_bind_port(
-    interface: std::net::IpAddr,
+    interface: IpAddr,
     base_port: u16,
     max_retries: u16,
 ) -> Result<(Server, SocketAddr)> {
@@ -120,6 +199,8 @@ fn try_bind_port(
                 if offset > 0 {
                     log!("serve"; "port {} in use, using {} instead", base_port, port);
                 }
+                // The OS picks the port when asked for port 0
+                let addr = server.server_addr().to_ip().unwrap_or(addr);
                 return Ok((server, addr));
             }
             Err(_) if offset + 1 < max_retries => {
@@ -145,15 +226,24 @@ fn try_bind_port(
 // Request Handling
 // ============================================================================
 
+/// How a request path resolves against the output directory.
+#[derive(Debug, PartialEq, Eq)]
+enum Resolved {
+    /// Serve this file
+    File(PathBuf),
+    /// Generate a listing for this directory
+    Listing(PathBuf),
+    /// Redirect to this URL path
+    Redirect(String),
+    NotFound,
+}
+
 /// Handle a single HTTP request.
-///
-/// Request resolution order:
-/// 1. Exact file match → serve file
-/// 2. Directory with index.html → serve index.html
-/// 3. Directory without index.html → generate listing
-/// 4. Nothing found → 404
 fn handle_request(request: Request, config: &SiteConfig) -> Result<()> {
-    let serve_root = &config.build.output;
+    if let Some(rule) = config.serve.proxy.iter().find(|r| r.matches(request.url())) {
+        return proxy_request(request, rule);
+    }
+
     let data_dir_name = config.build.data.to_string_lossy();
 
     // Decode URL-encoded characters (e.g., %20 → space)
@@ -165,27 +255,140 @@ fn handle_request(request: Request, config: &SiteConfig) -> Result<()> {
     // This is important for cache-busting URLs like "font.woff2?t=123"
     let path_without_query = url_path.split('?').next().unwrap_or(&url_path);
     let request_path = path_without_query.trim_matches('/');
-    let local_path = serve_root.join(request_path);
 
-    // Try to serve the file directly
+    match resolve_request(request_path, config) {
+        Resolved::File(path) => serve_file(request, &path, config),
+        Resolved::Listing(dir) => {
+            match generate_directory_listing(&dir, request_path, &data_dir_name, config) {
+                Ok(listing) => serve_html(request, listing),
+                Err(_) => serve_not_found(request),
+            }
+        }
+        Resolved::Redirect(location) => serve_redirect(request, &location),
+        Resolved::NotFound => serve_not_found(request),
+    }
+}
+
+/// Resolve a request path (without leading/trailing slashes).
+///
+/// Resolution order:
+/// 1. `/` with a `path_prefix` → redirect to `/<prefix>/`
+/// 2. Exact file match → serve file
+/// 3. Directory with an index page (`index.html`) → serve it
+/// 4. Directory without one → generate listing
+/// 5. Nothing found → 404
+///
+/// Pages are written under `<output>/<path_prefix>/`, so resolving against the
+/// output root serves them at the same `/<prefix>/...` URLs as in production.
+fn resolve_request(request_path: &str, config: &SiteConfig) -> Resolved {
+    let paths = config.paths();
+    if request_path.is_empty() && paths.has_prefix() {
+        return Resolved::Redirect(paths.url_for_filename(""));
+    }
+
+    let local_path = config.build.output.join(request_path);
     if local_path.is_file() {
-        return serve_file(request, &local_path);
+        return Resolved::File(local_path);
     }
 
-    // If it's a directory, try index.html or generate listing
     if local_path.is_dir() {
-        let index_path = local_path.join("index.html");
+        let index_path = local_path.join(config.build.index_filename());
         if index_path.is_file() {
-            return serve_file(request, &index_path);
+            return Resolved::File(index_path);
+        }
+        return Resolved::Listing(local_path);
+    }
+
+    Resolved::NotFound
+}
+
+// ============================================================================
+// Proxy
+// ============================================================================
+
+/// Hop-by-hop headers, which apply to a single connection and aren't forwarded.
+const HOP_BY_HOP: &[&str] = &[
+    "Connection",
+    "Keep-Alive",
+    "Proxy-Connection",
+    "TE",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+];
+
+fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP.iter().any(|h| h.eq_ignore_ascii_case(name))
+}
+
+/// Forward `request` to the backend of `rule` and relay its response.
+///
+/// Method, path, query, headers and body are passed through; `Host` names the
+/// backend. The backend is asked over HTTP/1.0 so it replies with a plain body
+/// (never chunked), which is streamed to the client until the backend closes
+/// the connection. Unreachable backends get a `502 Bad Gateway`.
+fn proxy_request(mut request: Request, rule: &ProxyRule) -> Result<()> {
+    let Some((authority, base)) = rule.backend() else {
+        return serve_bad_gateway(request);
+    };
+    let mut backend = match TcpStream::connect(&authority) {
+        Ok(stream) => stream,
+        Err(e) => {
+            log!("serve"; "proxy {}: {e}", rule.target);
+            return serve_bad_gateway(request);
+        }
+    };
+
+    let mut head = format!(
+        "{} {base}{} HTTP/1.0\r\nHost: {authority}\r\nConnection: close\r\n",
+        request.method().as_str(),
+        request.url()
+    );
+    for header in request.headers() {
+        let name = header.field.as_str().as_str();
+        if !header.field.equiv("Host") && !is_hop_by_hop(name) {
+            head.push_str(&format!("{name}: {}\r\n", header.value));
         }
+    }
+    head.push_str("\r\n");
+    backend.write_all(head.as_bytes())?;
+    io::copy(request.as_reader(), &mut backend)?;
 
-        if let Ok(listing) = generate_directory_listing(&local_path, request_path, &data_dir_name) {
-            return serve_html(request, listing);
+    let mut reader = BufReader::new(backend);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let status: u16 = line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .with_context(|| format!("invalid response from {}: {line:?}", rule.target))?;
+
+    let mut headers = Vec::new();
+    let mut length = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let Some((name, value)) = line.trim_end().split_once(':') else {
+            break;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("Content-Length") {
+            length = value.parse().ok();
+        } else if !is_hop_by_hop(name) {
+            headers.extend(Header::from_bytes(name, value).ok());
         }
     }
 
-    // 404 Not Found
-    serve_not_found(request)
+    request.respond(Response::new(
+        StatusCode(status),
+        headers,
+        reader,
+        length,
+        None,
+    ))?;
+    Ok(())
 }
 
 // ============================================================================
@@ -193,21 +396,233 @@ fn handle_request(request: Request, config: &SiteConfig) -> Result<()> {
 // ============================================================================
 
 /// Serve a file with appropriate content type.
-fn serve_file(request: Request, path: &Path) -> Result<()> {
-    let content = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
-    let content_type = guess_content_type(path);
+///
+/// If the client accepts it and a precompressed sidecar (`.br`/`.gz`) exists,
+/// the sidecar is served with `Content-Encoding` and the original `Content-Type`.
+///
+/// A single `Range: bytes=...` is answered with `206 Partial Content` (or
+/// `416` if unsatisfiable); ranges always refer to the uncompressed file.
+fn serve_file(request: Request, path: &Path, config: &SiteConfig) -> Result<()> {
+    let header = |name: &'static str| {
+        request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv(name))
+            .map(|h| h.value.as_str().to_owned())
+    };
+    let range = header("Range");
+    let accept_encoding = header("Accept-Encoding").unwrap_or_default();
+    let sidecar = range
+        .is_none()
+        .then(|| find_precompressed(path, &accept_encoding))
+        .flatten();
+    let file_path = sidecar.as_ref().map_or(path, |(p, _)| p.as_path());
+
+    let mut file = fs::File::open(file_path)
+        .with_context(|| format!("Failed to read {}", file_path.display()))?;
+    let content_type = content_type(path, config);
 
-    let response = Response::from_data(content)
-        .with_header(Header::from_bytes("Content-Type", content_type).unwrap());
+    // Only the requested bytes are read, however large the file
+    let len = usize::try_from(file.metadata()?.len())?;
+    let (status, body, body_len, content_range): (_, Box<dyn Read + Send>, _, _) =
+        match parse_range(range.as_deref(), len) {
+            ByteRange::Full => (200, Box::new(file), len, None),
+            ByteRange::Partial(start, end) => {
+                file.seek(SeekFrom::Start(start as u64))?;
+                let body_len = end - start + 1;
+                let body = Box::new(file.take(body_len as u64));
+                (
+                    206,
+                    body,
+                    body_len,
+                    Some(format!("bytes {start}-{end}/{len}")),
+                )
+            }
+            ByteRange::Unsatisfiable => (
+                416,
+                Box::new(io::empty()),
+                0,
+                Some(format!("bytes */{len}")),
+            ),
+        };
+
+    let mut response = Response::new(StatusCode(status), Vec::new(), body, Some(body_len), None)
+        .with_header(Header::from_bytes("Content-Type", content_type).unwrap())
+        .with_header(Header::from_bytes("Accept-Ranges", "bytes").unwrap())
+        .with_header(Header::from_bytes("Vary", "Accept-Encoding").unwrap())
+        .with_header(Header::from_bytes("Cache-Control", cache_control(path, config)).unwrap());
+    if let Some(content_range) = content_range {
+        response.add_header(Header::from_bytes("Content-Range", content_range).unwrap());
+    }
+    if let Some((_, encoding)) = sidecar {
+        response.add_header(Header::from_bytes("Content-Encoding", encoding).unwrap());
+    }
 
     request.respond(response)?;
     Ok(())
 }
 
+/// A `Range` request resolved against a file's length.
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    /// No (usable) range: serve the whole file
+    Full,
+    /// Inclusive byte offsets to serve
+    Partial(usize, usize),
+    /// Range starts past the end of the file
+    Unsatisfiable,
+}
+
+/// Parse a `Range` header value (`bytes=0-499`, `bytes=500-`, `bytes=-500`).
+///
+/// Multiple ranges and malformed values fall back to the full file, which
+/// servers may always do instead of a partial response.
+fn parse_range(header: Option<&str>, len: usize) -> ByteRange {
+    let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+        return ByteRange::Full;
+    };
+    let Some((start, end)) = spec.split_once('-').filter(|_| !spec.contains(',')) else {
+        return ByteRange::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    let (start, end) = if start.is_empty() {
+        // Suffix range: the last `end` bytes
+        let Ok(suffix) = end.parse::<usize>() else {
+            return ByteRange::Full;
+        };
+        if suffix == 0 {
+            return ByteRange::Unsatisfiable;
+        }
+        (len.saturating_sub(suffix), len.saturating_sub(1))
+    } else {
+        let Ok(start) = start.parse::<usize>() else {
+            return ByteRange::Full;
+        };
+        let end = match end {
+            "" => len.saturating_sub(1),
+            end => match end.parse::<usize>() {
+                Ok(end) if end >= start => end.min(len.saturating_sub(1)),
+                _ => return ByteRange::Full,
+            },
+        };
+        (start, end)
+    };
+
+    if start >= len {
+        ByteRange::Unsatisfiable
+    } else {
+        ByteRange::Partial(start, end)
+    }
+}
+
+/// `Cache-Control` for fingerprinted assets (content hash in the filename).
+const CACHE_IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+/// `Cache-Control` for everything else, so rebuilds show up on reload.
+const CACHE_REVALIDATE: &str = "no-cache";
+
+/// `Cache-Control` for `path`: `[serve].cache_control` override by extension,
+/// else long-lived for fingerprinted assets and `no-cache` otherwise.
+///
+/// HTML is never fingerprinted, so live reload always sees fresh pages.
+fn cache_control<'a>(path: &Path, config: &'a SiteConfig) -> &'a str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    if let Some((_, value)) = config
+        .serve
+        .cache_control
+        .iter()
+        .find(|(key, _)| key.trim_start_matches('.').eq_ignore_ascii_case(ext))
+    {
+        return value;
+    }
+
+    if !matches!(ext, "html" | "htm" | "xhtml") && is_fingerprinted(path) {
+        CACHE_IMMUTABLE
+    } else {
+        CACHE_REVALIDATE
+    }
+}
+
+/// Check if the file is named like tola's own fingerprinted assets:
+/// `.{name}-{hash}.{ext}` with the 8 lowercase hex digits of
+/// [`fingerprint`](crate::utils::hash::fingerprint), e.g. `.enhance-a1b2c3d4.css`.
+fn is_fingerprinted(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(|stem| stem.strip_prefix('.')?.rsplit_once('-'))
+        .is_some_and(|(_, hash)| {
+            hash.len() == 8
+                && hash
+                    .bytes()
+                    .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        })
+}
+
+/// Precompressed sidecars in order of preference: (extension, encoding).
+const PRECOMPRESSED: &[(&str, &str)] = &[("br", "br"), ("gz", "gzip")];
+
+/// Find an accepted precompressed sidecar next to `path` (e.g. `app.js.gz`).
+///
+/// Returns the sidecar path and its `Content-Encoding` value.
+fn find_precompressed(path: &Path, accept_encoding: &str) -> Option<(PathBuf, &'static str)> {
+    // (coding, accepted): `gzip;q=0` explicitly refuses the encoding
+    let codings: Vec<(&str, bool)> = accept_encoding
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';').map(str::trim);
+            let coding = params.next().filter(|c| !c.is_empty())?;
+            let refused =
+                params.any(|p| p.strip_prefix("q=").is_some_and(|q| q.parse() == Ok(0.0)));
+            Some((coding, !refused))
+        })
+        .collect();
+    // An explicit entry wins over `*`, so `br;q=0, *` refuses `br`
+    let accepts = |encoding: &str| {
+        codings
+            .iter()
+            .find(|(c, _)| c.eq_ignore_ascii_case(encoding))
+            .or_else(|| codings.iter().find(|(c, _)| *c == "*"))
+            .is_some_and(|&(_, accepted)| accepted)
+    };
+
+    PRECOMPRESSED.iter().find_map(|&(ext, encoding)| {
+        if !accepts(encoding) {
+            return None;
+        }
+        let mut sidecar = path.as_os_str().to_owned();
+        sidecar.push(".");
+        sidecar.push(ext);
+        let sidecar = PathBuf::from(sidecar);
+        sidecar.is_file().then_some((sidecar, encoding))
+    })
+}
+
 /// Serve HTML content.
 fn serve_html(request: Request, content: String) -> Result<()> {
     let response = Response::from_string(content)
-        .with_header(Header::from_bytes("Content-Type", "text/html; charset=utf-8").unwrap());
+        .with_header(Header::from_bytes("Content-Type", "text/html; charset=utf-8").unwrap())
+        .with_header(Header::from_bytes("Cache-Control", CACHE_REVALIDATE).unwrap());
+    request.respond(response)?;
+    Ok(())
+}
+
+/// Serve a 302 redirect to `location`.
+fn serve_redirect(request: Request, location: &str) -> Result<()> {
+    let response = Response::empty(StatusCode(302))
+        .with_header(Header::from_bytes("Location", location).unwrap());
+    request.respond(response)?;
+    Ok(())
+}
+
+/// Serve 502 Bad Gateway response, for an unreachable proxy backend.
+fn serve_bad_gateway(request: Request) -> Result<()> {
+    let response = Response::from_string("502 Bad Gateway")
+        .with_status_code(StatusCode(502))
+        .with_header(Header::from_bytes("Content-Type", "text/plain").unwrap());
     request.respond(response)?;
     Ok(())
 }
@@ -229,17 +644,37 @@ fn serve_not_found(request: Request) -> Result<()> {
 // Content Type Detection
 // ============================================================================
 
-/// Guess MIME content type from file extension.
+/// Content type for `path`: `[serve].mime_types` override, else built-in guess.
+fn content_type<'a>(path: &Path, config: &'a SiteConfig) -> &'a str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    ext.as_deref()
+        .and_then(|ext| {
+            config
+                .serve
+                .mime_types
+                .iter()
+                .find(|(key, _)| key.trim_start_matches('.').eq_ignore_ascii_case(ext))
+        })
+        .map_or_else(|| guess_content_type(ext.as_deref()), |(_, v)| v.as_str())
+}
+
+/// Guess MIME content type from a (lowercase) file extension.
 ///
 /// Returns `application/octet-stream` for unknown extensions.
-fn guess_content_type(path: &Path) -> &'static str {
-    match path.extension().and_then(|e| e.to_str()) {
+fn guess_content_type(ext: Option<&str>) -> &'static str {
+    match ext {
         // Web content
         Some("html" | "htm") => "text/html; charset=utf-8",
+        Some("xhtml") => "application/xhtml+xml; charset=utf-8",
         Some("css") => "text/css; charset=utf-8",
-        Some("js" | "mjs") => "application/javascript; charset=utf-8",
-        Some("json") => "application/json; charset=utf-8",
+        Some("js" | "mjs" | "cjs") => "text/javascript; charset=utf-8",
+        Some("json" | "map") => "application/json; charset=utf-8",
+        Some("webmanifest") => "application/manifest+json; charset=utf-8",
         Some("xml") => "application/xml; charset=utf-8",
+        Some("wasm") => "application/wasm",
 
         // Images
         Some("svg") => "image/svg+xml",
@@ -256,6 +691,11 @@ fn guess_content_type(path: &Path) -> &'static str {
         Some("ttf") => "font/ttf",
         Some("otf") => "font/otf",
 
+        // Media
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("mp3") => "audio/mpeg",
+
         // Documents
         Some("pdf") => "application/pdf",
         Some("txt") => "text/plain; charset=utf-8",
@@ -273,7 +713,7 @@ fn guess_content_type(path: &Path) -> &'static str {
 /// Generate HTML directory listing for browsing.
 ///
 /// Features:
-/// - Only shows directories and `.html` files
+/// - Only shows directories and page files (`[build].page_extension`)
 /// - Filters out hidden files (starting with '.')
 /// - Filters out internal data directory
 /// - Shows folder/file icons
@@ -283,6 +723,7 @@ fn generate_directory_listing(
     dir_path: &PathBuf,
     request_path: &str,
     data_dir_name: &str,
+    config: &SiteConfig,
 ) -> std::io::Result<String> {
     let entries: Vec<_> = fs::read_dir(dir_path)?
         .filter_map(Result::ok)
@@ -296,8 +737,8 @@ fn generate_directory_listing(
             // Allow directories
             let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
 
-            // Only show .html files, filter out feed.xml, sitemap.xml, etc.
-            !is_hidden && (is_dir || name_str.ends_with(".html"))
+            // Only show pages, filter out feed.xml, sitemap.xml, etc.
+            !is_hidden && (is_dir || config.build.is_page_file(Path::new(&name)))
         })
         .map(|entry| {
             let name = entry.file_name().to_string_lossy().into_owned();
@@ -344,3 +785,387 @@ fn generate_directory_listing(
         .replace("{parent_link}", &parent_link)
         .replace("{entries}", &entries.join("\n            ")))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_config(dir: &Path, prefix: &str) -> SiteConfig {
+        let mut config = SiteConfig::default();
+        config.build.output = dir.join("public");
+        config.build.path_prefix = PathBuf::from(prefix);
+        let site = config.paths().output_dir();
+        fs::create_dir_all(site.join("posts/hello")).unwrap();
+        fs::write(site.join("index.html"), "home").unwrap();
+        fs::write(site.join("posts/hello/index.html"), "hello").unwrap();
+        config
+    }
+
+    #[test]
+    fn test_resolve_with_path_prefix() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path(), "blog");
+        let site = dir.path().join("public/blog");
+
+        assert_eq!(
+            resolve_request("blog/index.html", &config),
+            Resolved::File(site.join("index.html"))
+        );
+        assert_eq!(
+            resolve_request("blog", &config),
+            Resolved::File(site.join("index.html"))
+        );
+        assert_eq!(
+            resolve_request("blog/posts/hello", &config),
+            Resolved::File(site.join("posts/hello/index.html"))
+        );
+        assert_eq!(
+            resolve_request("", &config),
+            Resolved::Redirect("/blog/".into())
+        );
+        assert_eq!(resolve_request("posts/hello", &config), Resolved::NotFound);
+    }
+
+    #[test]
+    fn test_precompressed_sidecar() {
+        let dir = TempDir::new().unwrap();
+        let js = dir.path().join("app.js");
+        fs::write(&js, "console.log(1)").unwrap();
+        fs::write(dir.path().join("app.js.gz"), [0x1f, 0x8b]).unwrap();
+
+        assert_eq!(
+            find_precompressed(&js, "gzip, deflate"),
+            Some((dir.path().join("app.js.gz"), "gzip"))
+        );
+        // Not accepted, or no sidecar for the accepted encoding
+        assert_eq!(find_precompressed(&js, ""), None);
+        assert_eq!(find_precompressed(&js, "gzip;q=0"), None);
+        assert_eq!(find_precompressed(&js, "br"), None);
+
+        // Brotli preferred when both exist and are accepted
+        fs::write(dir.path().join("app.js.br"), [0]).unwrap();
+        assert_eq!(
+            find_precompressed(&js, "gzip, br"),
+            Some((dir.path().join("app.js.br"), "br"))
+        );
+        // An explicit `q=0` holds against the wildcard
+        assert_eq!(
+            find_precompressed(&js, "br;q=0, *"),
+            Some((dir.path().join("app.js.gz"), "gzip"))
+        );
+        assert_eq!(find_precompressed(&js, "br;q=0, gzip;q=0, *"), None);
+        assert_eq!(find_precompressed(&js, "*;q=0"), None);
+        // Content type still comes from the original file
+        assert_eq!(
+            content_type(&js, &SiteConfig::default()),
+            "text/javascript; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn test_serves_gzip_sidecar() {
+        use std::io::{Read, Write};
+
+        let dir = TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().to_path_buf();
+        fs::write(dir.path().join("style.css"), "body{}").unwrap();
+        fs::write(dir.path().join("style.css.gz"), "GZIPPED").unwrap();
+
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let handle = std::thread::spawn(move || {
+            let request = server.recv().unwrap();
+            handle_request(request, &config).unwrap();
+        });
+
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "GET /style.css HTTP/1.1\r\nHost: localhost\r\nAccept-Encoding: gzip\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        handle.join().unwrap();
+
+        assert!(response.contains("Content-Encoding: gzip"), "{response}");
+        assert!(response.contains("Cache-Control: no-cache"), "{response}");
+        assert!(response.contains("Content-Type: text/css"), "{response}");
+        assert!(response.ends_with("GZIPPED"), "{response}");
+    }
+
+    #[test]
+    fn test_bind_addresses() {
+        let ips = |interface| bind_addresses(interface).unwrap();
+        assert_eq!(
+            ips("localhost"),
+            [
+                IpAddr::from(Ipv4Addr::LOCALHOST),
+                Ipv6Addr::LOCALHOST.into()
+            ]
+        );
+        assert_eq!(
+            ips("::"),
+            [
+                IpAddr::from(Ipv6Addr::UNSPECIFIED),
+                Ipv4Addr::UNSPECIFIED.into()
+            ]
+        );
+        assert_eq!(ips("127.0.0.1"), [IpAddr::from(Ipv4Addr::LOCALHOST)]);
+        assert!(bind_addresses("example.com").is_err());
+    }
+
+    #[test]
+    fn test_serves_over_ipv6() {
+        use std::io::Read;
+
+        let dir = TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().to_path_buf();
+        fs::write(dir.path().join("index.html"), "home").unwrap();
+
+        let Ok(mut listeners) = bind_servers("::1", 0) else {
+            eprintln!("IPv6 loopback unavailable, skipping");
+            return;
+        };
+        let (server, addr) = (listeners.servers.remove(0), listeners.addrs[0]);
+        assert!(addr.is_ipv6() && addr.port() != 0, "{addr}");
+        let handle = std::thread::spawn(move || {
+            let request = server.recv().unwrap();
+            handle_request(request, &config).unwrap();
+        });
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "GET / HTTP/1.1\r\nHost: [::1]\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        handle.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.ends_with("home"), "{response}");
+    }
+
+    #[test]
+    fn test_proxy_forwards_prefix() {
+        use std::io::Read;
+        use std::net::TcpListener;
+
+        // Stub backend: echo the request line and body
+        let backend = TcpListener::bind("127.0.0.1:0").unwrap();
+        let backend_addr = backend.local_addr().unwrap();
+        let backend_handle = std::thread::spawn(move || {
+            let (mut stream, _) = backend.accept().unwrap();
+            let mut received = String::new();
+            let mut buf = [0; 1024];
+            while !received.ends_with("\r\n\r\nping") {
+                let n = stream.read(&mut buf).unwrap();
+                assert!(n > 0, "{received}");
+                received.push_str(std::str::from_utf8(&buf[..n]).unwrap());
+            }
+            write!(
+                stream,
+                "HTTP/1.0 201 Created\r\nContent-Type: text/plain\r\nX-Backend: stub\r\n\r\nfrom backend"
+            )
+            .unwrap();
+            received
+        });
+
+        let dir = TempDir::new().unwrap();
+        let mut config = make_config(dir.path(), "");
+        config.serve.proxy = vec![ProxyRule {
+            prefix: "/api".into(),
+            target: format!("http://{backend_addr}"),
+        }];
+
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let handle = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let request = server.recv().unwrap();
+                handle_request(request, &config).unwrap();
+            }
+        });
+        let send = |raw: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(raw.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let proxied = send(
+            "POST /api/x?q=1 HTTP/1.1\r\nHost: localhost\r\nX-Token: abc\r\nContent-Length: 4\r\nConnection: close\r\n\r\nping",
+        );
+        let received = backend_handle.join().unwrap();
+        assert!(
+            received.starts_with("POST /api/x?q=1 HTTP/1.0\r\n"),
+            "{received}"
+        );
+        assert!(received.contains("X-Token: abc\r\n"), "{received}");
+        assert!(proxied.starts_with("HTTP/1.1 201"), "{proxied}");
+        assert!(proxied.contains("X-Backend: stub"), "{proxied}");
+        assert!(proxied.contains("from backend"), "{proxied}");
+
+        let local =
+            send("GET /posts/hello/ HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+        handle.join().unwrap();
+        assert!(local.starts_with("HTTP/1.1 200"), "{local}");
+        assert!(local.ends_with("hello"), "{local}");
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range(None, 10), ByteRange::Full);
+        assert_eq!(parse_range(Some("bytes=2-5"), 10), ByteRange::Partial(2, 5));
+        assert_eq!(parse_range(Some("bytes=4-"), 10), ByteRange::Partial(4, 9));
+        assert_eq!(parse_range(Some("bytes=-3"), 10), ByteRange::Partial(7, 9));
+        assert_eq!(
+            parse_range(Some("bytes=8-99"), 10),
+            ByteRange::Partial(8, 9)
+        );
+        assert_eq!(parse_range(Some("bytes=10-"), 10), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range(Some("bytes=0-1,4-5"), 10), ByteRange::Full);
+        assert_eq!(parse_range(Some("bytes=5-2"), 10), ByteRange::Full);
+        assert_eq!(parse_range(Some("items=0-1"), 10), ByteRange::Full);
+    }
+
+    #[test]
+    fn test_serves_byte_range() {
+        let dir = TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().to_path_buf();
+        fs::write(dir.path().join("clip.mp4"), "0123456789").unwrap();
+
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let handle = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let request = server.recv().unwrap();
+                handle_request(request, &config).unwrap();
+            }
+        });
+        let get = |range: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "GET /clip.mp4 HTTP/1.1\r\nHost: localhost\r\n{range}Connection: close\r\n\r\n"
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let partial = get("Range: bytes=2-5\r\n");
+        assert!(partial.starts_with("HTTP/1.1 206"), "{partial}");
+        assert!(partial.contains("Content-Range: bytes 2-5/10"), "{partial}");
+        assert!(partial.contains("Content-Length: 4"), "{partial}");
+        assert!(partial.contains("Accept-Ranges: bytes"), "{partial}");
+        assert!(partial.ends_with("\r\n\r\n2345"), "{partial}");
+
+        let full = get("");
+        handle.join().unwrap();
+        assert!(full.starts_with("HTTP/1.1 200"), "{full}");
+        assert!(full.contains("Accept-Ranges: bytes"), "{full}");
+        assert!(full.ends_with("0123456789"), "{full}");
+    }
+
+    #[test]
+    fn test_content_type() {
+        let mut config = SiteConfig::default();
+        assert_eq!(
+            content_type(Path::new("pkg/app_bg.wasm"), &config),
+            "application/wasm"
+        );
+        assert_eq!(
+            content_type(Path::new("mod.MJS"), &config),
+            "text/javascript; charset=utf-8"
+        );
+        assert_eq!(content_type(Path::new("a.avif"), &config), "image/avif");
+        assert_eq!(
+            content_type(Path::new("noext"), &config),
+            "application/octet-stream"
+        );
+
+        config
+            .serve
+            .mime_types
+            .insert(".wasm".into(), "application/x-custom".into());
+        config
+            .serve
+            .mime_types
+            .insert("glb".into(), "model/gltf-binary".into());
+        assert_eq!(
+            content_type(Path::new("a.wasm"), &config),
+            "application/x-custom"
+        );
+        assert_eq!(
+            content_type(Path::new("scene.glb"), &config),
+            "model/gltf-binary"
+        );
+    }
+
+    #[test]
+    fn test_cache_control() {
+        let mut config = SiteConfig::default();
+        assert_eq!(cache_control(Path::new("index.html"), &config), "no-cache");
+        assert_eq!(
+            cache_control(Path::new("a1b2c3d4.html"), &config),
+            "no-cache"
+        );
+        assert_eq!(cache_control(Path::new("styles.css"), &config), "no-cache");
+        assert_eq!(
+            cache_control(Path::new(".enhance-a1b2c3d4.css"), &config),
+            CACHE_IMMUTABLE
+        );
+        assert_eq!(
+            cache_control(Path::new(".enhance-a1b2c3d4.js"), &config),
+            CACHE_IMMUTABLE
+        );
+        // Hex-looking names that tola didn't produce aren't fingerprints
+        for name in [
+            "app.3f9e0c1a77.js",
+            "app-3f9e0c1a.js",
+            "report-20241231.pdf",
+            ".enhance-A1B2C3D4.css",
+            ".enhance-a1b2c3d4e5.css",
+        ] {
+            assert_eq!(
+                cache_control(Path::new(name), &config),
+                "no-cache",
+                "{name}"
+            );
+        }
+
+        config
+            .serve
+            .cache_control
+            .insert("js".into(), "no-store".into());
+        assert_eq!(
+            cache_control(Path::new(".enhance-a1b2c3d4.js"), &config),
+            "no-store"
+        );
+    }
+
+    #[test]
+    fn test_resolve_without_path_prefix() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path(), "");
+        let site = dir.path().join("public");
+
+        assert_eq!(
+            resolve_request("", &config),
+            Resolved::File(site.join("index.html"))
+        );
+        assert_eq!(
+            resolve_request("posts", &config),
+            Resolved::Listing(site.join("posts"))
+        );
+    }
+}
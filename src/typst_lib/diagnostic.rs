@@ -8,6 +8,9 @@
 //! - Colored output with theme support (error=red, warning=yellow, help=cyan)
 //! - Hints and trace information
 //!
+//! Diagnostics can also be resolved to structured [`Diagnostic`]s with 1-indexed
+//! positions, written by `tola build --errors-json` for editor integration.
+//!
 //! # Architecture
 //!
 //! The module is organized into several layers:
@@ -29,6 +32,7 @@
 use std::fmt::Write;
 
 use colored::{ColoredString, Colorize};
+use serde::Serialize;
 use typst::World;
 use typst::diag::{Severity, SourceDiagnostic};
 use typst::syntax::Span;
@@ -328,6 +332,50 @@ pub fn format_diagnostics<W: World>(world: &W, diagnostics: &[SourceDiagnostic])
     output
 }
 
+/// A diagnostic resolved to a file position, for editor problem matchers
+/// (`tola build --errors-json`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// File path (relative to project root)
+    pub file: String,
+    /// Line number (1-indexed)
+    pub line: usize,
+    /// Column (1-indexed, unlike the 0-indexed columns of the text output)
+    pub col: usize,
+    /// `"error"` or `"warning"`
+    pub severity: &'static str,
+    pub message: String,
+}
+
+/// Resolve diagnostics to file positions.
+///
+/// Diagnostics without a source span are attributed to the start of `file`.
+pub fn to_diagnostics<W: World>(
+    world: &W,
+    diagnostics: &[SourceDiagnostic],
+    file: &str,
+) -> Vec<Diagnostic> {
+    diagnostics
+        .iter()
+        .map(|diag| {
+            let (file, line, col) = SpanLocation::from_span(world, diag.span).map_or_else(
+                || (file.to_owned(), 1, 1),
+                |loc| (loc.path, loc.start_line, loc.start_col + 1),
+            );
+            Diagnostic {
+                file,
+                line,
+                col,
+                severity: match diag.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                message: diag.message.to_string(),
+            }
+        })
+        .collect()
+}
+
 /// Count errors and warnings in a diagnostic list.
 #[allow(dead_code)]
 pub fn count_diagnostics(diagnostics: &[SourceDiagnostic]) -> (usize, usize) {
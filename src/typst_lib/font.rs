@@ -21,6 +21,23 @@
 //! 1. Custom paths provided at initialization (e.g., project fonts)
 //! 2. System fonts (if enabled)
 //!
+//! # Discovery Cache
+//!
+//! With `--incremental`, discovery results (path, collection index and
+//! [`FontInfo`] of every font) are kept in `.tola-cache/fonts.json` so cold
+//! builds skip parsing every font file. The cache is rescanned when:
+//!
+//! - the tola version changes (typst's `FontInfo` may change with it)
+//! - any cached font file changed or disappeared (size/mtime)
+//! - any directory that held a font, or a search root, changed its mtime,
+//!   which catches fonts added next to existing ones
+//!
+//! Fonts added to a directory that held none before are only picked up after
+//! deleting the cache. Font data itself is still loaded lazily on first use.
+//!
+//! Packages need no extra cache: typst-kit already keeps them on disk (see
+//! `package.rs`), and comemo's in-memory memoization can't be serialized.
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -31,45 +48,180 @@
 //! let book: &FontBook = &fonts.1;
 //!
 //! // Get font by index
-//! if let Some(font) = fonts.0.fonts.get(0) {
+//! if let Some(font) = fonts.0.get(0) {
 //!     let font: Font = font.get()?;
 //! }
 //! ```
 
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::time::SystemTime;
 
-use typst::text::FontBook;
+use serde::{Deserialize, Serialize};
+use typst::foundations::Bytes;
+use typst::text::{Font, FontBook, FontInfo};
 use typst::utils::LazyHash;
 use typst_kit::fonts::Fonts;
 
+/// File name of the discovery cache inside `.tola-cache/`.
+pub const FONT_CACHE_FILE: &str = "fonts.json";
+
+/// Discovered fonts and their book, shared by all compilations.
+pub type SharedFonts = (Vec<FontSlot>, LazyHash<FontBook>);
+
 /// Global shared fonts - initialized once with custom font paths.
 ///
 /// Uses `OnceLock` for thread-safe, one-time initialization.
 /// The first call to `get_fonts` determines the font paths for all
 /// subsequent compilations.
-static GLOBAL_FONTS: OnceLock<(Fonts, LazyHash<FontBook>)> = OnceLock::new();
+static GLOBAL_FONTS: OnceLock<SharedFonts> = OnceLock::new();
+
+/// A discovered font file; the font itself is loaded on first access.
+#[derive(Debug)]
+pub struct FontSlot {
+    path: PathBuf,
+    index: u32,
+    font: OnceLock<Option<Font>>,
+}
+
+impl FontSlot {
+    fn new(path: PathBuf, index: u32) -> Self {
+        Self {
+            path,
+            index,
+            font: OnceLock::new(),
+        }
+    }
+
+    /// Get the font, reading it from disk on first access.
+    pub fn get(&self) -> Option<Font> {
+        self.font
+            .get_or_init(|| {
+                let data = fs::read(&self.path).ok()?;
+                Font::new(Bytes::new(data), self.index)
+            })
+            .clone()
+    }
+}
+
+/// One discovered font, as stored in the cache.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedFont {
+    path: PathBuf,
+    index: u32,
+    info: FontInfo,
+}
+
+/// On-disk discovery results, see the module docs for invalidation.
+#[derive(Debug, Serialize, Deserialize)]
+struct FontCache {
+    version: String,
+    /// Font files and directories (font parents and search roots) → mtime
+    stamps: BTreeMap<PathBuf, (u64, SystemTime)>,
+    fonts: Vec<CachedFont>,
+}
+
+impl FontCache {
+    fn new(font_paths: &[&Path], fonts: Vec<CachedFont>) -> Self {
+        let files = fonts.iter().map(|f| f.path.as_path());
+        let dirs = fonts.iter().filter_map(|f| f.path.parent());
+        let roots = font_paths.iter().copied();
+        let stamps = files
+            .chain(dirs)
+            .chain(roots)
+            .filter_map(|path| Some((path.to_path_buf(), stamp(path)?)))
+            .collect();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            stamps,
+            fonts,
+        }
+    }
+
+    /// Read the cache, or `None` if missing or stale.
+    fn load(path: &Path) -> Option<Self> {
+        let cache: Self = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+        let fresh = cache.version == env!("CARGO_PKG_VERSION")
+            && cache
+                .stamps
+                .iter()
+                .all(|(path, recorded)| stamp(path).is_some_and(|s| s == *recorded));
+        fresh.then_some(cache)
+    }
+
+    /// Write discovery results to `path`.
+    fn write(path: &Path, font_paths: &[&Path], fonts: Vec<CachedFont>) -> std::io::Result<()> {
+        // Create the cache dir before stamping, as it may sit in a search root
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let cache = Self::new(font_paths, fonts);
+        fs::write(path, serde_json::to_vec(&cache)?)
+    }
+}
+
+/// Size and mtime of a file or directory.
+fn stamp(path: &Path) -> Option<(u64, SystemTime)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.len(), meta.modified().ok()?))
+}
+
+/// Scan the font paths and system fonts.
+fn search_fonts(font_paths: &[&Path]) -> Vec<CachedFont> {
+    let mut searcher = Fonts::searcher();
+    // Include system fonts (platform-specific locations)
+    searcher.include_system_fonts(true);
+    // Search custom paths and system fonts
+    let fonts = searcher.search_with(font_paths);
+    fonts
+        .fonts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, slot)| {
+            Some(CachedFont {
+                path: slot.path()?.to_path_buf(),
+                index: slot.index(),
+                info: fonts.book.info(i)?.clone(),
+            })
+        })
+        .collect()
+}
 
 /// Initialize fonts with custom font paths.
 ///
 /// # Arguments
 ///
 /// * `font_paths` - Additional directories to search for fonts
+/// * `cache` - Discovery cache file to reuse (and refresh when stale)
 ///
 /// # Returns
 ///
 /// A tuple of:
-/// - `Fonts`: The font collection with lazy-loaded font data
+/// - `Vec<FontSlot>`: The font collection with lazy-loaded font data
 /// - `LazyHash<FontBook>`: The font book index wrapped for comemo caching
-fn init_fonts(font_paths: &[&Path]) -> (Fonts, LazyHash<FontBook>) {
-    let mut searcher = Fonts::searcher();
-    // Include system fonts (platform-specific locations)
-    searcher.include_system_fonts(true);
-    // Search custom paths and system fonts
-    let fonts = searcher.search_with(font_paths);
+fn init_fonts(font_paths: &[&Path], cache: Option<&Path>) -> SharedFonts {
+    let fonts = match cache.and_then(FontCache::load) {
+        Some(cached) => cached.fonts,
+        None => {
+            let fonts = search_fonts(font_paths);
+            if let Some(path) = cache {
+                // A failed write only costs the next build a rescan
+                let _ = FontCache::write(path, font_paths, fonts.clone());
+            }
+            fonts
+        }
+    };
+
     // Wrap font book in LazyHash for comemo caching
-    let book = LazyHash::new(fonts.book.clone());
-    (fonts, book)
+    let book = LazyHash::new(FontBook::from_infos(fonts.iter().map(|f| f.info.clone())));
+    let slots = fonts
+        .into_iter()
+        .map(|f| FontSlot::new(f.path, f.index))
+        .collect();
+    (slots, book)
 }
 
 /// Get or initialize global fonts.
@@ -91,9 +243,16 @@ fn init_fonts(font_paths: &[&Path]) -> (Fonts, LazyHash<FontBook>) {
 ///
 /// This function is thread-safe. If called concurrently, only one thread
 /// performs initialization; others wait and receive the shared result.
-pub fn get_fonts(font_path: Option<&Path>) -> &'static (Fonts, LazyHash<FontBook>) {
-    GLOBAL_FONTS
-        .get_or_init(|| font_path.map_or_else(|| init_fonts(&[]), |path| init_fonts(&[path])))
+pub fn get_fonts(font_path: Option<&Path>) -> &'static SharedFonts {
+    get_fonts_cached(font_path, None)
+}
+
+/// Like [`get_fonts`], reusing the discovery cache at `cache` if given.
+pub fn get_fonts_cached(font_path: Option<&Path>, cache: Option<&Path>) -> &'static SharedFonts {
+    GLOBAL_FONTS.get_or_init(|| {
+        let font_paths: Vec<&Path> = font_path.into_iter().collect();
+        init_fonts(&font_paths, cache)
+    })
 }
 
 #[cfg(test)]
@@ -105,7 +264,7 @@ mod tests {
         let fonts = get_fonts(None);
         // Should find at least some system fonts on most systems
         // Note: This test may fail in minimal container environments
-        assert!(!fonts.0.fonts.is_empty(), "Should find system fonts");
+        assert!(!fonts.0.is_empty(), "Should find system fonts");
     }
 
     #[test]
@@ -126,6 +285,39 @@ mod tests {
         assert!(std::ptr::eq(fonts1, fonts2), "Fonts should be shared");
     }
 
+    #[test]
+    fn test_discovery_cache_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache_path = dir.path().join(".tola-cache").join(FONT_CACHE_FILE);
+        let fonts_dir = dir.path().join("fonts");
+        fs::create_dir_all(&fonts_dir).unwrap();
+
+        let found = search_fonts(&[]);
+        // Pretend one font lives in the project so its directory is stamped
+        let font = found.first().cloned().expect("Should find system fonts");
+        let local = fonts_dir.join("local.ttf");
+        fs::copy(&font.path, &local).unwrap();
+        let fonts = vec![CachedFont {
+            path: local.clone(),
+            ..font
+        }];
+
+        FontCache::write(&cache_path, &[dir.path()], fonts.clone()).unwrap();
+        let loaded = FontCache::load(&cache_path).expect("fresh cache loads");
+        assert_eq!(loaded.fonts, fonts);
+
+        // The cached entries build a usable book and slots
+        let (slots, book) = init_fonts(&[dir.path()], Some(&cache_path));
+        assert_eq!(slots.len(), 1);
+        assert_eq!(book.info(0), Some(&fonts[0].info));
+        assert!(slots[0].get().is_some());
+
+        // Adding a font next to a cached one invalidates the cache
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(fonts_dir.join("new.ttf"), "").unwrap();
+        assert!(FontCache::load(&cache_path).is_none());
+    }
+
     #[test]
     fn test_subsequent_calls_ignore_path() {
         // First call initializes (may have been done by other tests)
@@ -14,6 +14,12 @@
 //! HTML-specific functions like `#html.elem` for raw HTML output. This is
 //! required for HTML export via `typst-html`.
 //!
+//! # Inputs
+//!
+//! Values from `[build.typst].inputs` / `--input key=value` are exposed to
+//! documents as `sys.inputs`. Changing them rebuilds the library via
+//! [`set_inputs`]; worlds created afterwards pick up the new instance.
+//!
 //! # Usage
 //!
 //! The library is accessed via the `World::library()` method:
@@ -21,16 +27,25 @@
 //! ```ignore
 //! impl World for SystemWorld {
 //!     fn library(&self) -> &LazyHash<Library> {
-//!         &GLOBAL_LIBRARY
+//!         &self.library // Arc cloned from `library()` at construction
 //!     }
 //! }
 //! ```
 
-use std::sync::LazyLock;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
 
+use parking_lot::RwLock;
+use typst::foundations::{Dict, IntoValue, Str};
 use typst::utils::LazyHash;
 use typst::{Feature, Features, Library, LibraryExt};
 
+/// Shared library together with the inputs it was built from.
+struct SharedLibrary {
+    inputs: HashMap<String, String>,
+    library: Arc<LazyHash<Library>>,
+}
+
 /// Global shared library - Typst's standard library with HTML feature enabled.
 ///
 /// Uses `LazyLock` for thread-safe, one-time initialization on first access.
@@ -39,14 +54,42 @@ use typst::{Feature, Features, Library, LibraryExt};
 /// # Features Enabled
 ///
 /// - `Feature::Html` - Enables HTML-specific functions for HTML export
-pub static GLOBAL_LIBRARY: LazyLock<LazyHash<Library>> = LazyLock::new(|| {
+static GLOBAL_LIBRARY: LazyLock<RwLock<SharedLibrary>> = LazyLock::new(|| {
+    let inputs = HashMap::new();
+    let library = build_library(&inputs);
+    RwLock::new(SharedLibrary { inputs, library })
+});
+
+/// Get the current shared library.
+pub fn library() -> Arc<LazyHash<Library>> {
+    GLOBAL_LIBRARY.read().library.clone()
+}
+
+/// Set the values exposed as `sys.inputs`.
+///
+/// No-op if the inputs are unchanged, so the comemo cache stays warm.
+pub fn set_inputs(inputs: &HashMap<String, String>) {
+    if GLOBAL_LIBRARY.read().inputs == *inputs {
+        return;
+    }
+    let mut shared = GLOBAL_LIBRARY.write();
+    shared.inputs = inputs.clone();
+    shared.library = build_library(inputs);
+}
+
+fn build_library(inputs: &HashMap<String, String>) -> Arc<LazyHash<Library>> {
+    let inputs: Dict = inputs
+        .iter()
+        .map(|(k, v)| (Str::from(k.as_str()), v.as_str().into_value()))
+        .collect();
     let library = Library::builder()
         // Enable HTML feature for html export support
         .with_features(Features::from_iter([Feature::Html]))
+        .with_inputs(inputs)
         .build();
     // Wrap in LazyHash for comemo caching
-    LazyHash::new(library)
-});
+    Arc::new(LazyHash::new(library))
+}
 
 #[cfg(test)]
 mod tests {
@@ -55,12 +98,12 @@ mod tests {
     #[test]
     fn test_library_initialized() {
         // Should not panic on access
-        let _lib = &*GLOBAL_LIBRARY;
+        let _lib = library();
     }
 
     #[test]
     fn test_library_has_global_scope() {
-        let lib = &*GLOBAL_LIBRARY;
+        let lib = library();
         // The library should have a global scope with standard functions
         let scope = lib.global.scope();
         // Check for some standard typst functions
@@ -70,9 +113,9 @@ mod tests {
 
     #[test]
     fn test_library_is_shared() {
-        let lib1 = &*GLOBAL_LIBRARY;
-        let lib2 = &*GLOBAL_LIBRARY;
-        // Should return the same static reference
-        assert!(std::ptr::eq(lib1, lib2), "Library should be shared");
+        let lib1 = library();
+        let lib2 = library();
+        // Should return the same shared instance
+        assert!(Arc::ptr_eq(&lib1, &lib2), "Library should be shared");
     }
 }
@@ -39,7 +39,7 @@
 //! use tola::typst_lib;
 //!
 //! // Pre-warm at startup (optional but recommended)
-//! typst_lib::warmup_with_root(Path::new("/project/root"));
+//! typst_lib::warmup_with_root(Path::new("/project/root"), None);
 //!
 //! // Compile files - template.typ is cached after first use!
 //! let result = typst_lib::compile_meta(
@@ -56,6 +56,8 @@ mod library;
 mod package;
 mod world;
 
+use crate::report::BUILD_STATS;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use typst::Document;
@@ -63,6 +65,7 @@ use typst::foundations::{Label, Selector, Value};
 use typst::introspection::MetadataElem;
 use typst::utils::PicoStr;
 
+pub use diagnostic::Diagnostic;
 pub use world::SystemWorld;
 
 // =============================================================================
@@ -114,14 +117,29 @@ const fn acquire_test_lock() -> DummyGuard {
 /// Pre-warm global resources (fonts, library, package storage).
 ///
 /// Call once at startup to avoid lazy initialization during compilation.
-/// Pass the project root to include custom fonts from the project directory.
-pub fn warmup_with_root(root: &Path) {
-    let _ = font::get_fonts(Some(root));
-    let _ = &*library::GLOBAL_LIBRARY;
+/// Pass the project root to include custom fonts from the project directory,
+/// and a cache directory to reuse font discovery across runs.
+pub fn warmup_with_root(root: &Path, cache_dir: Option<&Path>) {
+    let font_cache = cache_dir.map(|dir| dir.join(font::FONT_CACHE_FILE));
+    let _ = font::get_fonts_cached(Some(root), font_cache.as_deref());
+    let _ = library::library();
     let _ = &*package::GLOBAL_PACKAGE_STORAGE;
     let _ = &*file::GLOBAL_FILE_CACHE;
 }
 
+/// Set the values exposed to documents as `sys.inputs`.
+///
+/// Applies to all compilations started afterwards.
+pub fn set_inputs(inputs: &HashMap<String, String>) {
+    let _guard = acquire_test_lock();
+    library::set_inputs(inputs);
+}
+
+/// Version of the embedded Typst compiler, e.g. `"0.14.2"`.
+pub fn typst_version() -> String {
+    typst::syntax::package::PackageVersion::compiler().to_string()
+}
+
 /// Compile a Typst file and extract metadata in a single pass.
 ///
 /// This is the **recommended** entry point for building sites, avoiding
@@ -165,6 +183,22 @@ fn compile_base(
 
     let world = SystemWorld::new(path, root);
     let result = typst::compile(&world);
+    let rel = path.strip_prefix(root).unwrap_or(path);
+
+    // Errors and warnings, minus the HTML export notice
+    let diags = match &result.output {
+        Ok(_) => diagnostic::filter_html_warnings(&result.warnings),
+        Err(errors) => {
+            let all_diags: Vec<_> = errors.iter().chain(&result.warnings).cloned().collect();
+            diagnostic::filter_html_warnings(&all_diags)
+        }
+    };
+    // Structured copies for `--errors-json`
+    BUILD_STATS.record_diagnostics(diagnostic::to_diagnostics(
+        &world,
+        &diags,
+        &rel.to_string_lossy(),
+    ));
 
     // Check for errors in warnings
     if diagnostic::has_errors(&result.warnings) {
@@ -173,13 +207,16 @@ fn compile_base(
     }
 
     // Extract document or format errors
-    let document = result.output.map_err(|errors| {
-        let all_diags: Vec<_> = errors.iter().chain(&result.warnings).cloned().collect();
-        let filtered = diagnostic::filter_html_warnings(&all_diags);
-        let formatted = diagnostic::format_diagnostics(&world, &filtered);
+    let document = result.output.map_err(|_| {
+        let formatted = diagnostic::format_diagnostics(&world, &diags);
         anyhow::anyhow!("Typst compilation failed:\n{formatted}")
     })?;
 
+    // Non-fatal warnings only surface in the report and under `--strict`
+    for diag in &diags {
+        BUILD_STATS.warn(format!("{}: {}", rel.display(), diag.message));
+    }
+
     Ok((world, document))
 }
 
@@ -270,7 +307,7 @@ mod tests {
     fn test_warmup_does_not_panic() {
         let dir = TempDir::new().unwrap();
         // Should not panic even with empty directory
-        warmup_with_root(dir.path());
+        warmup_with_root(dir.path(), None);
     }
 
     #[test]
@@ -285,6 +322,24 @@ mod tests {
         assert!(html.contains("Hello World"), "HTML should contain heading");
     }
 
+    #[test]
+    fn test_compile_reads_sys_inputs() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("inputs.typ");
+        fs::write(&file_path, "#sys.inputs.at(\"foo\", default: \"unset\")").unwrap();
+
+        // Hold the lock across set + compile so parallel builds can't reset inputs
+        let _guard = acquire_test_lock();
+        library::set_inputs(&HashMap::from([("foo".into(), "bar".into())]));
+        let result = compile_base(&file_path, dir.path());
+        library::set_inputs(&HashMap::new());
+
+        let (_world, document) = result.unwrap();
+        let html = typst_html::html(&document).unwrap();
+        assert!(html.contains("bar"), "{html}");
+        assert!(!html.contains("unset"), "{html}");
+    }
+
     #[test]
     fn test_compile_nonexistent_file() {
         let dir = TempDir::new().unwrap();
@@ -377,6 +432,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diagnostics_json() {
+        let dir = TempDir::new().unwrap();
+        let content_dir = dir.path().join("content");
+        fs::create_dir_all(&content_dir).unwrap();
+        let file_path = content_dir.join("error.typ");
+        fs::write(&file_path, "= Title\n\n  #undefined_var").unwrap();
+
+        let _guard = acquire_test_lock();
+        let world = SystemWorld::new(&file_path, dir.path());
+        let errors = typst::compile::<typst_html::HtmlDocument>(&world)
+            .output
+            .unwrap_err();
+        let diags = diagnostic::to_diagnostics(&world, &errors, "content/error.typ");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&diags).unwrap()).unwrap();
+        let first = &json[0];
+        assert_eq!(first["file"], "content/error.typ");
+        assert_eq!(first["line"], 3);
+        // 1-indexed: `#undefined_var` is indented by two spaces
+        assert_eq!(first["col"], 4);
+        assert_eq!(first["severity"], "error");
+        assert!(
+            first["message"].as_str().unwrap().contains("undefined_var"),
+            "{json}"
+        );
+    }
+
     #[test]
     fn test_query_meta_basic() {
         let dir = TempDir::new().unwrap();
@@ -10,17 +10,18 @@
 //! SystemWorld (per-compilation, ~lightweight)
 //! ├── root: PathBuf          // Project root for path resolution
 //! ├── main: FileId           // Entry point file ID
-//! ├── fonts: &'static Fonts  // → Global shared fonts
+//! ├── fonts: &'static SharedFonts  // → Global shared fonts
+//! ├── library: Arc<Library>  // → Global shared library
 //! ├── slots: FxHashMap       // Per-instance file cache
 //! └── now: Now               // Lazy datetime
 //!
 //! World trait methods:
-//! ├── library() → &GLOBAL_LIBRARY
-//! ├── book()    → &fonts.book
+//! ├── library() → &library
+//! ├── book()    → &fonts.1
 //! ├── main()    → main FileId
 //! ├── source()  → FileSlot cache
 //! ├── file()    → FileSlot cache
-//! ├── font()    → fonts.fonts[index]
+//! ├── font()    → fonts.0[index]
 //! └── today()   → Now (lazy UTC)
 //! ```
 //!
@@ -44,7 +45,7 @@
 //! ```
 
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 use chrono::{DateTime, Datelike, FixedOffset, Local, Utc};
 use typst::diag::FileResult;
@@ -53,11 +54,11 @@ use typst::syntax::{FileId, Source, VirtualPath};
 use typst::text::{Font, FontBook};
 use typst::utils::LazyHash;
 use typst::{Library, World};
-use typst_kit::fonts::Fonts;
 
 use super::file::{FileSlot, GLOBAL_FILE_CACHE};
-use super::font::get_fonts;
-use super::library::GLOBAL_LIBRARY;
+use super::font::{SharedFonts, get_fonts};
+use super::library;
+use crate::utils::date::source_date_epoch;
 
 // =============================================================================
 // DateTime handling
@@ -96,7 +97,11 @@ pub struct SystemWorld {
 
     /// Reference to global fonts (initialized on first use).
     /// This is a static reference to avoid allocation per compilation.
-    fonts: &'static (Fonts, LazyHash<FontBook>),
+    fonts: &'static SharedFonts,
+
+    /// Shared standard library (carries `sys.inputs`).
+    /// Captured at construction so one compilation sees consistent inputs.
+    library: Arc<LazyHash<Library>>,
 
     /// The current datetime if requested.
     /// Lazily initialized to ensure consistent time throughout compilation.
@@ -145,6 +150,7 @@ impl SystemWorld {
             root,
             main,
             fonts,
+            library: library::library(),
             now: LazyNow(OnceLock::new()),
         }
     }
@@ -181,7 +187,7 @@ impl World for SystemWorld {
     ///
     /// Uses globally shared library with HTML feature enabled.
     fn library(&self) -> &LazyHash<Library> {
-        &GLOBAL_LIBRARY
+        &self.library
     }
 
     /// Returns the font book for font lookup.
@@ -218,22 +224,26 @@ impl World for SystemWorld {
     /// Fonts are indexed in the order they were discovered during
     /// font search. The index comes from font book lookups.
     fn font(&self, index: usize) -> Option<Font> {
-        self.fonts.0.fonts.get(index)?.get()
+        self.fonts.0.get(index)?.get()
     }
 
     /// Get the current date.
     ///
     /// Returns the date at the time of first access within this compilation.
     /// The time is captured once and reused for consistency.
+    /// `SOURCE_DATE_EPOCH` replaces the current time when set.
     ///
     /// # Arguments
     ///
-    /// * `offset` - Optional UTC offset in hours. If `None`, uses local timezone.
+    /// * `offset` - Optional UTC offset in hours. If `None`, uses local timezone
+    ///   (UTC under `SOURCE_DATE_EPOCH`, so the result doesn't depend on the machine).
     fn today(&self, offset: Option<i64>) -> Option<Datetime> {
-        let now = self.now.0.get_or_init(Utc::now);
+        let fixed = source_date_epoch();
+        let now = self.now.0.get_or_init(|| fixed.unwrap_or_else(Utc::now));
 
         // Apply timezone offset
         let with_offset = match offset {
+            None if fixed.is_some() => now.fixed_offset(),
             None => now.with_timezone(&Local).fixed_offset(),
             Some(hours) => {
                 let seconds = i32::try_from(hours).ok()?.checked_mul(3600)?;
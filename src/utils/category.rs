@@ -95,6 +95,17 @@ pub fn categorize_path(path: &Path, config: &SiteConfig) -> FileCategory {
     }
 }
 
+/// Derive a page's section from its path relative to the content directory.
+///
+/// The section is the top-level subdirectory (`posts/hello` → `posts`);
+/// pages directly under the content root have no section.
+pub fn content_section(relative: &Path) -> Option<String> {
+    let mut components = relative.components();
+    let first = components.next()?;
+    components.next()?;
+    Some(first.as_os_str().to_string_lossy().into_owned())
+}
+
 /// Normalize a path to absolute form for reliable comparison.
 ///
 /// Config paths are already canonicalized, so we need to canonicalize
@@ -183,6 +194,24 @@ mod tests {
         assert!(!FileCategory::Unknown.is_directory());
     }
 
+    // ========================================================================
+    // content_section Tests
+    // ========================================================================
+
+    #[test]
+    fn test_content_section() {
+        assert_eq!(
+            content_section(Path::new("posts/hello")),
+            Some("posts".to_string())
+        );
+        assert_eq!(
+            content_section(Path::new("docs/guide/intro")),
+            Some("docs".to_string())
+        );
+        assert_eq!(content_section(Path::new("about")), None);
+        assert_eq!(content_section(Path::new("index")), None);
+    }
+
     // ========================================================================
     // normalize_path Tests
     // ========================================================================
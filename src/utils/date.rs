@@ -7,6 +7,7 @@
 //!
 //! - Zero external dependencies for date parsing
 //! - RFC 2822 and RFC 3339 formatting for feeds
+//! - strftime-like display formatting with localized month/weekday names
 //! - Validation with clear error messages
 //! - Leap year handling
 //!
@@ -20,11 +21,34 @@
 //! // Format for RSS
 //! assert_eq!(dt.to_rfc2822(), "Sat, 15 Jun 2024 14:30:45 GMT");
 //! ```
+//!
+//! # Reproducible Builds
+//!
+//! When `SOURCE_DATE_EPOCH` is set to a Unix timestamp, it replaces the wall
+//! clock for timestamps written into the output: the RSS `<lastBuildDate>`
+//! and Typst's `datetime.today()` (in UTC unless an offset is given).
+//! Whether a page is future-dated or expired is still decided by the clock.
 
 use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+
+/// Environment variable pinning build timestamps (reproducible-builds.org).
+pub const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+/// `SOURCE_DATE_EPOCH`, if set to a valid Unix timestamp.
+pub fn source_date_epoch() -> Option<DateTime<Utc>> {
+    parse_epoch(&std::env::var(SOURCE_DATE_EPOCH).ok()?)
+}
+
+/// Parse a Unix timestamp as given in `SOURCE_DATE_EPOCH`.
+fn parse_epoch(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(value.trim().parse().ok()?, 0)
+}
 
 /// UTC datetime without timezone complexity
-#[derive(Debug, Clone, Copy)]
+///
+/// Ordering is chronological (fields compare from year down to second).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct DateTimeUtc {
     pub year: u16,
     pub month: u8,
@@ -51,6 +75,34 @@ impl DateTimeUtc {
         Self::new(year, month, day, 0, 0, 0)
     }
 
+    /// Current time in UTC.
+    pub fn now() -> Self {
+        Self::from_chrono(Utc::now())
+    }
+
+    /// Time to stamp build output with: `SOURCE_DATE_EPOCH` if set, else now.
+    pub fn build_time() -> Self {
+        source_date_epoch().map_or_else(Self::now, Self::from_chrono)
+    }
+
+    /// Parse a Unix timestamp as given in `SOURCE_DATE_EPOCH`.
+    pub fn from_epoch(value: &str) -> Option<Self> {
+        parse_epoch(value).map(Self::from_chrono)
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // chrono fields are within range
+    fn from_chrono(time: DateTime<Utc>) -> Self {
+        use chrono::{Datelike, Timelike};
+        Self::new(
+            time.year() as u16,
+            time.month() as u8,
+            time.day() as u8,
+            time.hour() as u8,
+            time.minute() as u8,
+            time.second() as u8,
+        )
+    }
+
     /// Parse from "YYYY-MM-DD" or "YYYY-MM-DDTHH:MM:SSZ" format
     pub fn parse(s: &str) -> Option<Self> {
         let bytes = s.as_bytes();
@@ -163,6 +215,61 @@ impl DateTimeUtc {
         )
     }
 
+    /// "2024-06-15T14:30:45Z"
+    pub fn to_rfc3339(self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// Format with strftime-like specifiers (`[build].date_format`).
+    ///
+    /// Supports `%Y %m %d %e %B %b %A %a %H %M %S %%`; month and weekday
+    /// names follow `language` (`en`, `de`, `fr`, `es`, `zh`, `ja`; English
+    /// otherwise). Unknown specifiers are kept as-is.
+    ///
+    /// ```ignore
+    /// let dt = DateTimeUtc::from_ymd(2024, 6, 5);
+    /// dt.format("%B %d, %Y", "en") // → "June 05, 2024"
+    /// dt.format("%e. %B %Y", "de") // → "5. Juni 2024"
+    /// ```
+    pub fn format(self, fmt: &str, language: &str) -> String {
+        let locale = Locale::for_language(language);
+        let month = (self.month - 1) as usize;
+        // weekday_index() starts at Saturday, locale names at Sunday
+        let weekday = (self.weekday_index() + 6) % 7;
+
+        let mut out = String::with_capacity(fmt.len() + 16);
+        let mut chars = fmt.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => out.push_str(&format!("{:04}", self.year)),
+                Some('m') => out.push_str(&format!("{:02}", self.month)),
+                Some('d') => out.push_str(&format!("{:02}", self.day)),
+                Some('e') => out.push_str(&self.day.to_string()),
+                Some('H') => out.push_str(&format!("{:02}", self.hour)),
+                Some('M') => out.push_str(&format!("{:02}", self.minute)),
+                Some('S') => out.push_str(&format!("{:02}", self.second)),
+                Some('B') => out.push_str(locale.months[month]),
+                Some('b') => out.push_str(locale.months_abbr[month]),
+                Some('A') => out.push_str(locale.weekdays[weekday]),
+                Some('a') => out.push_str(locale.weekdays_abbr[weekday]),
+                Some('%') => out.push('%'),
+                Some(other) => {
+                    out.push('%');
+                    out.push(other);
+                }
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+
     #[inline]
     #[allow(clippy::trivially_copy_pass_by_ref)] // Method style is more idiomatic
     #[allow(clippy::cast_sign_loss)] // Result of % 7 is always 0-6
@@ -177,6 +284,193 @@ impl DateTimeUtc {
     }
 }
 
+/// Month and weekday names (weekdays start at Sunday) for [`DateTimeUtc::format`].
+struct Locale {
+    months: [&'static str; 12],
+    months_abbr: [&'static str; 12],
+    weekdays: [&'static str; 7],
+    weekdays_abbr: [&'static str; 7],
+}
+
+impl Locale {
+    const EN: Self = Self {
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_abbr: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekdays: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        weekdays_abbr: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+    };
+
+    const DE: Self = Self {
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_abbr: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekdays: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        weekdays_abbr: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+    };
+
+    const FR: Self = Self {
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_abbr: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        weekdays_abbr: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+    };
+
+    const ES: Self = Self {
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        months_abbr: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        weekdays_abbr: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+    };
+
+    const ZH: Self = Self {
+        months: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        months_abbr: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        weekdays: [
+            "星期日",
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+        ],
+        weekdays_abbr: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+    };
+
+    const JA: Self = Self {
+        months: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        months_abbr: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        weekdays: [
+            "日曜日",
+            "月曜日",
+            "火曜日",
+            "水曜日",
+            "木曜日",
+            "金曜日",
+            "土曜日",
+        ],
+        weekdays_abbr: ["日", "月", "火", "水", "木", "金", "土"],
+    };
+
+    /// Locale for a language tag like `"de-AT"` or `"zh-Hans"` (English if unknown).
+    fn for_language(language: &str) -> &'static Self {
+        let primary = language.split(['-', '_']).next().unwrap_or_default();
+        match primary.to_ascii_lowercase().as_str() {
+            "de" => &Self::DE,
+            "fr" => &Self::FR,
+            "es" => &Self::ES,
+            "zh" => &Self::ZH,
+            "ja" => &Self::JA,
+            _ => &Self::EN,
+        }
+    }
+}
+
 /// Parse 2-digit ASCII number
 #[inline]
 fn parse_u8(bytes: &[u8]) -> Option<u8> {
@@ -365,4 +659,50 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_datetime_utc_ordering() {
+        let a = DateTimeUtc::parse("2024-06-15").unwrap();
+        let b = DateTimeUtc::parse("2024-06-15T00:00:01Z").unwrap();
+        let c = DateTimeUtc::parse("2025-01-01").unwrap();
+        assert!(a < b && b < c);
+        assert!(DateTimeUtc::now() > c);
+    }
+
+    #[test]
+    fn test_to_rfc3339() {
+        let dt = DateTimeUtc::new(2024, 6, 5, 9, 7, 3);
+        assert_eq!(dt.to_rfc3339(), "2024-06-05T09:07:03Z");
+        assert_eq!(DateTimeUtc::parse(&dt.to_rfc3339()), Some(dt));
+    }
+
+    #[test]
+    fn test_format() {
+        let dt = DateTimeUtc::new(2024, 6, 5, 9, 7, 3);
+        assert_eq!(dt.format("%Y-%m-%d", "en"), "2024-06-05");
+        assert_eq!(dt.format("%B %d, %Y", "en"), "June 05, 2024");
+        assert_eq!(
+            dt.format("%a %e %b %H:%M:%S", "en-US"),
+            "Wed 5 Jun 09:07:03"
+        );
+        assert_eq!(
+            dt.format("%A, %e. %B %Y", "de-AT"),
+            "Mittwoch, 5. Juni 2024"
+        );
+        assert_eq!(dt.format("%e %B %Y", "fr"), "5 juin 2024");
+        assert_eq!(
+            dt.format("%Y年%m月%d日 %A", "zh-Hans"),
+            "2024年06月05日 星期三"
+        );
+        assert_eq!(dt.format("100%% %q %", "xx"), "100% %q %");
+    }
+
+    #[test]
+    fn test_from_epoch() {
+        assert_eq!(
+            DateTimeUtc::from_epoch(" 1700000000\n"),
+            Some(DateTimeUtc::new(2023, 11, 14, 22, 13, 20))
+        );
+        assert_eq!(DateTimeUtc::from_epoch("yesterday"), None);
+    }
 }
@@ -0,0 +1,51 @@
+//! `:shortcode:` → emoji replacement for metadata text (`[build].emoji_shortcodes`).
+//!
+//! Uses the GitHub (gemoji) shortcode table bundled with the `emojis` crate.
+//! Only applied to feed text; page bodies are left to Typst.
+
+use std::borrow::Cow;
+
+/// Replace known `:shortcode:`s in `text` with their emoji.
+///
+/// Unknown shortcodes and stray colons (`12:30`) are left as-is.
+pub fn replace_shortcodes(text: &str) -> Cow<'_, str> {
+    if !text.contains(':') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(':') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let emoji = after
+            .find(':')
+            .and_then(|end| Some((end, emojis::get_by_shortcode(&after[..end])?)));
+        match emoji {
+            Some((end, emoji)) => {
+                out.push_str(emoji.as_str());
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push(':');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_shortcodes() {
+        assert_eq!(replace_shortcodes(":rocket: Launch"), "🚀 Launch");
+        assert_eq!(replace_shortcodes("Done :tada::tada:"), "Done 🎉🎉");
+        assert_eq!(replace_shortcodes("at 12:30 :tada:"), "at 12:30 🎉");
+        assert_eq!(replace_shortcodes(":not_an_emoji: x"), ":not_an_emoji: x");
+        assert!(matches!(replace_shortcodes("plain"), Cow::Borrowed(_)));
+    }
+}
@@ -13,6 +13,8 @@ use std::{
     path::Path,
     process::{Child, ChildStdin, Command, Output, Stdio},
     sync::OnceLock,
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
 // ============================================================================
@@ -239,6 +241,60 @@ fn exec_no_pty(
     Ok(output)
 }
 
+/// Execute a command without PTY, killing it once it runs longer than `timeout`.
+///
+/// Returns `Ok(None)` if the command was killed.
+pub fn exec_timeout(
+    root: Option<&Path>,
+    cmd: &[OsString],
+    args: &[OsString],
+    filter: &'static FilterRule,
+    timeout: Duration,
+) -> Result<Option<Output>> {
+    let deadline = Instant::now() + timeout;
+    let (name, mut command) = prepare(root, cmd, args)?;
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute `{name}`"))?;
+
+    // Drain both pipes while waiting, so the command never blocks on a full one
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let output = Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    };
+    log_output(&name, &output, filter)?;
+    Ok(Some(output))
+}
+
+/// Read a child pipe to the end on its own thread.
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
 /// Execute a command with PTY (pseudo-terminal) support.
 ///
 /// PTY allows commands to behave as if running in a real terminal, which is
@@ -487,7 +543,7 @@ fn prepare_pty(
 // Output Filtering
 // ============================================================================
 
-fn strip_ansi(s: &str) -> std::borrow::Cow<'_, str> {
+pub fn strip_ansi(s: &str) -> std::borrow::Cow<'_, str> {
     static RE: OnceLock<Regex> = OnceLock::new();
     let re = RE.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
     re.replace_all(s, "")
@@ -511,7 +567,7 @@ impl FilterRule {
     /// Check if output should be skipped entirely.
     ///
     /// Returns true if output is empty or starts with any of the skip prefixes.
-    fn should_skip(&self, output: &str) -> bool {
+    pub fn should_skip(&self, output: &str) -> bool {
         output.is_empty() || self.skip_prefixes.iter().any(|p| output.starts_with(p))
     }
 
@@ -674,6 +730,29 @@ mod tests {
         assert!(msg.contains("Command `test` failed"));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_exec_timeout_kills_command() {
+        let run = |script: &str| {
+            let cmd = [OsString::from("sh")];
+            let args = [OsString::from("-c"), OsString::from(script)];
+            exec_timeout(
+                None,
+                &cmd,
+                &args,
+                &SILENT_FILTER,
+                Duration::from_millis(200),
+            )
+        };
+
+        let output = run("echo hi").unwrap().unwrap();
+        assert_eq!(output.stdout, b"hi\n");
+
+        let start = Instant::now();
+        assert!(run("sleep 10").unwrap().is_none());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
     #[test]
     fn test_strip_ansi() {
         // Basic colors
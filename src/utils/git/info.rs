@@ -0,0 +1,104 @@
+//! Commit metadata of the project repository (for `/_data/git.json`).
+
+use chrono::{FixedOffset, TimeZone};
+use gix::bstr::ByteSlice;
+use serde::Serialize;
+use std::path::Path;
+
+/// HEAD commit of the project repository, exposed in `/_data/git.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct GitInfo {
+    /// Full commit hash
+    pub hash: String,
+
+    /// Abbreviated commit hash (7 characters)
+    pub short_hash: String,
+
+    /// Commit date as RFC 3339 string (committer time, with its offset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+
+    /// Current branch name (`None` for detached HEAD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
+/// Read HEAD commit info from the repository containing `root`.
+///
+/// Returns `None` if `root` is not inside a git repository or has no commits.
+pub fn read_git_info(root: &Path) -> Option<GitInfo> {
+    let repo = gix::discover(root).ok()?;
+    let commit = repo.head_commit().ok()?;
+
+    let hash = commit.id().to_string();
+    let short_hash = hash.chars().take(7).collect();
+    let date = commit.time().ok().and_then(|time| {
+        let offset = FixedOffset::east_opt(time.offset)?;
+        let date = offset.timestamp_opt(time.seconds, 0).single()?;
+        Some(date.to_rfc3339())
+    });
+    let branch = repo
+        .head_name()
+        .ok()
+        .flatten()
+        .map(|name| name.shorten().to_str_lossy().into_owned());
+
+    Some(GitInfo {
+        hash,
+        short_hash,
+        date,
+        branch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::git::create_repo;
+    use gix::date::parse::TimeBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_git_info() {
+        let dir = TempDir::new().unwrap();
+        let repo = create_repo(dir.path()).unwrap();
+        assert!(read_git_info(dir.path()).is_none(), "no commits yet");
+
+        // Explicit signature, so no git identity needs to be configured
+        let local = repo.to_thread_local();
+        let signature = gix::actor::Signature {
+            name: "Tola Test".into(),
+            email: "test@example.com".into(),
+            time: gix::date::Time::new(1_700_000_000, 3600),
+        };
+        let (mut author_time, mut committer_time) = (TimeBuf::default(), TimeBuf::default());
+        let tree = local.write_object(gix::objs::Tree::empty()).unwrap();
+        local
+            .commit_as(
+                signature.to_ref(&mut committer_time),
+                signature.to_ref(&mut author_time),
+                "HEAD",
+                "init",
+                tree,
+                gix::commit::NO_PARENT_IDS,
+            )
+            .unwrap();
+
+        // Discovered from a subdirectory as well
+        let sub = dir.path().join("content");
+        std::fs::create_dir(&sub).unwrap();
+        let info = read_git_info(&sub).unwrap();
+
+        let head = repo.to_thread_local().head_id().unwrap().to_string();
+        assert_eq!(info.hash, head);
+        assert_eq!(info.short_hash, &head[..7]);
+        assert_eq!(info.date.as_deref(), Some("2023-11-14T23:13:20+01:00"));
+        assert!(info.branch.is_some_and(|b| !b.is_empty()));
+    }
+
+    #[test]
+    fn test_read_git_info_not_a_repo() {
+        let dir = TempDir::new().unwrap();
+        assert!(read_git_info(dir.path()).is_none());
+    }
+}
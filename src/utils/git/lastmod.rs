@@ -0,0 +1,200 @@
+//! Last-commit date per file (for `[build.sitemap].lastmod = "git"`).
+//!
+//! File mtimes reset on checkout, so CI builds would report every page as
+//! modified today. Instead, first-parent history is walked once from HEAD,
+//! recording for each path the newest commit that changed it.
+//!
+//! The walk is cached and only redone when HEAD moves, so lookups are O(1)
+//! rather than O(files × history).
+
+use gix::bstr::ByteSlice;
+use parking_lot::RwLock;
+use rustc_hash::FxHashMap;
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Commit times of the project repository, computed by [`load_commit_times`].
+static COMMIT_TIMES: LazyLock<RwLock<Option<CommitTimes>>> = LazyLock::new(Default::default);
+
+/// Last-commit time of every path in a repository's history.
+#[derive(Debug)]
+pub struct CommitTimes {
+    /// HEAD the history was walked from
+    head: gix::ObjectId,
+    /// Canonical working directory (paths are relative to it)
+    workdir: PathBuf,
+    /// Repo-relative path (`/`-separated) → commit time (seconds since epoch)
+    times: FxHashMap<String, i64>,
+}
+
+impl CommitTimes {
+    /// Walk the history of the repository containing `root`.
+    ///
+    /// Returns `None` outside a git repository or without commits.
+    pub fn collect(root: &Path) -> Option<Self> {
+        let repo = gix::discover(root).ok()?;
+        let head = repo.head_id().ok()?.detach();
+        let workdir = repo.workdir()?.canonicalize().ok()?;
+
+        let mut options = gix::diff::Options::default().with_rewrites(None);
+        options.track_path();
+
+        let mut times = FxHashMap::default();
+        let walk = repo.rev_walk([head]).first_parent_only().all().ok()?;
+        for info in walk {
+            let Some(commit) = info.ok().and_then(|info| info.object().ok()) else {
+                break;
+            };
+            let (Ok(time), Ok(tree)) = (commit.time(), commit.tree()) else {
+                continue;
+            };
+            // Root commit is diffed against the empty tree
+            let parent_tree = commit
+                .parent_ids()
+                .next()
+                .and_then(|id| id.object().ok()?.peel_to_tree().ok());
+
+            let Ok(changes) = repo.diff_tree_to_tree(parent_tree.as_ref(), &tree, options) else {
+                continue;
+            };
+            for change in changes {
+                let path = change.location().to_str_lossy().into_owned();
+                // Newest commit wins: history is walked newest-first
+                times.entry(path).or_insert(time.seconds);
+            }
+        }
+
+        Some(Self {
+            head,
+            workdir,
+            times,
+        })
+    }
+
+    /// Last-commit time of `path`, or `None` if it was never committed.
+    pub fn get(&self, path: &Path) -> Option<SystemTime> {
+        let path = path.canonicalize().ok()?;
+        let rel = path.strip_prefix(&self.workdir).ok()?;
+        let key = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let seconds = u64::try_from(*self.times.get(&key)?).ok()?;
+        Some(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+}
+
+/// Load (or reuse, if HEAD is unchanged) the commit times for `root`.
+pub fn load_commit_times(root: &Path) {
+    if let Some(cached) = COMMIT_TIMES.read().as_ref()
+        && gix::discover(root)
+            .ok()
+            .and_then(|repo| repo.head_id().ok().map(|id| id.detach()))
+            == Some(cached.head)
+    {
+        return;
+    }
+    *COMMIT_TIMES.write() = CommitTimes::collect(root);
+}
+
+/// Last-commit time of `path` from the loaded history.
+///
+/// Returns `None` if untracked, or if [`load_commit_times`] found no repository.
+pub fn last_commit_time(path: &Path) -> Option<SystemTime> {
+    COMMIT_TIMES.read().as_ref()?.get(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gix::actor::SignatureRef;
+    use gix::objs::{Tree, tree::Entry, tree::EntryKind};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Commit `content/<name>` files at a fixed time, on top of HEAD.
+    fn commit_at(repo: &gix::Repository, files: &[(&str, &str)], time: &str) {
+        let mut entries: Vec<Entry> = files
+            .iter()
+            .map(|(name, data)| Entry {
+                mode: EntryKind::Blob.into(),
+                filename: (*name).into(),
+                oid: repo.write_blob(data).unwrap().detach(),
+            })
+            .collect();
+        entries.sort();
+        let content = repo.write_object(&Tree { entries }).unwrap().detach();
+        let root = Tree {
+            entries: vec![Entry {
+                mode: EntryKind::Tree.into(),
+                filename: "content".into(),
+                oid: content,
+            }],
+        };
+        let tree = repo.write_object(&root).unwrap().detach();
+
+        let sig = SignatureRef {
+            name: "tola".into(),
+            email: "tola@example.com".into(),
+            time,
+        };
+        let parents: Vec<_> = repo
+            .head_id()
+            .ok()
+            .map(|id| id.detach())
+            .into_iter()
+            .collect();
+        repo.commit_as(sig, sig, "HEAD", "commit", tree, parents)
+            .unwrap();
+    }
+
+    fn ymd(time: SystemTime) -> String {
+        let secs = time.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        chrono::DateTime::from_timestamp(secs.cast_signed(), 0)
+            .unwrap()
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    #[test]
+    fn test_last_commit_per_file() {
+        let dir = TempDir::new().unwrap();
+        let repo = gix::init(dir.path()).unwrap();
+
+        // 2024-01-01: a + b; 2024-03-01: only b changes
+        commit_at(&repo, &[("a.typ", "a"), ("b.typ", "b")], "1704067200 +0000");
+        commit_at(
+            &repo,
+            &[("a.typ", "a"), ("b.typ", "b2")],
+            "1709251200 +0000",
+        );
+
+        let content = dir.path().join("content");
+        fs::create_dir_all(&content).unwrap();
+        for name in ["a.typ", "b.typ", "new.typ"] {
+            fs::write(content.join(name), "").unwrap();
+        }
+
+        let times = CommitTimes::collect(dir.path()).unwrap();
+        assert_eq!(
+            ymd(times.get(&content.join("a.typ")).unwrap()),
+            "2024-01-01"
+        );
+        assert_eq!(
+            ymd(times.get(&content.join("b.typ")).unwrap()),
+            "2024-03-01"
+        );
+        // Untracked: caller falls back to mtime
+        assert!(times.get(&content.join("new.typ")).is_none());
+    }
+
+    #[test]
+    fn test_not_a_repo() {
+        let dir = TempDir::new().unwrap();
+        assert!(CommitTimes::collect(dir.path()).is_none());
+    }
+}
@@ -1,11 +1,16 @@
 //! Git operations for the static site generator.
 //!
-//! Handles repository initialization, commits, and remote pushing.
+//! Handles repository initialization, commits, remote pushing, and reading
+//! HEAD commit info of the project repository.
 
 mod ignore;
+mod info;
+mod lastmod;
 mod remote;
 mod repo;
 mod tree;
 
+pub use info::{GitInfo, read_git_info};
+pub use lastmod::{last_commit_time, load_commit_times};
 pub use remote::push;
 pub use repo::{commit_all, create_repo, open_repo};
@@ -3,10 +3,12 @@
 pub mod category;
 pub mod css;
 pub mod date;
+pub mod emoji;
 pub mod exec;
 pub mod git;
 pub mod hash;
 pub mod minify;
 pub mod slug;
 pub mod svg;
+pub mod typst_element;
 pub mod xml;
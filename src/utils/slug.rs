@@ -32,6 +32,7 @@
 
 use crate::config::{SiteConfig, SlugCase, SlugMode};
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// Characters that are unsafe for URLs and file paths.
@@ -58,15 +59,19 @@ pub const FORBIDDEN_CHARS: &[char] = &[
 /// slugify_fragment("Hello World") // → "hello-world"
 /// slugify_fragment("Chapter:One") // → "Chapter-One"
 /// ```
+///
+/// With `SlugMode::No` the text is returned unchanged, ignoring `slug.case`.
 pub fn slugify_fragment(text: &str, config: &SiteConfig) -> String {
     let slug = &config.build.slug;
     let sep = slug.separator.as_char();
 
+    let overrides = &slug.transliteration;
+
     let result = match slug.fragment {
         SlugMode::No => return text.to_owned(),
-        SlugMode::Full => slugify_full(text, sep),
+        SlugMode::Full => slugify_full(text, sep, overrides),
         SlugMode::Safe => sanitize(text, sep),
-        SlugMode::Ascii => sanitize(&deunicode::deunicode(text), sep),
+        SlugMode::Ascii => sanitize(&to_ascii(text, overrides), sep),
     };
 
     apply_case(&result, &slug.case).into_owned()
@@ -89,13 +94,15 @@ pub fn slugify_fragment(text: &str, config: &SiteConfig) -> String {
 pub fn slugify_path(path: impl AsRef<Path>, config: &SiteConfig) -> PathBuf {
     let slug = &config.build.slug;
     let sep = slug.separator.as_char();
+    let path = path.as_ref();
+    let overrides = &slug.transliteration;
 
     match slug.path {
-        SlugMode::No => path.as_ref().to_path_buf(),
+        SlugMode::No => path.to_path_buf(),
         // Full mode: process each component with full slugification (ASCII + lowercase)
-        SlugMode::Full => transform_path_components_full(path.as_ref(), sep),
-        SlugMode::Safe => transform_path_components(path.as_ref(), sep, &slug.case, false),
-        SlugMode::Ascii => transform_path_components(path.as_ref(), sep, &slug.case, true),
+        SlugMode::Full => transform_path_components_full(path, sep, overrides),
+        SlugMode::Safe => transform_path_components(path, sep, &slug.case, None),
+        SlugMode::Ascii => transform_path_components(path, sep, &slug.case, Some(overrides)),
     }
 }
 
@@ -109,7 +116,7 @@ pub fn slugify_path(path: impl AsRef<Path>, config: &SiteConfig) -> PathBuf {
 /// Always produces lowercase output regardless of case settings.
 ///
 /// # Processing Steps
-/// 1. Transliterate Unicode to ASCII (via `deunicode`)
+/// 1. Transliterate Unicode to ASCII (overrides, then `deunicode`)
 /// 2. Convert to lowercase
 /// 3. Replace forbidden chars and whitespace with separator
 /// 4. Collapse consecutive separators
@@ -117,13 +124,13 @@ pub fn slugify_path(path: impl AsRef<Path>, config: &SiteConfig) -> PathBuf {
 ///
 /// # Examples
 /// ```ignore
-/// slugify_full("Hello World", '-')  // → "hello-world"
-/// slugify_full("München", '-')      // → "munchen"
-/// slugify_full("Café Naïve", '-')   // → "cafe-naive"
-/// slugify_full("a:::b", '-')        // → "a-b"
+/// slugify_full("Hello World", '-', &none)  // → "hello-world"
+/// slugify_full("München", '-', &none)      // → "munchen"
+/// slugify_full("Café Naïve", '-', &none)   // → "cafe-naive"
+/// slugify_full("a:::b", '-', &none)        // → "a-b"
 /// ```
-fn slugify_full(text: &str, sep: char) -> String {
-    let ascii = deunicode::deunicode(text);
+fn slugify_full(text: &str, sep: char, overrides: &BTreeMap<String, String>) -> String {
+    let ascii = to_ascii(text, overrides);
     let replaced = replace_special_chars(&ascii.to_lowercase(), sep);
     collapse_consecutive_separators(&replaced, sep)
 }
@@ -154,9 +161,13 @@ fn sanitize(text: &str, sep: char) -> String {
 ///
 /// Applies `slugify_full` to each path component individually,
 /// preserving the directory structure while fully slugifying each part.
-fn transform_path_components_full(path: &Path, sep: char) -> PathBuf {
+fn transform_path_components_full(
+    path: &Path,
+    sep: char,
+    overrides: &BTreeMap<String, String>,
+) -> PathBuf {
     path.components()
-        .map(|component| slugify_full(&component.as_os_str().to_string_lossy(), sep))
+        .map(|component| slugify_full(&component.as_os_str().to_string_lossy(), sep, overrides))
         .collect()
 }
 
@@ -166,15 +177,19 @@ fn transform_path_components_full(path: &Path, sep: char) -> PathBuf {
 /// * `path` - The path to transform
 /// * `sep` - Separator character
 /// * `case` - Case transformation to apply
-/// * `to_ascii` - Whether to transliterate Unicode to ASCII
-fn transform_path_components(path: &Path, sep: char, case: &SlugCase, to_ascii: bool) -> PathBuf {
+/// * `ascii` - Transliterate Unicode to ASCII, with these overrides
+fn transform_path_components(
+    path: &Path,
+    sep: char,
+    case: &SlugCase,
+    ascii: Option<&BTreeMap<String, String>>,
+) -> PathBuf {
     path.components()
         .map(|component| {
             let text = component.as_os_str().to_string_lossy();
-            let sanitized = if to_ascii {
-                sanitize(&deunicode::deunicode(&text), sep)
-            } else {
-                sanitize(&text, sep)
+            let sanitized = match ascii {
+                Some(overrides) => sanitize(&to_ascii(&text, overrides), sep),
+                None => sanitize(&text, sep),
             };
             apply_case(&sanitized, case).into_owned()
         })
@@ -185,6 +200,43 @@ fn transform_path_components(path: &Path, sep: char, case: &SlugCase, to_ascii:
 // Helper Functions
 // ============================================================================
 
+/// Transliterates Unicode to ASCII, applying `[build.slug].transliteration` first.
+///
+/// At each position the longest matching key is replaced; the rest goes
+/// through `deunicode`.
+///
+/// # Examples
+/// ```ignore
+/// // With { "ü" = "ue" }
+/// to_ascii("Müller", &overrides) // → "Mueller"
+/// to_ascii("Müller", &none)      // → "Muller"
+/// ```
+fn to_ascii(text: &str, overrides: &BTreeMap<String, String>) -> String {
+    if overrides.is_empty() {
+        return deunicode::deunicode(text);
+    }
+
+    let mut replaced = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let matched = overrides
+            .iter()
+            .filter(|(key, _)| !key.is_empty() && rest.starts_with(key.as_str()))
+            .max_by_key(|(key, _)| key.len());
+        match matched {
+            Some((key, value)) => {
+                replaced.push_str(value);
+                rest = &rest[key.len()..];
+            }
+            None => {
+                replaced.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    deunicode::deunicode(&replaced)
+}
+
 /// Replaces forbidden characters and whitespace with the separator.
 #[inline]
 fn replace_special_chars(text: &str, sep: char) -> String {
@@ -286,6 +338,7 @@ mod tests {
     const SEP_UNDERSCORE: char = '_';
     const SEP_DASH: char = '-';
     const CASE: SlugCase = SlugCase::Preserve;
+    const NO_OVERRIDES: BTreeMap<String, String> = BTreeMap::new();
 
     // ========================================================================
     // sanitize() tests
@@ -461,91 +514,93 @@ mod tests {
     #[test]
     fn test_transform_path_simple() {
         let path = Path::new("content/posts/hello-world");
-        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, false);
+        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, None);
         assert_eq!(result, PathBuf::from("content/posts/hello-world"));
     }
 
     #[test]
     fn test_transform_path_with_forbidden_chars() {
         let path = Path::new("content/posts/hello<world>");
-        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, false);
+        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, None);
         assert_eq!(result, PathBuf::from("content/posts/hello_world"));
     }
 
     #[test]
     fn test_transform_path_with_spaces() {
         let path = Path::new("content/my posts/hello world");
-        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, false);
+        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, None);
         assert_eq!(result, PathBuf::from("content/my_posts/hello_world"));
     }
 
     #[test]
     fn test_transform_path_unicode() {
         let path = Path::new("content/Artikel/Café");
-        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, false);
+        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, None);
         assert_eq!(result, PathBuf::from("content/Artikel/Café"));
     }
 
     #[test]
     fn test_transform_path_unicode_with_forbidden() {
         let path = Path::new("content/Artikel#1/Café[World]");
-        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, false);
+        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, None);
         assert_eq!(result, PathBuf::from("content/Artikel_1/Café_World"));
     }
 
     #[test]
     fn test_transform_path_mixed_unicode() {
         let path = Path::new("posts/2024年/第一篇 文章");
-        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, false);
+        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, None);
         assert_eq!(result, PathBuf::from("posts/2024年/第一篇_文章"));
     }
 
     #[test]
     fn test_transform_path_japanese() {
         let path = Path::new("ブログ/記事/こんにちは");
-        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, false);
+        let result = transform_path_components(path, SEP_UNDERSCORE, &CASE, None);
         assert_eq!(result, PathBuf::from("ブログ/記事/こんにちは"));
     }
 
     #[test]
     fn test_transform_path_with_hyphen_separator() {
         let path = Path::new("content/my posts/hello world");
-        let result = transform_path_components(path, SEP_DASH, &CASE, false);
+        let result = transform_path_components(path, SEP_DASH, &CASE, None);
         assert_eq!(result, PathBuf::from("content/my-posts/hello-world"));
     }
 
     #[test]
     fn test_transform_path_ascii_mode() {
         let path = Path::new("content/Artikel/Café");
-        let result = transform_path_components(path, SEP_DASH, &SlugCase::Preserve, true);
+        let result =
+            transform_path_components(path, SEP_DASH, &SlugCase::Preserve, Some(&NO_OVERRIDES));
         assert_eq!(result, PathBuf::from("content/Artikel/Cafe"));
     }
 
     #[test]
     fn test_transform_path_ascii_with_case_lower() {
         let path = Path::new("content/Artikel/Café");
-        let result = transform_path_components(path, SEP_DASH, &SlugCase::Lower, true);
+        let result =
+            transform_path_components(path, SEP_DASH, &SlugCase::Lower, Some(&NO_OVERRIDES));
         assert_eq!(result, PathBuf::from("content/artikel/cafe"));
     }
 
     #[test]
     fn test_transform_path_with_case_lower() {
         let path = Path::new("Content/Posts/Hello World");
-        let result = transform_path_components(path, SEP_DASH, &SlugCase::Lower, false);
+        let result = transform_path_components(path, SEP_DASH, &SlugCase::Lower, None);
         assert_eq!(result, PathBuf::from("content/posts/hello-world"));
     }
 
     #[test]
     fn test_transform_path_with_case_upper() {
         let path = Path::new("content/posts/hello world");
-        let result = transform_path_components(path, SEP_DASH, &SlugCase::Upper, false);
+        let result = transform_path_components(path, SEP_DASH, &SlugCase::Upper, None);
         assert_eq!(result, PathBuf::from("CONTENT/POSTS/HELLO-WORLD"));
     }
 
     #[test]
     fn test_transform_path_with_case_capitalize() {
         let path = Path::new("content/posts/hello world");
-        let result = transform_path_components(path, SEP_DASH, &SlugCase::Capitalize, false);
+        let result = transform_path_components(path, SEP_DASH, &SlugCase::Capitalize, None);
         assert_eq!(result, PathBuf::from("Content/Posts/Hello-World"));
     }
 
@@ -555,27 +610,36 @@ mod tests {
 
     #[test]
     fn test_slugify_full_basic() {
-        assert_eq!(slugify_full("Hello World", SEP_DASH), "hello-world");
-        assert_eq!(slugify_full("Hello World", SEP_UNDERSCORE), "hello_world");
+        assert_eq!(
+            slugify_full("Hello World", SEP_DASH, &NO_OVERRIDES),
+            "hello-world"
+        );
+        assert_eq!(
+            slugify_full("Hello World", SEP_UNDERSCORE, &NO_OVERRIDES),
+            "hello_world"
+        );
     }
 
     #[test]
     fn test_slugify_full_unicode_to_ascii() {
         // Unicode → ASCII
-        assert_eq!(slugify_full("München", SEP_DASH), "munchen");
-        assert_eq!(slugify_full("Åland", SEP_DASH), "aland");
+        assert_eq!(slugify_full("München", SEP_DASH, &NO_OVERRIDES), "munchen");
+        assert_eq!(slugify_full("Åland", SEP_DASH, &NO_OVERRIDES), "aland");
 
         // European accents → ASCII
-        assert_eq!(slugify_full("café", SEP_DASH), "cafe");
-        assert_eq!(slugify_full("über", SEP_DASH), "uber");
-        assert_eq!(slugify_full("naïve", SEP_DASH), "naive");
+        assert_eq!(slugify_full("café", SEP_DASH, &NO_OVERRIDES), "cafe");
+        assert_eq!(slugify_full("über", SEP_DASH, &NO_OVERRIDES), "uber");
+        assert_eq!(slugify_full("naïve", SEP_DASH, &NO_OVERRIDES), "naive");
     }
 
     #[test]
     fn test_slugify_full_mixed() {
-        assert_eq!(slugify_full("Hello München", SEP_DASH), "hello-munchen");
+        assert_eq!(
+            slugify_full("Hello München", SEP_DASH, &NO_OVERRIDES),
+            "hello-munchen"
+        );
         // Note: 2024år → "2024ar"
-        assert_eq!(slugify_full("2024år", SEP_DASH), "2024ar");
+        assert_eq!(slugify_full("2024år", SEP_DASH, &NO_OVERRIDES), "2024ar");
     }
 
     // ========================================================================
@@ -695,6 +759,37 @@ mod tests {
         assert_eq!(slugify_fragment("Hello World", &config), "Hello World");
     }
 
+    #[test]
+    fn test_transliteration_overrides() {
+        let mut config = make_config("full", "full", "lower", SEP_DASH);
+        assert_eq!(slugify_fragment("Müller", &config), "muller");
+
+        config.build.slug.transliteration = [("ä", "ae"), ("ö", "oe"), ("ü", "ue"), ("ß", "ss")]
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .into();
+        assert_eq!(slugify_fragment("Müller", &config), "mueller");
+        assert_eq!(
+            slugify_path("Straße/Müller", &config),
+            PathBuf::from("strasse/mueller")
+        );
+
+        config.build.slug.path = SlugMode::Ascii;
+        config.build.slug.case = SlugCase::Preserve;
+        assert_eq!(slugify_path("Müller", &config), PathBuf::from("Mueller"));
+        // Safe mode keeps Unicode, so no transliteration
+        config.build.slug.path = SlugMode::Safe;
+        assert_eq!(slugify_path("Müller", &config), PathBuf::from("Müller"));
+    }
+
+    #[test]
+    fn test_to_ascii_longest_key_wins() {
+        let overrides = [("a", "1"), ("ab", "2")]
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .into();
+        assert_eq!(to_ascii("abac", &overrides), "21c");
+        assert_eq!(to_ascii("café", &NO_OVERRIDES), "cafe");
+    }
+
     #[test]
     fn test_slugify_path_modes() {
         // Full mode
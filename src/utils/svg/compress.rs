@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use parking_lot::{Condvar, Mutex};
 use rayon::prelude::*;
 use std::fs;
 use std::io::Write;
@@ -9,6 +10,53 @@ use super::{OutputFormat, Svg};
 use crate::config::{ExtractSvgType, SiteConfig};
 use crate::{exec_with_stdin, log};
 
+/// Slots for `magick`/`ffmpeg` processes, shared by every page being written.
+static CONVERT_SLOTS: ConvertSlots = ConvertSlots::new();
+
+/// Counting semaphore bounding concurrent external conversions.
+///
+/// The limit is passed on acquire, so a reloaded `convert_jobs` applies to
+/// the next conversion.
+struct ConvertSlots {
+    running: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl ConvertSlots {
+    const fn new() -> Self {
+        Self {
+            running: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Block until fewer than `limit` conversions are running, then take a slot.
+    fn acquire(&self, limit: usize) -> ConvertPermit<'_> {
+        let mut running = self.running.lock();
+        while *running >= limit.max(1) {
+            self.freed.wait(&mut running);
+        }
+        *running += 1;
+        ConvertPermit(self)
+    }
+}
+
+/// A taken slot, released on drop.
+struct ConvertPermit<'a>(&'a ConvertSlots);
+
+impl Drop for ConvertPermit<'_> {
+    fn drop(&mut self) {
+        *self.0.running.lock() -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+/// Run an external conversion once one of `[build.typst.svg].convert_jobs` slots is free.
+fn with_convert_slot<T>(config: &SiteConfig, convert: impl FnOnce() -> T) -> T {
+    let _permit = CONVERT_SLOTS.acquire(config.build.typst.svg.convert_jobs);
+    convert()
+}
+
 /// Compress multiple SVGs in parallel
 pub fn compress_svgs_parallel(svgs: &[Svg], html_path: &Path, config: &SiteConfig) -> Result<()> {
     let output_dir = html_path.parent().context("Invalid html path")?;
@@ -21,7 +69,7 @@ pub fn compress_svgs_parallel(svgs: &[Svg], html_path: &Path, config: &SiteConfi
     svgs.par_iter().try_for_each(|svg| {
         let output_path = output_dir.join(svg.filename(config));
 
-        if should_skip_compression(&output_path, html_mtime) {
+        if !config.build.force_rebuild && should_skip_compression(&output_path, html_mtime) {
             return Ok(());
         }
 
@@ -37,7 +85,9 @@ fn get_log_prefix(html_path: &Path, config: &SiteConfig) -> String {
         .strip_prefix(&config.build.output)
         .map(|p| p.to_string_lossy())
         .unwrap_or_default();
-    rel_path.trim_end_matches("index.html").to_string()
+    rel_path
+        .trim_end_matches(config.build.index_filename().as_str())
+        .to_string()
 }
 
 fn should_skip_compression(output_path: &Path, html_mtime: Option<SystemTime>) -> bool {
@@ -53,19 +103,44 @@ fn should_skip_compression(output_path: &Path, html_mtime: Option<SystemTime>) -
 /// Compress a single SVG based on configuration
 fn compress_svg(svg: &Svg, output_path: &Path, scale: f32, config: &SiteConfig) -> Result<()> {
     // Small SVGs or JustSvg mode: write as-is
-    if svg.output_format(config) == OutputFormat::Svg {
+    if matches!(
+        svg.output_format(config),
+        OutputFormat::Inline | OutputFormat::Svg
+    ) {
         return fs::write(output_path, &svg.data).map_err(Into::into);
     }
 
     // Compress to AVIF using configured backend
-    match &config.build.typst.svg.extract_type {
+    let extract_type = &config.build.typst.svg.extract_type;
+    match extract_type {
         ExtractSvgType::Embedded | ExtractSvgType::JustSvg => {
             // Already handled above
-            Ok(())
+            return Ok(());
         }
-        ExtractSvgType::Magick => compress_magick(output_path, &svg.data, scale),
-        ExtractSvgType::Ffmpeg => compress_ffmpeg(output_path, &svg.data),
-        ExtractSvgType::Builtin => compress_builtin(output_path, &svg.data, svg.size, scale),
+        ExtractSvgType::Magick => {
+            with_convert_slot(config, || compress_magick(output_path, &svg.data, scale))?;
+        }
+        ExtractSvgType::Ffmpeg => {
+            with_convert_slot(config, || compress_ffmpeg(output_path, &svg.data))?;
+        }
+        ExtractSvgType::Builtin => compress_builtin(output_path, &svg.data, svg.size, scale)?,
+    }
+
+    // PNG fallback for `<picture>`, with the same backend
+    let Some(fallback) = svg.fallback_filename(config) else {
+        return Ok(());
+    };
+    let fallback = output_path.with_file_name(fallback);
+    match extract_type {
+        ExtractSvgType::Embedded | ExtractSvgType::JustSvg => Ok(()),
+        // ImageMagick picks the format from the extension
+        ExtractSvgType::Magick => {
+            with_convert_slot(config, || compress_magick(&fallback, &svg.data, scale))
+        }
+        ExtractSvgType::Ffmpeg => {
+            with_convert_slot(config, || fallback_ffmpeg(&fallback, &svg.data))
+        }
+        ExtractSvgType::Builtin => fallback_builtin(&fallback, &svg.data, svg.size, scale),
     }
 }
 
@@ -108,10 +183,31 @@ fn compress_ffmpeg(output: &Path, data: &[u8]) -> Result<()> {
     proc.wait()
 }
 
-/// Compress using built-in ravif encoder
+/// Encode a PNG fallback using `FFmpeg`
+fn fallback_ffmpeg(output: &Path, data: &[u8]) -> Result<()> {
+    let mut proc = exec_with_stdin!(
+        ["ffmpeg"];
+        "-f", "svg_pipe",
+        "-frame_size", "1000000000",
+        "-i", "pipe:",
+        "-frames:v", "1",
+        "-y", output
+    )?;
+    if let Some(stdin) = proc.stdin() {
+        stdin.write_all(data)?;
+    }
+    proc.wait()
+}
+
+/// Raster dimensions (width, height) of an SVG at `scale`
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // Dimensions are always positive
+fn raster_size(size: (f32, f32), scale: f32) -> (usize, usize) {
+    ((size.0 * scale) as usize, (size.1 * scale) as usize)
+}
+
+/// Compress using built-in ravif encoder
 fn compress_builtin(output: &Path, data: &[u8], size: (f32, f32), scale: f32) -> Result<()> {
-    let (width, height) = ((size.0 * scale) as usize, (size.1 * scale) as usize);
+    let (width, height) = raster_size(size, scale);
     let pixel_count = width * height;
 
     // Pre-allocate with exact capacity
@@ -131,11 +227,94 @@ fn compress_builtin(output: &Path, data: &[u8], size: (f32, f32), scale: f32) ->
     Ok(())
 }
 
+/// Encode a PNG fallback using the `image` crate
+#[allow(clippy::cast_possible_truncation)] // Raster dimensions fit in u32
+fn fallback_builtin(output: &Path, data: &[u8], size: (f32, f32), scale: f32) -> Result<()> {
+    let (width, height) = raster_size(size, scale);
+    let pixels = data
+        .get(..width * height * 4)
+        .context("SVG data smaller than its raster size")?;
+    image::save_buffer(
+        output,
+        pixels,
+        width as u32,
+        height as u32,
+        image::ExtendedColorType::Rgba8,
+    )?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_picture_fallback_writes_both_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().to_path_buf();
+        config.build.typst.svg.extract_type = ExtractSvgType::Builtin;
+        config.build.typst.svg.inline_max_size = "0B".into();
+        config.build.typst.svg.picture_fallback = true;
+
+        let svg = Svg::new(vec![128; 4 * 4 * 4], (4.0, 4.0), 0);
+        let html_path = dir.path().join("index.html");
+        compress_svgs_parallel(&[svg], &html_path, &config).unwrap();
+
+        assert!(dir.path().join("svg-0.avif").is_file());
+        let png = image::open(dir.path().join("svg-0.png")).unwrap();
+        assert_eq!((png.width(), png.height()), (4, 4));
+    }
+
+    #[test]
+    fn test_external_svg_written_uncompressed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().to_path_buf();
+        config.build.typst.svg.extract_type = ExtractSvgType::JustSvg;
+
+        let data = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>".to_vec();
+        let svg = Svg::new(data.clone(), (1.0, 1.0), 0);
+        compress_svgs_parallel(&[svg], &dir.path().join("index.html"), &config).unwrap();
+
+        let files: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(files, ["svg-0.svg"]);
+        assert_eq!(fs::read(dir.path().join("svg-0.svg")).unwrap(), data);
+    }
+
+    #[test]
+    fn test_convert_jobs_caps_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let mut config = SiteConfig::default();
+        config.build.typst.svg.convert_jobs = 2;
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        // Stand-in for a `magick` process: count while "running"
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(8)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            (0..32).into_par_iter().for_each(|_| {
+                with_convert_slot(&config, || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(5));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                });
+            });
+        });
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
     #[test]
     fn test_get_log_prefix() {
         let mut config = SiteConfig::default();
@@ -146,5 +325,9 @@ mod tests {
 
         let path = PathBuf::from("public/index.html");
         assert_eq!(get_log_prefix(&path, &config), "");
+
+        config.build.page_extension = "htm".to_string();
+        let path = PathBuf::from("public/blog/post/index.htm");
+        assert_eq!(get_log_prefix(&path, &config), "blog/post/");
     }
 }
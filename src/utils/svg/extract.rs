@@ -1,16 +1,21 @@
 use anyhow::{Context, Result};
 use quick_xml::events::attributes::Attribute;
-use quick_xml::events::{BytesStart, Event};
+use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::{Reader, Writer};
-use std::io::Cursor;
+use std::io::{Cursor, Write};
 
 use super::optimize::optimize_svg;
 use super::transform::transform_svg_attrs;
 use crate::compiler::meta::url_from_output_path;
 use crate::config::SiteConfig;
+use crate::utils::hash;
 use crate::utils::svg::{HtmlContext, INITIAL_SVG_BUFFER_SIZE, Svg};
 
 /// Extract and optimize an SVG element, writing an img placeholder to the output
+///
+/// Returns `None` when nothing needs writing: under `[build.typst.svg].inline`
+/// SVGs below `inline_max_size` stay in the HTML, and under `[build.typst.svg].dedupe` a duplicate's
+/// placeholder points at the earlier identical SVG, which is written once.
 pub fn extract_svg_element(
     reader: &mut Reader<&[u8]>,
     writer: &mut Writer<Cursor<Vec<u8>>>,
@@ -20,6 +25,31 @@ pub fn extract_svg_element(
     // Process SVG data (transform, capture, optimize)
     let (optimized_data, size) = process_svg_data(reader, elem, ctx.config)?;
 
+    // Small enough to stay in the HTML
+    let svg = &ctx.config.build.typst.svg;
+    if svg.inline && optimized_data.len() < ctx.config.get_inline_max_size() {
+        writer.get_mut().write_all(&optimized_data)?;
+        return Ok(None);
+    }
+
+    // Reuse the index of an identical SVG already extracted from this page
+    let duplicate = if ctx.config.build.typst.svg.dedupe {
+        let index = ctx.svg_count;
+        let existing = *ctx
+            .svg_hashes
+            .entry(hash::compute(&optimized_data))
+            .or_insert(index);
+        (existing != index).then_some(existing)
+    } else {
+        None
+    };
+
+    if let Some(index) = duplicate {
+        let svg = Svg::new(optimized_data, size, index);
+        write_img_placeholder(writer, &svg, ctx)?;
+        return Ok(None);
+    }
+
     // Create SVG and write placeholder
     let svg = Svg::new(optimized_data, size, ctx.svg_count);
     ctx.svg_count += 1;
@@ -142,12 +172,24 @@ fn write_img_placeholder(
     svg: &Svg,
     ctx: &HtmlContext<'_>,
 ) -> Result<()> {
-    let filename = svg.filename(ctx.config);
     let output_dir = ctx.html_path.parent().context("Invalid html path")?;
-    let full_path = output_dir.join(&filename);
+    let url = |filename: String| {
+        url_from_output_path(&output_dir.join(&filename), ctx.config).unwrap_or(filename)
+    };
 
-    // Build src attribute
-    let src = url_from_output_path(&full_path, ctx.config).unwrap_or_else(|_| filename.clone());
+    // Build src attribute, offering the AVIF through `<picture>` with a PNG fallback
+    let fallback = svg.fallback_filename(ctx.config);
+    let src = url(svg.filename(ctx.config));
+    let src = if let Some(fallback) = fallback.clone() {
+        let mut source = BytesStart::new("source");
+        source.push_attribute(("type", "image/avif"));
+        source.push_attribute(("srcset", src.as_str()));
+        writer.write_event(Event::Start(BytesStart::new("picture")))?;
+        writer.write_event(Event::Start(source))?;
+        url(fallback)
+    } else {
+        src
+    };
 
     // Build style attribute with scaled dimensions
     let scale = ctx.config.get_scale();
@@ -162,6 +204,9 @@ fn write_img_placeholder(
         img.push_attribute(("class", "color-invert"));
     }
     writer.write_event(Event::Start(img))?;
+    if fallback.is_some() {
+        writer.write_event(Event::End(BytesEnd::new("picture")))?;
+    }
 
     Ok(())
 }
@@ -171,6 +216,26 @@ mod tests {
     use super::*;
     use quick_xml::events::attributes::Attribute;
     use quick_xml::name::QName;
+    use std::path::Path;
+
+    #[test]
+    fn test_picture_placeholder() {
+        let mut config = SiteConfig::default();
+        config.build.output = "/out".into();
+        config.build.typst.svg.extract_type = crate::config::ExtractSvgType::Builtin;
+        config.build.typst.svg.inline_max_size = "0B".into();
+        config.build.typst.svg.picture_fallback = true;
+        let ctx = HtmlContext::new(&config, Path::new("/out/post/index.html"), 0);
+
+        let mut writer = Writer::new(Cursor::new(Vec::new()));
+        let svg = Svg::new(vec![0; 16], (10.0, 20.0), 1);
+        write_img_placeholder(&mut writer, &svg, &ctx).unwrap();
+        let html = String::from_utf8(writer.into_inner().into_inner()).unwrap();
+        assert_eq!(
+            html,
+            r#"<picture><source type="image/avif" srcset="/post/svg-1.avif"><img src="/post/svg-1.png" style="width:10px;height:20px;" class="color-invert"></picture>"#
+        );
+    }
 
     #[test]
     fn test_capture_svg_content() {
@@ -4,7 +4,8 @@
 //!
 //! - **Extract**: Parse inline SVGs from HTML output
 //! - **Optimize**: Adjust viewBox, normalize dimensions
-//! - **Compress**: Parallel SVGZ compression for external files
+//! - **Compress**: Write external files in parallel (plain `.svg`, or AVIF
+//!   when rasterized); no `.svgz` is produced
 //!
 //! # Architecture
 //!
@@ -23,7 +24,7 @@
 //!         │
 //!         ▼
 //!    ┌──────────┐
-//!    │ compress │ ──► GZIP → .svgz (parallel)
+//!    │ compress │ ──► .svg / .avif files (parallel)
 //!    └──────────┘
 //! ```
 
@@ -35,7 +36,9 @@ mod transform;
 pub use compress::compress_svgs_parallel;
 pub use extract::extract_svg_element;
 
+use crate::compiler::meta::PageMeta;
 use crate::config::{ExtractSvgType, SiteConfig};
+use rustc_hash::FxHashMap;
 use std::path::Path;
 
 // ============================================================================
@@ -71,10 +74,19 @@ impl Svg {
     }
 
     /// Determine the output format based on config and file size
+    ///
+    /// | Size | Format |
+    /// |------|--------|
+    /// | `< inline_max_size` (with `inline`) | [`Inline`](OutputFormat::Inline) |
+    /// | `< raster_min_size` (or `just_svg`) | [`Svg`](OutputFormat::Svg) |
+    /// | otherwise | [`Avif`](OutputFormat::Avif) |
     #[inline]
-    fn output_format(&self, config: &SiteConfig) -> OutputFormat {
-        if matches!(config.build.typst.svg.extract_type, ExtractSvgType::JustSvg)
-            || self.data.len() < config.get_inline_max_size()
+    pub fn output_format(&self, config: &SiteConfig) -> OutputFormat {
+        let len = self.data.len();
+        if config.build.typst.svg.inline && len < config.get_inline_max_size() {
+            OutputFormat::Inline
+        } else if matches!(config.build.typst.svg.extract_type, ExtractSvgType::JustSvg)
+            || len < config.get_raster_min_size()
         {
             OutputFormat::Svg
         } else {
@@ -86,45 +98,72 @@ impl Svg {
     #[inline]
     pub fn filename(&self, config: &SiteConfig) -> String {
         match self.output_format(config) {
-            OutputFormat::Svg => format!("svg-{}.svg", self.index),
+            OutputFormat::Inline | OutputFormat::Svg => format!("svg-{}.svg", self.index),
             OutputFormat::Avif => format!("svg-{}.avif", self.index),
         }
     }
+
+    /// PNG fallback filename (e.g., "svg-0.png") for a rasterized SVG
+    /// under `[build.typst.svg].picture_fallback`
+    #[inline]
+    pub fn fallback_filename(&self, config: &SiteConfig) -> Option<String> {
+        (config.build.typst.svg.picture_fallback
+            && self.output_format(config) == OutputFormat::Avif)
+            .then(|| format!("svg-{}.png", self.index))
+    }
 }
 
 /// Output format for extracted SVGs
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OutputFormat {
+    /// Kept inline in the HTML
+    Inline,
+    /// External `.svg` file
     Svg,
+    /// Rasterized external `.avif` file
     Avif,
 }
 
 /// Processing context for HTML transformation
 pub struct HtmlContext<'a> {
     pub config: &'a SiteConfig,
+    /// Source `.typ` file, for links to `auto_copy_referenced` files
+    pub source: Option<&'a Path>,
+    /// Page being processed, for metadata-driven `<head>` content
+    pub page: Option<&'a PageMeta>,
     pub html_path: &'a Path,
+    /// Next extracted SVG index. Scoped to this page (each page has its own
+    /// output directory) and assigned in document order, before the parallel
+    /// compression, so `svg-<n>` names don't depend on thread scheduling.
     pub svg_count: usize,
+    /// Hash of each extracted SVG's data → its index (`[build.typst.svg].dedupe`)
+    pub svg_hashes: FxHashMap<u64, usize>,
     pub extract_svg: bool,
-    /// Whether the source file was named "index.typ".
+    /// How many directories deeper the output page sits than its source.
     ///
-    /// This affects relative path resolution:
-    /// - `index.typ` → `dir/index.html` (same level, no adjustment needed)
-    /// - `foo.typ` → `foo/index.html` (one level deeper, needs `../` prefix)
-    pub is_source_index: bool,
+    /// Relative links are prefixed with this many `../`
+    /// (see [`relative_link_depth`](crate::utils::xml::link::relative_link_depth)).
+    pub link_depth: usize,
+    /// Slugified id of the open heading, for its `heading_anchors` permalink.
+    pub heading_id: Option<String>,
 }
 
 impl<'a> HtmlContext<'a> {
     #[allow(clippy::missing_const_for_fn)] // matches! macro is not const
-    pub fn new(config: &'a SiteConfig, html_path: &'a Path, is_source_index: bool) -> Self {
+    pub fn new(config: &'a SiteConfig, html_path: &'a Path, link_depth: usize) -> Self {
         Self {
             config,
+            source: None,
+            page: None,
             html_path,
             svg_count: 0,
+            svg_hashes: FxHashMap::default(),
             extract_svg: !matches!(
                 config.build.typst.svg.extract_type,
                 ExtractSvgType::Embedded
             ),
-            is_source_index,
+            link_depth,
+            heading_id: None,
         }
     }
 }
@@ -148,6 +187,26 @@ mod tests {
         assert_eq!(large.output_format(config), OutputFormat::Avif);
     }
 
+    #[test]
+    fn test_output_format_size_bands() {
+        let mut config = SiteConfig::default();
+        config.build.typst.svg.inline = true;
+        config.build.typst.svg.inline_max_size = "1KB".into();
+        config.build.typst.svg.raster_min_size = Some("4KB".into());
+        let format = |len| Svg::new(vec![0; len], (1.0, 1.0), 0).output_format(&config);
+
+        assert_eq!(format(1023), OutputFormat::Inline);
+        assert_eq!(format(1024), OutputFormat::Svg);
+        assert_eq!(format(4095), OutputFormat::Svg);
+        assert_eq!(format(4096), OutputFormat::Avif);
+
+        // just_svg never rasterizes
+        config.build.typst.svg.extract_type = ExtractSvgType::JustSvg;
+        let format = |len| Svg::new(vec![0; len], (1.0, 1.0), 0).output_format(&config);
+        assert_eq!(format(1023), OutputFormat::Inline);
+        assert_eq!(format(1 << 20), OutputFormat::Svg);
+    }
+
     #[test]
     fn test_svg_filename() {
         let config = Box::leak(Box::new(SiteConfig::default()));
@@ -159,6 +218,15 @@ mod tests {
         // Large SVG gets .avif extension
         let large = Svg::new(vec![0; 100_000], (100.0, 100.0), 3);
         assert_eq!(large.filename(config), "svg-3.avif");
+        assert_eq!(large.fallback_filename(config), None);
+
+        let mut config = config.clone();
+        config.build.typst.svg.picture_fallback = true;
+        assert_eq!(
+            large.fallback_filename(&config).as_deref(),
+            Some("svg-3.png")
+        );
+        assert_eq!(small.fallback_filename(&config), None);
     }
 
     #[test]
@@ -178,7 +246,7 @@ mod tests {
         // Embedded mode: don't extract
         config.build.typst.svg.extract_type = ExtractSvgType::Embedded;
         let config = Box::leak(Box::new(config));
-        let ctx = HtmlContext::new(config, Path::new("/test.html"), true);
+        let ctx = HtmlContext::new(config, Path::new("/test.html"), 0);
         assert!(!ctx.extract_svg);
     }
 }
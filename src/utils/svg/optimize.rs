@@ -1,19 +1,39 @@
 use crate::config::SiteConfig;
 use anyhow::{Context, Result};
 
+/// Numeric precision (coordinates, transforms) at `optimize_level = 2`.
+const AGGRESSIVE_PRECISION: (u8, u8) = (3, 4);
+
 /// Optimize SVG using usvg, returning optimized bytes and dimensions.
+///
+/// `[build.typst.svg].optimize_level` picks how much is rewritten:
+/// - 0: content kept as-is (still parsed, for its size)
+/// - 1: usvg output, without comments, `<metadata>` or unused definitions
+/// - 2: like 1, with coordinates and transforms rounded
 pub fn optimize_svg(content: &[u8], config: &SiteConfig) -> Result<(Vec<u8>, (f32, f32))> {
+    let svg = &config.build.typst.svg;
     let options = usvg::Options {
-        dpi: config.build.typst.svg.dpi,
+        dpi: svg.dpi,
         ..Default::default()
     };
 
     let tree = usvg::Tree::from_data(content, &options).context("Failed to parse SVG")?;
 
-    let write_options = usvg::WriteOptions {
+    if svg.optimize_level == 0 {
+        let size = tree.size();
+        return Ok((content.to_vec(), (size.width(), size.height())));
+    }
+
+    let mut write_options = usvg::WriteOptions {
         indent: usvg::Indent::None,
         ..Default::default()
     };
+    if svg.optimize_level >= 2 {
+        (
+            write_options.coordinates_precision,
+            write_options.transforms_precision,
+        ) = AGGRESSIVE_PRECISION;
+    }
 
     let optimized = tree.to_string(&write_options);
     let size = parse_dimensions(&optimized).unwrap_or((0.0, 0.0));
@@ -41,6 +61,38 @@ fn extract_attr<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
 mod tests {
     use super::*;
 
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape" width="10" height="10" viewBox="0 0 10 10">
+        <!-- drawn by hand -->
+        <metadata><rdf>cruft</rdf></metadata>
+        <path inkscape:label="box" d="M 0.123456789 0 L 9.87654321 0 L 9.87654321 9.87654321 Z"/>
+    </svg>"#;
+
+    fn optimize_at(level: u8) -> (String, (f32, f32)) {
+        let mut config = SiteConfig::default();
+        config.build.typst.svg.optimize_level = level;
+        let (data, size) = optimize_svg(SVG.as_bytes(), &config).unwrap();
+        (String::from_utf8(data).unwrap(), size)
+    }
+
+    #[test]
+    fn test_optimize_levels() {
+        let (none, size) = optimize_at(0);
+        assert_eq!(none, SVG);
+        assert_eq!(size, (10.0, 10.0));
+
+        let (safe, size) = optimize_at(1);
+        assert_eq!(size, (10.0, 10.0));
+        for cruft in ["<!--", "metadata", "inkscape"] {
+            assert!(!safe.contains(cruft), "{safe}");
+        }
+        assert!(safe.contains("0.12345679"), "{safe}");
+        assert!(safe.len() < SVG.len());
+
+        let (aggressive, _) = optimize_at(2);
+        assert!(aggressive.contains("0.123 "), "{aggressive}");
+        assert!(aggressive.len() < safe.len());
+    }
+
     #[test]
     fn test_parse_dimensions() {
         // Valid cases
@@ -0,0 +1,276 @@
+//! Typst content elements in summaries, rendered as inline HTML.
+//!
+//! `summary: [...]` in page metadata arrives as JSON-serialized Typst content.
+//! It is rendered once here, and the result is used everywhere a summary is
+//! shown: `pages.json`, templates and RSS descriptions. Feeds additionally
+//! make site-root links absolute with [`absolutize_links`].
+
+use serde::Deserialize;
+use std::borrow::Cow;
+
+/// Typst content element for summary field deserialization.
+///
+/// Parses JSON-serialized Typst content and converts to HTML.
+///
+/// # Supported Elements
+///
+/// | Element    | HTML Output               |
+/// |------------|---------------------------|
+/// | Space      | ` ` (space)               |
+/// | Linebreak  | `<br/>`                   |
+/// | Text       | Escaped text              |
+/// | Strike     | `<s>text</s>`             |
+/// | Emph       | `<em>body</em>`           |
+/// | Strong     | `<strong>body</strong>`   |
+/// | Raw        | `<code>text</code>`       |
+/// | Link       | `<a href="...">text</a>`  |
+/// | Sequence   | Concatenated children     |
+/// | Unknown    | Empty string (ignored)    |
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "func", rename_all = "lowercase")]
+pub enum TypstElement {
+    Space,
+    Linebreak,
+    Text {
+        text: String,
+    },
+    Strike {
+        text: String,
+    },
+    Emph {
+        body: Box<Self>,
+    },
+    Strong {
+        body: Box<Self>,
+    },
+    Raw {
+        text: String,
+    },
+    Link {
+        dest: String,
+        body: Box<Self>,
+    },
+    Sequence {
+        children: Vec<Self>,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+impl TypstElement {
+    /// Convert Typst element to HTML string.
+    pub fn to_html(&self) -> String {
+        match self {
+            Self::Space => " ".into(),
+            Self::Linebreak => "<br/>".into(),
+            Self::Text { text } => html_escape(text).into_owned(),
+            Self::Strike { text } => format!("<s>{}</s>", html_escape(text)),
+            Self::Emph { body } => format!("<em>{}</em>", body.to_html()),
+            Self::Strong { body } => format!("<strong>{}</strong>", body.to_html()),
+            Self::Raw { text } => format!("<code>{}</code>", html_escape(text)),
+            Self::Link { dest, body } => {
+                format!("<a href=\"{}\">{}</a>", html_escape(dest), body.to_html())
+            }
+            Self::Sequence { children } => children.iter().map(Self::to_html).collect(),
+            Self::Unknown => String::new(),
+        }
+    }
+}
+
+/// Escape HTML special characters.
+///
+/// Uses `Cow` to avoid allocation when no escaping is needed.
+#[inline]
+pub fn html_escape(s: &str) -> Cow<'_, str> {
+    // Fast path: check if escaping is needed
+    if !s.contains(['<', '>', '&', '"']) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '&' => result.push_str("&amp;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// Prefix site-root links (`href="/..."`) in summary HTML with `origin`.
+///
+/// Feed readers resolve links against the feed, not the site, so RSS
+/// descriptions need absolute URLs. Protocol-relative links are kept.
+pub fn absolutize_links<'a>(html: &'a str, origin: &str) -> Cow<'a, str> {
+    const ROOT_HREF: &str = "href=\"/";
+    if origin.is_empty() || !html.contains(ROOT_HREF) {
+        return Cow::Borrowed(html);
+    }
+
+    let mut out = String::with_capacity(html.len() + origin.len());
+    let mut rest = html;
+    while let Some(i) = rest.find(ROOT_HREF) {
+        let (before, after) = rest.split_at(i + ROOT_HREF.len() - 1);
+        out.push_str(before);
+        if !after.starts_with("//") {
+            out.push_str(origin.trim_end_matches('/'));
+        }
+        out.push('/');
+        rest = &after[1..];
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ========================================================================
+    // TypstElement tests
+    // ========================================================================
+
+    #[test]
+    fn test_typst_element_text() {
+        let json = r#"{"func": "text", "text": "Hello World"}"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        assert_eq!(elem.to_html(), "Hello World");
+    }
+
+    #[test]
+    fn test_typst_element_space() {
+        let json = r#"{"func": "space"}"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        assert!(matches!(elem, TypstElement::Space));
+        assert_eq!(elem.to_html(), " ");
+    }
+
+    #[test]
+    fn test_typst_element_linebreak() {
+        let json = r#"{"func": "linebreak"}"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        assert!(matches!(elem, TypstElement::Linebreak));
+        assert_eq!(elem.to_html(), "<br/>");
+    }
+
+    #[test]
+    fn test_typst_element_strike() {
+        let json = r#"{"func": "strike", "text": "deleted"}"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        assert_eq!(elem.to_html(), "<s>deleted</s>");
+    }
+
+    #[test]
+    fn test_typst_element_link() {
+        let json = r#"{"func": "link", "dest": "https://example.com", "body": {"func": "text", "text": "click here"}}"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        if let TypstElement::Link { dest, body } = &elem {
+            assert_eq!(dest, "https://example.com");
+            assert!(matches!(body.as_ref(), TypstElement::Text { text } if text == "click here"));
+        } else {
+            panic!("Expected Link element");
+        }
+        assert_eq!(
+            elem.to_html(),
+            r#"<a href="https://example.com">click here</a>"#
+        );
+    }
+
+    #[test]
+    fn test_typst_element_sequence() {
+        let json = r#"{"func": "sequence", "children": [{"func": "text", "text": "Hello"}, {"func": "space"}, {"func": "text", "text": "World"}]}"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        if let TypstElement::Sequence { children } = &elem {
+            assert_eq!(children.len(), 3);
+            assert!(matches!(&children[0], TypstElement::Text { text } if text == "Hello"));
+            assert!(matches!(&children[1], TypstElement::Space));
+            assert!(matches!(&children[2], TypstElement::Text { text } if text == "World"));
+        } else {
+            panic!("Expected Sequence element");
+        }
+        assert_eq!(elem.to_html(), "Hello World");
+    }
+
+    #[test]
+    fn test_typst_element_unknown() {
+        let json = r#"{"func": "some_unknown_func"}"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        assert!(matches!(elem, TypstElement::Unknown));
+        assert_eq!(elem.to_html(), "");
+    }
+
+    #[test]
+    fn test_typst_element_nested_sequence() {
+        let json = r#"{
+            "func": "sequence",
+            "children": [
+                {"func": "text", "text": "Start "},
+                {"func": "link", "dest": "https://rust-lang.org", "body": {"func": "text", "text": "Rust"}},
+                {"func": "text", "text": " is great"}
+            ]
+        }"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            elem.to_html(),
+            r#"Start <a href="https://rust-lang.org">Rust</a> is great"#
+        );
+    }
+
+    // ========================================================================
+    // html_escape tests
+    // ========================================================================
+
+    #[test]
+    fn test_html_escape_plain() {
+        assert_eq!(html_escape("hello world"), "hello world");
+    }
+
+    #[test]
+    fn test_html_escape_special_chars() {
+        assert_eq!(html_escape("<script>"), "&lt;script&gt;");
+        assert_eq!(html_escape("a & b"), "a &amp; b");
+        assert_eq!(html_escape("say \"hi\""), "say &quot;hi&quot;");
+    }
+
+    #[test]
+    fn test_html_escape_mixed() {
+        assert_eq!(
+            html_escape("<a href=\"#\">link & text</a>"),
+            "&lt;a href=&quot;#&quot;&gt;link &amp; text&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_html_escape_empty() {
+        assert_eq!(html_escape(""), "");
+    }
+
+    // ========================================================================
+    // absolutize_links tests
+    // ========================================================================
+
+    #[test]
+    fn test_absolutize_links() {
+        let html =
+            r#"<a href="/posts/a/">A</a> <a href="//cdn.x/y">B</a> <a href="https://x.com/">C</a>"#;
+        assert_eq!(
+            absolutize_links(html, "https://example.com"),
+            r#"<a href="https://example.com/posts/a/">A</a> <a href="//cdn.x/y">B</a> <a href="https://x.com/">C</a>"#
+        );
+        assert!(matches!(absolutize_links(html, ""), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_link_dest_is_escaped() {
+        let json =
+            r#"{"func": "link", "dest": "/a?x=1&y=\"2\"", "body": {"func": "text", "text": "a"}}"#;
+        let elem: TypstElement = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            elem.to_html(),
+            r#"<a href="/a?x=1&amp;y=&quot;2&quot;">a</a>"#
+        );
+    }
+}
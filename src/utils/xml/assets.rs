@@ -1,13 +1,21 @@
-use crate::compiler::meta::AssetMeta;
-use crate::config::SiteConfig;
+use crate::compiler::meta::{AssetMeta, url_from_output_path};
+use crate::config::{SiteConfig, TailwindEntry};
 use anyhow::Result;
+use parking_lot::RwLock;
 use std::collections::HashSet;
 use std::ffi::OsString;
 use std::fs;
-use std::path::Path;
-use std::sync::OnceLock;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-static ASSET_TOP_LEVELS: OnceLock<HashSet<OsString>> = OnceLock::new();
+/// Assets directory and the names of its top-level entries.
+type AssetTopLevels = (PathBuf, Arc<HashSet<OsString>>);
+
+/// Top-level entries of the assets directory, keyed by that directory.
+///
+/// Listed on first use and kept for the rest of the build; the watcher
+/// resets it with [`invalidate_asset_top_levels`] when assets change.
+static ASSET_TOP_LEVELS: RwLock<Option<AssetTopLevels>> = RwLock::new(None);
 
 /// Get MIME type for icon based on file extension
 pub fn get_icon_mime_type(path: &Path) -> &'static str {
@@ -25,8 +33,8 @@ pub fn get_icon_mime_type(path: &Path) -> &'static str {
         })
 }
 
-/// Compute href for an asset path relative to `path_prefix`
-pub fn compute_asset_href(asset_path: &Path, config: &SiteConfig) -> Result<String> {
+/// Resolve a `[build.head]` asset path to its source file in the assets directory
+pub fn resolve_asset_source(asset_path: &Path, config: &SiteConfig) -> PathBuf {
     let assets_dir = &config.build.assets;
     // Strip the leading "./" prefix if present
     let without_dot_prefix = asset_path.strip_prefix("./").unwrap_or(asset_path);
@@ -35,37 +43,129 @@ pub fn compute_asset_href(asset_path: &Path, config: &SiteConfig) -> Result<Stri
         .strip_prefix("assets/")
         .unwrap_or(without_dot_prefix);
 
-    let source = assets_dir.join(rel_path);
+    assets_dir.join(rel_path)
+}
+
+/// Compute href for an asset path relative to `path_prefix`
+pub fn compute_asset_href(asset_path: &Path, config: &SiteConfig) -> Result<String> {
+    let source = resolve_asset_source(asset_path, config);
     let meta = AssetMeta::from_source(source, config)?;
     Ok(meta.paths.url)
 }
 
-/// Compute stylesheet href from input path
-pub fn compute_stylesheet_href(input: &Path, config: &SiteConfig) -> Result<String> {
-    let input = input.canonicalize()?;
-    let meta = AssetMeta::from_source(input, config)?;
-    Ok(meta.paths.url)
+/// Check if a `[build.head]` path is a remote URL (`https://`, `//cdn...`)
+pub fn is_remote_url(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    path.starts_with("http://") || path.starts_with("https://") || path.starts_with("//")
+}
+
+/// Compute the Subresource Integrity value (`sha384-<base64>`) of a file
+pub fn compute_sri(path: &Path) -> Result<String> {
+    use base64::Engine;
+    use sha2::{Digest, Sha384};
+
+    let content = fs::read(path)?;
+    let digest = Sha384::digest(&content);
+    Ok(format!(
+        "sha384-{}",
+        base64::engine::general_purpose::STANDARD.encode(digest)
+    ))
+}
+
+/// Compute stylesheet href of a Tailwind entrypoint
+pub fn compute_tailwind_href(entry: &TailwindEntry, config: &SiteConfig) -> Result<String> {
+    let output = crate::compiler::assets::tailwind_output(entry, config)?;
+    url_from_output_path(&output, config)
+}
+
+/// Compute href of the compiled Sass stylesheet
+pub fn compute_sass_href(config: &SiteConfig) -> Result<String> {
+    let output = crate::compiler::assets::sass_output(config)?;
+    url_from_output_path(&output, config)
 }
 
 /// Get top-level asset directory names
-fn get_asset_top_levels(assets_dir: &Path) -> &'static HashSet<OsString> {
-    ASSET_TOP_LEVELS.get_or_init(|| {
+fn get_asset_top_levels(assets_dir: &Path) -> Arc<HashSet<OsString>> {
+    if let Some((dir, names)) = &*ASSET_TOP_LEVELS.read()
+        && dir == assets_dir
+    {
+        return Arc::clone(names);
+    }
+
+    let names: Arc<HashSet<OsString>> = Arc::new(
         fs::read_dir(assets_dir)
             .map(|dir| dir.flatten().map(|entry| entry.file_name()).collect())
-            .unwrap_or_default()
-    })
+            .unwrap_or_default(),
+    );
+    *ASSET_TOP_LEVELS.write() = Some((assets_dir.to_path_buf(), Arc::clone(&names)));
+    names
+}
+
+/// Drop the cached asset listing so the next lookup re-reads the directory.
+pub fn invalidate_asset_top_levels() {
+    *ASSET_TOP_LEVELS.write() = None;
 }
 
 /// Check if a path is an asset link
 pub fn is_asset_link(path: &str, config: &SiteConfig) -> bool {
     let asset_top_levels = get_asset_top_levels(&config.build.assets);
+    asset_top_levels.contains(first_component(path).as_ref() as &std::ffi::OsStr)
+}
 
-    // Extract first path component after the leading slash
-    let first_component = path
-        .trim_start_matches('/')
-        .split('/')
+/// First path component after the leading slash, ignoring `?query` and `#fragment`.
+fn first_component(path: &str) -> &str {
+    path.trim_start_matches('/')
+        .split(['/', '?', '#'])
         .next()
-        .unwrap_or_default();
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_first_component_ignores_query_and_fragment() {
+        assert_eq!(first_component("/images/photo.png?v=2#crop"), "images");
+        assert_eq!(first_component("/favicon.ico?v=1"), "favicon.ico");
+        assert_eq!(first_component("/logo.svg#icon"), "logo.svg");
+        assert_eq!(first_component("/"), "");
+    }
+
+    #[test]
+    fn test_new_asset_dir_after_invalidate() {
+        let dir = TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.assets = dir.path().join("assets");
+        fs::create_dir_all(config.build.assets.join("images")).unwrap();
+
+        assert!(is_asset_link("/images/a.png", &config));
+        assert!(!is_asset_link("/fonts/a.woff2", &config));
+
+        // As the watcher does when something under `assets/` changes
+        fs::create_dir(config.build.assets.join("fonts")).unwrap();
+        invalidate_asset_top_levels();
+        assert!(is_asset_link("/fonts/a.woff2", &config));
+    }
+
+    #[test]
+    fn test_compute_sri() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("hello.js");
+        fs::write(&path, "alert('Hello, world.');").unwrap();
+
+        assert_eq!(
+            compute_sri(&path).unwrap(),
+            "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO"
+        );
+    }
 
-    asset_top_levels.contains(first_component.as_ref() as &std::ffi::OsStr)
+    #[test]
+    fn test_is_remote_url() {
+        assert!(is_remote_url(Path::new("https://cdn.example.com/a.css")));
+        assert!(is_remote_url(Path::new("//cdn.example.com/a.js")));
+        assert!(!is_remote_url(Path::new("assets/app.css")));
+        assert!(!is_remote_url(Path::new("./assets/app.js")));
+    }
 }
@@ -82,12 +82,13 @@ pub fn write_empty_elem(writer: &mut XmlWriter, tag: &str, attrs: &[(&str, &str)
     Ok(())
 }
 
-/// Write a script element with optional defer/async.
+/// Write a script element with optional defer/async and SRI hash.
 pub fn write_script(
     writer: &mut XmlWriter,
     src: &str,
     defer: bool,
     async_attr: bool,
+    integrity: Option<&str>,
 ) -> Result<()> {
     let mut elem = BytesStart::new("script");
     elem.push_attribute(("src", src));
@@ -97,6 +98,10 @@ pub fn write_script(
     if async_attr {
         elem.push_attribute(("async", ""));
     }
+    if let Some(integrity) = integrity {
+        elem.push_attribute(("integrity", integrity));
+        elem.push_attribute(("crossorigin", "anonymous"));
+    }
     writer.write_event(Event::Start(elem))?;
     // Space ensures proper HTML parsing of script tags
     writer.write_event(Event::Text(BytesText::new(" ")))?;
@@ -0,0 +1,173 @@
+//! Intrinsic image dimensions for `<img>` elements.
+//!
+//! When `[build].image_dimensions` is enabled, local raster images get
+//! `width`/`height` attributes so browsers can reserve space before loading.
+//!
+//! Only the image header is read, and results are cached per file (keyed by
+//! mtime, so watch mode picks up replaced images).
+
+use super::assets::is_asset_link;
+use super::link::{is_external_link, lexical_normalize, process_relative_link};
+use crate::config::SiteConfig;
+use parking_lot::RwLock;
+use rustc_hash::FxHashMap;
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::SystemTime,
+};
+
+/// Raster formats whose headers can be decoded
+const RASTER_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
+
+/// Cached dimensions: path → (mtime, size). `None` size means unreadable.
+type DimensionCache = FxHashMap<PathBuf, (Option<SystemTime>, Option<(u32, u32)>)>;
+
+static DIMENSION_CACHE: LazyLock<RwLock<DimensionCache>> = LazyLock::new(Default::default);
+
+/// Get `(width, height)` for an `<img src>` value.
+///
+/// Returns `None` for remote images, SVGs, unsupported formats, and missing files.
+pub fn image_dimensions(
+    src: &str,
+    html_path: &Path,
+    config: &SiteConfig,
+    link_depth: usize,
+) -> Option<(u32, u32)> {
+    let path = resolve_local_image(src, html_path, config, link_depth)?;
+    read_cached(&path)
+}
+
+/// Map an `<img src>` value to the source file it was copied from.
+///
+/// - `/images/a.png` → `assets/images/a.png` (asset link) or `content/images/a.png`
+/// - `a.png` (relative) → resolved against the output HTML, then mapped back to content
+fn resolve_local_image(
+    src: &str,
+    html_path: &Path,
+    config: &SiteConfig,
+    link_depth: usize,
+) -> Option<PathBuf> {
+    if src.starts_with("//") || is_external_link(src) {
+        return None;
+    }
+
+    // Drop query string and fragment
+    let src = src.split(['?', '#']).next().unwrap_or_default();
+    let ext = Path::new(src).extension()?.to_str()?.to_ascii_lowercase();
+    if !RASTER_EXTENSIONS.contains(&ext.as_str()) {
+        return None;
+    }
+
+    if src.starts_with('/') {
+        let rel = src.trim_start_matches('/');
+        let base = if is_asset_link(src, config) {
+            &config.build.assets
+        } else {
+            &config.build.content
+        };
+        return Some(base.join(rel));
+    }
+
+    let adjusted = process_relative_link(src, link_depth).ok()?;
+    let output_path = lexical_normalize(&html_path.parent()?.join(&*adjusted));
+    let rel = output_path.strip_prefix(config.paths().output_dir()).ok()?;
+    Some(config.build.content.join(rel))
+}
+
+/// Read image dimensions, reusing the cached value if the file is unchanged.
+fn read_cached(path: &Path) -> Option<(u32, u32)> {
+    let mtime = path.metadata().and_then(|m| m.modified()).ok();
+
+    if let Some((cached_mtime, size)) = DIMENSION_CACHE.read().get(path)
+        && *cached_mtime == mtime
+    {
+        return *size;
+    }
+
+    let size = ::image::image_dimensions(path).ok();
+    DIMENSION_CACHE
+        .write()
+        .insert(path.to_path_buf(), (mtime, size));
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::xml::process_html;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn make_config(dir: &Path) -> SiteConfig {
+        let mut config = SiteConfig::default();
+        config.build.content = dir.join("content");
+        config.build.output = dir.join("public");
+        config.build.assets = dir.join("assets");
+        config.build.image_dimensions = true;
+        config
+    }
+
+    #[test]
+    fn test_injects_png_dimensions() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path());
+        fs::create_dir_all(config.build.content.join("posts")).unwrap();
+        ::image::RgbImage::new(3, 2)
+            .save(config.build.content.join("posts/dot.png"))
+            .unwrap();
+
+        // posts/hello.typ → posts/hello/index.html, `dot.png` → `../dot.png`
+        let source = config.build.content.join("posts/hello.typ");
+        let html_path = config.build.output.join("posts/hello/index.html");
+        let html = br#"<html><head></head><body><img src="dot.png"></body></html>"#;
+        let out = process_html(&source, &html_path, html, &config, 1).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains(r#"src="../dot.png""#), "{out}");
+        assert!(out.contains(r#"width="3""#), "{out}");
+        assert!(out.contains(r#"height="2""#), "{out}");
+    }
+
+    #[test]
+    fn test_keeps_existing_dimensions() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path());
+        fs::create_dir_all(&config.build.content).unwrap();
+        ::image::RgbImage::new(3, 2)
+            .save(config.build.content.join("dot.png"))
+            .unwrap();
+
+        let source = config.build.content.join("index.typ");
+        let html_path = config.build.output.join("index.html");
+        let html = br#"<html><head></head><body><img src="dot.png" width="10"></body></html>"#;
+        let out = process_html(&source, &html_path, html, &config, 0).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains(r#"width="10""#), "{out}");
+        assert!(!out.contains("height="), "{out}");
+    }
+
+    #[test]
+    fn test_skips_remote_and_svg() {
+        let dir = TempDir::new().unwrap();
+        let config = make_config(dir.path());
+        let html_path = config.build.output.join("index.html");
+
+        assert!(resolve_local_image("https://x.com/a.png", &html_path, &config, 0).is_none());
+        assert!(resolve_local_image("//x.com/a.png", &html_path, &config, 0).is_none());
+        assert!(resolve_local_image("logo.svg", &html_path, &config, 0).is_none());
+        assert_eq!(
+            resolve_local_image("a.png?v=1", &html_path, &config, 0),
+            Some(config.build.content.join("a.png"))
+        );
+    }
+
+    #[test]
+    fn test_lexical_normalize() {
+        assert_eq!(
+            lexical_normalize(Path::new("/out/posts/hello/.././dot.png")),
+            PathBuf::from("/out/posts/dot.png")
+        );
+    }
+}
@@ -1,14 +1,53 @@
+use crate::compiler::meta::PageMeta;
 use crate::config::SiteConfig;
+use crate::log;
+use crate::report::BUILD_STATS;
 use crate::utils::css;
 use anyhow::Result;
-use quick_xml::events::{BytesEnd, Event};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use regex::{Captures, Regex};
+use std::borrow::Cow;
 use std::io::Write;
+use std::path::Path;
+use std::sync::LazyLock;
 
-use super::assets::{compute_asset_href, compute_stylesheet_href, get_icon_mime_type};
+use super::assets::{
+    compute_asset_href, compute_sass_href, compute_sri, compute_tailwind_href, get_icon_mime_type,
+    is_remote_url, resolve_asset_source,
+};
 use super::common::{XmlWriter, write_empty_elem, write_script, write_text_element};
+use super::summary::html_to_text;
+
+/// `<meta name="generator">` content (`[build.head].generator_meta`).
+const GENERATOR: &str = concat!("tola-ssg ", env!("CARGO_PKG_VERSION"));
+
+/// `{{ extra.key }}` / `{{ base.key }}` placeholders in `[build.head].elements`.
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*(extra|base)\.([A-Za-z0-9_.-]+)\s*\}\}").unwrap());
+
+/// Write the `<head>` start tag, followed by `<base href>` if
+/// `[build.head].base_href` is set, ahead of any relative reference.
+pub fn write_head_start(
+    elem: &BytesStart<'_>,
+    writer: &mut XmlWriter,
+    config: &SiteConfig,
+) -> Result<()> {
+    writer.write_event(Event::Start(elem.to_owned()))?;
+    if let Some(href) = &config.build.head.base_href {
+        write_empty_elem(writer, "base", &[("href", href)])?;
+    }
+    Ok(())
+}
 
 /// Write `<head>` section content before closing tag.
-pub fn write_head_content(writer: &mut XmlWriter, config: &SiteConfig) -> Result<()> {
+///
+/// `page` is the content page being written, if any (taxonomy pages have none).
+pub fn write_head_content(
+    writer: &mut XmlWriter,
+    config: &SiteConfig,
+    page: Option<&PageMeta>,
+) -> Result<()> {
     let head = &config.build.head;
 
     if !config.base.title.is_empty() {
@@ -24,6 +63,28 @@ pub fn write_head_content(writer: &mut XmlWriter, config: &SiteConfig) -> Result
             ],
         )?;
     }
+    if page.is_some_and(|page| page.content_meta.as_ref().is_some_and(|c| c.noindex)) {
+        write_empty_elem(
+            writer,
+            "meta",
+            &[("name", "robots"), ("content", "noindex, nofollow")],
+        )?;
+    }
+    if head.generator_meta {
+        write_empty_elem(
+            writer,
+            "meta",
+            &[("name", "generator"), ("content", GENERATOR)],
+        )?;
+    }
+    let authors = page_authors(page, config);
+    if !authors.is_empty() {
+        write_empty_elem(
+            writer,
+            "meta",
+            &[("name", "author"), ("content", &authors.join(", "))],
+        )?;
+    }
 
     if let Some(icon) = &head.icon {
         let href = compute_asset_href(icon, config)?;
@@ -38,15 +99,45 @@ pub fn write_head_content(writer: &mut XmlWriter, config: &SiteConfig) -> Result
         )?;
     }
 
+    // Sass sources listed in `head.styles` link to their compiled CSS
+    let sass_href = if config.build.css.sass.enable {
+        Some(compute_sass_href(config)?)
+    } else {
+        None
+    };
+    let mut sass_linked = false;
     for style in &head.styles {
-        let href = compute_asset_href(style, config)?;
-        write_empty_elem(writer, "link", &[("rel", "stylesheet"), ("href", &href)])?;
+        let (href, integrity) = match &sass_href {
+            Some(sass_href) if css::is_sass_source(style, config) => {
+                sass_linked = true;
+                (sass_href.clone(), None)
+            }
+            _ if is_remote_url(style) => (style.to_string_lossy().into_owned(), None),
+            // PostCSS rewrites stylesheets after pages are written
+            _ if config.build.css.postcss.enable => (compute_asset_href(style, config)?, None),
+            _ => (
+                compute_asset_href(style, config)?,
+                asset_integrity(style, config)?,
+            ),
+        };
+        let mut attrs = vec![("rel", "stylesheet"), ("href", href.as_str())];
+        if let Some(integrity) = &integrity {
+            attrs.extend([
+                ("integrity", integrity.as_str()),
+                ("crossorigin", "anonymous"),
+            ]);
+        }
+        write_empty_elem(writer, "link", &attrs)?;
     }
 
-    if config.build.css.tailwind.enable
-        && let Some(input) = &config.build.css.tailwind.input
-    {
-        let href = compute_stylesheet_href(input, config)?;
+    if config.build.css.tailwind.enable {
+        for entry in config.build.css.tailwind.entries() {
+            let href = compute_tailwind_href(&entry, config)?;
+            write_empty_elem(writer, "link", &[("rel", "stylesheet"), ("href", &href)])?;
+        }
+    }
+
+    if let Some(href) = sass_href.filter(|_| !sass_linked) {
         write_empty_elem(writer, "link", &[("rel", "stylesheet"), ("href", &href)])?;
     }
 
@@ -59,15 +150,169 @@ pub fn write_head_content(writer: &mut XmlWriter, config: &SiteConfig) -> Result
 
     // Scripts
     for script in &head.scripts {
-        let src = compute_asset_href(script.path(), config)?;
-        write_script(writer, &src, script.is_defer(), script.is_async())?;
+        let path = script.path();
+        let (src, integrity) = if is_remote_url(path) {
+            (path.to_string_lossy().into_owned(), None)
+        } else {
+            (
+                compute_asset_href(path, config)?,
+                asset_integrity(path, config)?,
+            )
+        };
+        write_script(
+            writer,
+            &src,
+            script.is_defer(),
+            script.is_async(),
+            integrity.as_deref(),
+        )?;
+    }
+
+    if head.json_ld
+        && let Some(json) = page.and_then(|page| article_json_ld(page, config))
+    {
+        let script = format!(r#"<script type="application/ld+json">{json}</script>"#);
+        writer.get_mut().write_all(script.as_bytes())?;
     }
 
     // Raw HTML elements (trusted input)
     for raw in &head.elements {
-        writer.get_mut().write_all(raw.as_bytes())?;
+        writer
+            .get_mut()
+            .write_all(interpolate(raw, config).as_bytes())?;
     }
 
     writer.write_event(Event::End(BytesEnd::new("head")))?;
     Ok(())
 }
+
+/// schema.org `BlogPosting` for a page with a title, as JSON-LD.
+///
+/// Written raw inside `<script>`, so `</` is escaped as `<\/` to keep the
+/// JSON from closing the element early.
+fn article_json_ld(page: &PageMeta, config: &SiteConfig) -> Option<String> {
+    let content = page.content_meta.as_ref()?;
+    let headline = content.title.as_deref()?;
+
+    let mut article = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "BlogPosting",
+        "headline": headline,
+        "mainEntityOfPage": {
+            "@type": "WebPage",
+            "@id": page.paths.full_url,
+        },
+    });
+    if let Some(date) = &content.date {
+        article["datePublished"] = date.as_str().into();
+    }
+    if let Some(update) = &content.update {
+        article["dateModified"] = update.as_str().into();
+    }
+    let authors = page_authors(Some(page), config);
+    if !authors.is_empty() {
+        article["author"] = authors
+            .iter()
+            .map(|name| serde_json::json!({ "@type": "Person", "name": name }))
+            .collect();
+    }
+    if let Some(summary) = &content.summary {
+        article["description"] = html_to_text(summary).into();
+    }
+
+    Some(article.to_string().replace("</", "<\\/"))
+}
+
+/// Authors of `page` from its metadata, falling back to `[base].author`.
+fn page_authors<'a>(page: Option<&'a PageMeta>, config: &'a SiteConfig) -> Vec<&'a str> {
+    let authors = page
+        .and_then(|page| page.content_meta.as_ref())
+        .map(|content| &content.authors)
+        .filter(|authors| !authors.is_empty());
+    match authors {
+        Some(authors) => authors.iter().map(String::as_str).collect(),
+        None => Some(config.base.author.as_str())
+            .filter(|a| !a.is_empty())
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Substitute `{{ extra.key }}` and `{{ base.key }}` in a raw head element.
+///
+/// Values are escaped so they are safe in both text and quoted attributes.
+/// Nested `extra` tables are reached with dotted keys (`extra.a.b`); unknown
+/// keys and non-scalar values leave the placeholder untouched, with a warning.
+fn interpolate<'a>(raw: &'a str, config: &SiteConfig) -> Cow<'a, str> {
+    PLACEHOLDER.replace_all(raw, |caps: &Captures<'_>| {
+        let value = match &caps[1] {
+            "base" => base_value(config, &caps[2]).map(str::to_string),
+            _ => extra_value(config, &caps[2]),
+        };
+        match value {
+            Some(value) => escape(value.as_str()).into_owned(),
+            None => {
+                let msg = format!(
+                    "[build.head].elements: unknown key `{}.{}` in <{}>",
+                    &caps[1],
+                    &caps[2],
+                    element_name(raw)
+                );
+                // Head elements are written on every page, so log only once
+                if BUILD_STATS.warn(msg.clone()) {
+                    log!("warn"; "{msg}");
+                }
+                caps[0].to_string()
+            }
+        }
+    })
+}
+
+/// Tag name of a raw head element (`script` for `<script src=..>`).
+fn element_name(raw: &str) -> &str {
+    let tag = raw.trim_start().trim_start_matches('<');
+    let end = tag
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+/// `[base]` field by name, for head placeholders.
+fn base_value<'a>(config: &'a SiteConfig, key: &str) -> Option<&'a str> {
+    let base = &config.base;
+    match key {
+        "title" => Some(&base.title),
+        "author" => Some(&base.author),
+        "email" => Some(&base.email),
+        "description" => Some(&base.description),
+        "url" => base.url.as_deref(),
+        "language" => Some(&base.language),
+        "copyright" => Some(&base.copyright),
+        _ => None,
+    }
+}
+
+/// Scalar `[extra]` value at a dotted path, for head placeholders.
+fn extra_value(config: &SiteConfig, key: &str) -> Option<String> {
+    let mut parts = key.split('.');
+    let mut value = config.extra.get(parts.next()?)?;
+    for part in parts {
+        value = value.get(part)?;
+    }
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+            Some(value.to_string())
+        }
+        toml::Value::Datetime(dt) => Some(dt.to_string()),
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
+}
+
+/// SRI hash of a local `[build.head]` asset, if `[build.head].sri` is set.
+fn asset_integrity(path: &Path, config: &SiteConfig) -> Result<Option<String>> {
+    if !config.build.head.sri {
+        return Ok(None);
+    }
+    compute_sri(&resolve_asset_source(path, config)).map(Some)
+}
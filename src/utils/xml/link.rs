@@ -1,7 +1,9 @@
+use crate::compiler::assets::copy_referenced;
 use crate::config::SiteConfig;
 use crate::utils::slug::{slugify_fragment, slugify_path};
 use anyhow::Result;
 use std::borrow::Cow;
+use std::path::{Component, Path, PathBuf};
 use std::str;
 
 use super::assets::is_asset_link;
@@ -23,24 +25,54 @@ use super::assets::is_asset_link;
 ///
 /// # Arguments
 ///
-/// * `is_source_index` - Whether the source was `index.typ`. Affects relative path resolution:
-///   - `index.typ` → `dir/index.html` (same level, no adjustment)
-///   - `foo.typ` → `foo/index.html` (one level deeper, needs `../` prefix)
+/// * `link_depth` - Number of `../` prepended to relative links
+///   (see [`relative_link_depth`])
 pub fn process_link_value(
     value: &[u8],
     config: &SiteConfig,
-    is_source_index: bool,
+    link_depth: usize,
 ) -> Result<Cow<'static, [u8]>> {
     let value_str = str::from_utf8(value)?;
     let processed: String = match value_str.bytes().next() {
         Some(b'/') => process_absolute_link(value_str, config)?,
         Some(b'#') => process_fragment_link(value_str, config)?,
-        Some(_) => process_relative_link(value_str, is_source_index)?.into_owned(),
+        Some(_) => process_relative_link(value_str, link_depth)?.into_owned(),
         None => anyhow::bail!("empty link URL found in typst file"),
     };
     Ok(Cow::Owned(processed.into_bytes()))
 }
 
+/// Redirect a relative link to a file outside `content` to its copy
+/// (`[build].auto_copy_referenced`).
+///
+/// `../photos/cat.jpg?v=2` in `content/index.typ` → `/photos/cat.jpg?v=2`,
+/// with the file copied to `<output>/photos/cat.jpg`. Returns `None` for
+/// other links, which go through [`process_link_value`].
+pub fn process_referenced_link(
+    value: &str,
+    source: &Path,
+    config: &SiteConfig,
+) -> Result<Option<String>> {
+    if !config.build.auto_copy_referenced
+        || value.starts_with(['/', '#'])
+        || is_external_link(value)
+    {
+        return Ok(None);
+    }
+
+    let (path, _, _) = split_path_query_fragment(value);
+    let Some(dir) = source.parent() else {
+        return Ok(None);
+    };
+    let Some(rel) = copy_referenced(&lexical_normalize(&dir.join(path)), config)? else {
+        return Ok(None);
+    };
+
+    let mut url = config.paths().url_for_rel_path(rel);
+    url.push_str(&value[path.len()..]);
+    Ok(Some(url))
+}
+
 /// Process absolute links (starting with `/` or `//`).
 ///
 /// # Examples
@@ -49,24 +81,39 @@ pub fn process_link_value(
 /// |-------|----------------------|
 /// | `/about` | `/about` |
 /// | `/about#team` | `/about#team` (fragment slugified) |
+/// | `/about?tab=1#team` | `/about?tab=1#team` (query kept as-is) |
+/// | `/images/a.png?v=2#crop` | `/images/a.png?v=2#crop` (asset: query and fragment kept) |
 /// | `//example.com` | `//example.com` (protocol-relative) |
 #[allow(clippy::unnecessary_wraps)] // Result for API consistency
 pub fn process_absolute_link(value: &str, config: &SiteConfig) -> Result<String> {
+    // Protocol-relative links point to another host: keep verbatim
+    if value.starts_with("//") {
+        return Ok(value.to_string());
+    }
+
     let paths = config.paths();
 
+    // Split off query and fragment so only the path gets slugified
+    let (path, query, fragment) = split_path_query_fragment(value);
+
     // Asset links: just add prefix, no slugification
-    if is_asset_link(value, config) {
-        let path = value.trim_start_matches('/');
-        return Ok(paths.url_for_rel_path(path));
+    if is_asset_link(path, config) {
+        let mut result = paths.url_for_rel_path(path.trim_start_matches('/'));
+        result.push_str(&value[path.len()..]);
+        return Ok(result);
     }
 
-    // Split path and fragment
-    let (path, fragment) = split_path_fragment(value);
     let path = path.trim_start_matches('/');
 
     // Build URL with proper prefix handling
     let mut result = build_prefixed_url(path, config);
 
+    // Reattach the query unchanged
+    if !query.is_empty() {
+        result.push('?');
+        result.push_str(query);
+    }
+
     // Append slugified fragment if present
     if !fragment.is_empty() {
         result.push('#');
@@ -161,13 +208,16 @@ fn path_starts_with_segment(path: &str, segment: &str) -> bool {
 // Link Parsing Utilities
 // ============================================================================
 
-/// Split a URL into path and fragment parts.
+/// Split a URL into path, query and fragment parts.
 ///
 /// # Returns
-/// A tuple of (path, fragment) where fragment is empty string if no `#` found.
+/// A tuple of (path, query, fragment), without the `?` and `#` separators.
+/// Missing parts are empty strings. A `?` after the `#` belongs to the fragment.
 #[inline]
-fn split_path_fragment(url: &str) -> (&str, &str) {
-    url.split_once('#').unwrap_or((url, ""))
+fn split_path_query_fragment(url: &str) -> (&str, &str, &str) {
+    let (rest, fragment) = url.split_once('#').unwrap_or((url, ""));
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    (path, query, fragment)
 }
 
 /// Process fragment links (starting with `#`).
@@ -176,37 +226,74 @@ pub fn process_fragment_link(value: &str, config: &SiteConfig) -> Result<String>
     Ok(format!("#{}", slugify_fragment(&value[1..], config)))
 }
 
-/// Process relative links (starting with `./`, `../`, or no prefix).
+/// Number of `../` needed to resolve source-relative links from the output page.
 ///
-/// # Path Adjustment Logic
+/// Relative links are written relative to the source `.typ` file, but the
+/// page is written to `<slug>/index.html`, which may sit deeper than its
+/// source. The difference in directory depth (output page vs output root,
+/// source vs content root) is the number of levels to climb.
 ///
-/// | Source File | Output Structure | Relative Path Adjustment |
-/// |-------------|------------------|--------------------------|
-/// | `foo/index.typ` | `foo/index.html` | None (same level) |
-/// | `foo.typ` | `foo/index.html` | Prepend `../` (one level deeper) |
+/// | Source File | Output | Depth |
+/// |-------------|--------|-------|
+/// | `index.typ` | `index.html` | 0 |
+/// | `a/b/index.typ` | `a/b/index/index.html` | 1 |
+/// | `a/b.typ` | `a/b/index.html` | 1 |
+pub fn relative_link_depth(source: &Path, html: &Path, config: &SiteConfig) -> usize {
+    fn dir_depth(path: &Path, root: &Path) -> usize {
+        path.parent()
+            .and_then(|dir| dir.strip_prefix(root).ok())
+            .map_or(0, |rel| rel.components().count())
+    }
+
+    let html_depth = dir_depth(html, &config.paths().output_dir());
+    let source_depth = dir_depth(source, &config.build.content);
+    html_depth.saturating_sub(source_depth)
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+pub fn lexical_normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                result.pop();
+            }
+            other => result.push(other),
+        }
+    }
+    result
+}
+
+/// Process relative links (starting with `./`, `../`, or no prefix).
+///
+/// Prepends `link_depth` levels of `../` (see [`relative_link_depth`]).
 ///
 /// # Examples
 ///
-/// For `index.typ`:
+/// For `index.typ` (depth 0):
 /// - `./img.png` → `./img.png` (no change)
 /// - `../doc.pdf` → `../doc.pdf` (no change)
 ///
-/// For `page.typ`:
+/// For `page.typ` (depth 1):
 /// - `./img.png` → `../img.png` (adjusted for extra directory level)
 /// - `../doc.pdf` → `../../doc.pdf` (adjusted)
 #[allow(clippy::unnecessary_wraps)] // Result for API consistency
-pub fn process_relative_link(value: &str, is_source_index: bool) -> Result<Cow<'_, str>> {
-    Ok(if is_external_link(value) || is_source_index {
-        // External links or index.typ: unchanged
+pub fn process_relative_link(value: &str, link_depth: usize) -> Result<Cow<'_, str>> {
+    Ok(if is_external_link(value) || link_depth == 0 {
+        // External links or same-level pages: unchanged
         Cow::Borrowed(value)
     } else {
-        // Non-index files: output is one level deeper, prepend ../
-        Cow::Owned(format!("../{value}"))
+        // Output is deeper than the source, climb back up
+        Cow::Owned(format!("{}{value}", "../".repeat(link_depth)))
     })
 }
 
 /// Check if a link is external (has a scheme like http:, mailto:, etc.)
 ///
+/// This includes non-navigational schemes (`data:`, `blob:`, `javascript:`),
+/// which are passed through untouched rather than rewritten as relative paths.
+///
 /// A valid scheme must:
 /// - Have at least 1 character before the colon
 /// - Only contain ASCII alphanumeric or `+`, `-`, `.`
@@ -232,20 +319,17 @@ mod tests {
     #[test]
     fn test_relative_link_index_no_adjustment() {
         // index.typ: output is at same level, no adjustment needed
+        assert_eq!(process_relative_link("./img.png", 0).unwrap(), "./img.png");
         assert_eq!(
-            process_relative_link("./img.png", true).unwrap(),
-            "./img.png"
-        );
-        assert_eq!(
-            process_relative_link("../doc.pdf", true).unwrap(),
+            process_relative_link("../doc.pdf", 0).unwrap(),
             "../doc.pdf"
         );
         assert_eq!(
-            process_relative_link("asset/logo.svg", true).unwrap(),
+            process_relative_link("asset/logo.svg", 0).unwrap(),
             "asset/logo.svg"
         );
         assert_eq!(
-            process_relative_link("../../up/up.txt", true).unwrap(),
+            process_relative_link("../../up/up.txt", 0).unwrap(),
             "../../up/up.txt"
         );
     }
@@ -254,36 +338,80 @@ mod tests {
     fn test_relative_link_non_index_prepend() {
         // Non-index.typ: output is one level deeper, prepend ../
         assert_eq!(
-            process_relative_link("./img.png", false).unwrap(),
+            process_relative_link("./img.png", 1).unwrap(),
             ".././img.png"
         );
         assert_eq!(
-            process_relative_link("../doc.pdf", false).unwrap(),
+            process_relative_link("../doc.pdf", 1).unwrap(),
             "../../doc.pdf"
         );
         assert_eq!(
-            process_relative_link("asset/logo.svg", false).unwrap(),
+            process_relative_link("asset/logo.svg", 1).unwrap(),
             "../asset/logo.svg"
         );
     }
 
+    #[test]
+    fn test_relative_link_depth() {
+        let mut config = SiteConfig::default();
+        config.build.content = "content".into();
+        config.build.output = "public".into();
+        let depth = |source: &str, html: &str| {
+            relative_link_depth(Path::new(source), Path::new(html), &config)
+        };
+
+        // Root page, sibling link stays as-is
+        assert_eq!(depth("content/index.typ", "public/index.html"), 0);
+        assert_eq!(
+            process_relative_link("sibling.png", 0).unwrap(),
+            "sibling.png"
+        );
+
+        // `a/b.typ` → `a/b/index.html`: one level deeper than its source
+        assert_eq!(depth("content/a/b.typ", "public/a/b/index.html"), 1);
+        // `a/b/index.typ` → `a/b/index/index.html`
+        assert_eq!(
+            depth("content/a/b/index.typ", "public/a/b/index/index.html"),
+            1
+        );
+        // ... or `a/b/index.html` under `[build].index_names`, staying level
+        assert_eq!(depth("content/a/b/index.typ", "public/a/b/index.html"), 0);
+        // Output two levels below its source directory
+        assert_eq!(depth("content/b.typ", "public/a/b/index.html"), 2);
+        assert_eq!(
+            process_relative_link("sibling.png", 2).unwrap(),
+            "../../sibling.png"
+        );
+
+        // Under path_prefix, depth is measured from the prefixed output dir
+        config.build.path_prefix = "blog".into();
+        assert_eq!(
+            relative_link_depth(
+                Path::new("content/a/b.typ"),
+                Path::new("public/blog/a/b/index.html"),
+                &config
+            ),
+            1
+        );
+    }
+
     #[test]
     fn test_relative_link_external_unchanged() {
-        // External links: unchanged regardless of is_source_index
+        // External links: unchanged regardless of link_depth
         assert_eq!(
-            process_relative_link("https://example.com", true).unwrap(),
+            process_relative_link("https://example.com", 0).unwrap(),
             "https://example.com"
         );
         assert_eq!(
-            process_relative_link("https://example.com", false).unwrap(),
+            process_relative_link("https://example.com", 1).unwrap(),
             "https://example.com"
         );
         assert_eq!(
-            process_relative_link("mailto:user@example.com", true).unwrap(),
+            process_relative_link("mailto:user@example.com", 0).unwrap(),
             "mailto:user@example.com"
         );
         assert_eq!(
-            process_relative_link("tel:+1234567890", false).unwrap(),
+            process_relative_link("tel:+1234567890", 1).unwrap(),
             "tel:+1234567890"
         );
     }
@@ -292,6 +420,37 @@ mod tests {
     // is_external_link tests
     // ========================================================================
 
+    #[test]
+    fn test_scheme_uris_pass_through() {
+        let config = SiteConfig::default();
+        for uri in [
+            "data:image/png;base64,iVBORw0KGgo=",
+            "data:image/svg+xml,%3Csvg%3E%3C/svg%3E",
+            "blob:https://example.com/550e8400",
+            "javascript:void(0)",
+        ] {
+            let out = process_link_value(uri.as_bytes(), &config, 2).unwrap();
+            assert_eq!(str::from_utf8(&out).unwrap(), uri);
+        }
+    }
+
+    #[test]
+    fn test_protocol_relative_and_contact_links_verbatim() {
+        let mut config = SiteConfig::default();
+        config.build.path_prefix = "blog".into();
+        for link in [
+            "//cdn.example.com/x.js",
+            "//cdn.example.com/Some Path/#Frag",
+            "mailto:me@x.com",
+            "tel:+1234",
+        ] {
+            for depth in [0, 2] {
+                let out = process_link_value(link.as_bytes(), &config, depth).unwrap();
+                assert_eq!(str::from_utf8(&out).unwrap(), link);
+            }
+        }
+    }
+
     #[test]
     fn test_is_external_link_schemes() {
         // Common protocols
@@ -327,6 +486,30 @@ mod tests {
     // process_fragment_link tests
     // ========================================================================
 
+    #[test]
+    fn test_split_path_query_fragment() {
+        assert_eq!(split_path_query_fragment("/a/b"), ("/a/b", "", ""));
+        assert_eq!(split_path_query_fragment("/a?v=2"), ("/a", "v=2", ""));
+        assert_eq!(split_path_query_fragment("/a#crop"), ("/a", "", "crop"));
+        assert_eq!(
+            split_path_query_fragment("/a?v=2#crop?x"),
+            ("/a", "v=2", "crop?x")
+        );
+    }
+
+    #[test]
+    fn test_absolute_link_query_and_fragment() {
+        let config = SiteConfig::default();
+        let link = |v| process_absolute_link(v, &config).unwrap();
+        assert_eq!(
+            link("/search?q=Hello World&page=2"),
+            "/search?q=Hello World&page=2"
+        );
+        assert_eq!(link("/about#team"), "/about#team");
+        assert_eq!(link("/about?tab=1#team"), "/about?tab=1#team");
+        assert_eq!(link("/about?#team"), "/about#team");
+    }
+
     #[test]
     fn test_fragment_link_simple() {
         let config = SiteConfig::default();
@@ -357,30 +540,30 @@ mod tests {
         let config = SiteConfig::default();
 
         // Absolute path -> process_absolute_link
-        let result = process_link_value(b"/about", &config, true).unwrap();
+        let result = process_link_value(b"/about", &config, 0).unwrap();
         assert!(result.starts_with(b"/"));
 
         // Fragment -> process_fragment_link
-        let result = process_link_value(b"#section", &config, true).unwrap();
+        let result = process_link_value(b"#section", &config, 0).unwrap();
         assert!(result.starts_with(b"#"));
 
         // External link -> unchanged
-        let result = process_link_value(b"https://example.com", &config, true).unwrap();
+        let result = process_link_value(b"https://example.com", &config, 0).unwrap();
         assert_eq!(&*result, b"https://example.com");
 
         // Relative path (index.typ) -> no adjustment
-        let result = process_link_value(b"./img.png", &config, true).unwrap();
+        let result = process_link_value(b"./img.png", &config, 0).unwrap();
         assert_eq!(&*result, b"./img.png");
 
         // Relative path (non-index.typ) -> prepend ../
-        let result = process_link_value(b"./img.png", &config, false).unwrap();
+        let result = process_link_value(b"./img.png", &config, 1).unwrap();
         assert_eq!(&*result, b".././img.png");
     }
 
     #[test]
     fn test_process_link_value_empty_error() {
         let config = SiteConfig::default();
-        let result = process_link_value(b"", &config, true);
+        let result = process_link_value(b"", &config, 0);
         assert!(result.is_err());
     }
 
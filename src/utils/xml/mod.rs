@@ -2,9 +2,11 @@
 
 pub mod assets;
 pub mod common;
+pub mod dimensions;
 pub mod head;
 pub mod link;
 pub mod processor;
+pub mod summary;
 
 // Re-export for backward compatibility and ease of use
-pub use processor::process_html;
+pub use processor::{process_html, process_page_html};
@@ -1,37 +1,66 @@
+use crate::compiler::meta::PageMeta;
 use crate::config::SiteConfig;
+use crate::report::{BUILD_STATS, Stage};
 use crate::utils::slug::slugify_fragment;
 use crate::utils::svg::{HtmlContext, Svg, compress_svgs_parallel, extract_svg_element};
 use anyhow::Result;
 use quick_xml::{
     Reader, Writer,
-    events::{BytesEnd, BytesStart, Event},
+    events::{BytesEnd, BytesStart, BytesText, Event},
 };
+use std::borrow::Cow;
 use std::io::Cursor;
 use std::path::Path;
 use std::str;
+use std::time::Instant;
 
 use super::common::{XmlWriter, create_xml_reader, rebuild_elem, rebuild_elem_try};
-use super::head::write_head_content;
-use super::link::process_link_value;
+use super::dimensions::image_dimensions;
+use super::head::{write_head_content, write_head_start};
+use super::link::{process_link_value, process_referenced_link};
+use super::summary::is_more_marker;
 
 pub fn process_html(
+    source: &Path,
     html_path: &Path,
     content: &[u8],
     config: &SiteConfig,
-    is_source_index: bool,
+    link_depth: usize,
 ) -> Result<Vec<u8>> {
-    let mut ctx = HtmlContext::new(config, html_path, is_source_index);
+    let mut ctx = HtmlContext::new(config, html_path, link_depth);
+    ctx.source = Some(source);
+    process_with_context(&mut ctx, content)
+}
+
+/// [`process_html`] for a content page, whose metadata feeds `<head>` content
+/// such as `[build.head].json_ld`.
+pub fn process_page_html(
+    page: &PageMeta,
+    content: &[u8],
+    config: &SiteConfig,
+    link_depth: usize,
+) -> Result<Vec<u8>> {
+    let mut ctx = HtmlContext::new(config, &page.paths.html, link_depth);
+    ctx.source = Some(&page.paths.source);
+    ctx.page = Some(page);
+    process_with_context(&mut ctx, content)
+}
+
+fn process_with_context(ctx: &mut HtmlContext<'_>, content: &[u8]) -> Result<Vec<u8>> {
     let mut writer = Writer::new(Cursor::new(Vec::with_capacity(content.len())));
     let mut reader = create_xml_reader(content);
     let mut svgs = Vec::new();
 
     loop {
         match reader.read_event() {
+            Ok(event) if is_more_marker(&event) => {
+                write_more_anchor(&event, &mut reader, &mut writer)?;
+            }
             Ok(Event::Start(elem)) => {
-                handle_start_element(&elem, &mut reader, &mut writer, &mut ctx, &mut svgs)?;
+                handle_start_element(&elem, &mut reader, &mut writer, ctx, &mut svgs)?;
             }
             Ok(Event::End(elem)) => {
-                handle_end_element(&elem, &mut writer, config)?;
+                handle_end_element(&elem, &mut writer, ctx)?;
             }
             Ok(Event::Eof) => break,
             Ok(event) => writer.write_event(event)?,
@@ -45,12 +74,30 @@ pub fn process_html(
 
     // Compress SVGs in parallel
     if ctx.extract_svg && !svgs.is_empty() {
-        compress_svgs_parallel(&svgs, html_path, config)?;
+        let start = Instant::now();
+        compress_svgs_parallel(&svgs, ctx.html_path, ctx.config)?;
+        BUILD_STATS.record(Stage::Svg, start.elapsed());
     }
 
     Ok(writer.into_inner().into_inner())
 }
 
+/// Replace a "read more" marker with a `<span id="more">` link target.
+fn write_more_anchor(
+    event: &Event<'_>,
+    reader: &mut Reader<&[u8]>,
+    writer: &mut XmlWriter,
+) -> Result<()> {
+    if let Event::Start(elem) = event {
+        reader.read_to_end(elem.name())?;
+    }
+    writer.write_event(Event::Start(
+        BytesStart::new("span").with_attributes([("id", "more")]),
+    ))?;
+    writer.write_event(Event::End(BytesEnd::new("span")))?;
+    Ok(())
+}
+
 fn handle_start_element(
     elem: &BytesStart<'_>,
     reader: &mut Reader<&[u8]>,
@@ -59,17 +106,18 @@ fn handle_start_element(
     svgs: &mut Vec<Svg>,
 ) -> Result<()> {
     match elem.name().as_ref() {
-        b"html" => write_html_with_lang(elem, writer, ctx.config)?,
+        b"html" => write_html_with_lang(elem, writer, ctx.config, ctx.page)?,
+        b"head" => write_head_start(elem, writer, ctx.config)?,
         b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => {
-            write_heading_with_slugified_id(elem, writer, ctx.config)?;
+            ctx.heading_id = write_heading_with_slugified_id(elem, writer, ctx.config)?;
         }
         b"svg" if ctx.extract_svg => {
             if let Some(svg) = extract_svg_element(reader, writer, elem, ctx)? {
                 svgs.push(svg);
             }
         }
-        b"img" if ctx.config.build.css.auto_enhance => {
-            write_img_with_color_invert(elem, writer, ctx)?;
+        b"img" if ctx.config.build.css.auto_enhance || ctx.config.build.image_dimensions => {
+            write_img(elem, writer, ctx)?;
         }
         _ => write_element_with_processed_links(elem, writer, ctx)?,
     }
@@ -79,42 +127,79 @@ fn handle_start_element(
 fn handle_end_element(
     elem: &BytesEnd<'_>,
     writer: &mut Writer<Cursor<Vec<u8>>>,
-    config: &SiteConfig,
+    ctx: &mut HtmlContext<'_>,
 ) -> Result<()> {
     match elem.name().as_ref() {
-        b"head" => write_head_content(writer, config)?,
+        b"head" => write_head_content(writer, ctx.config, ctx.page)?,
+        b"h1" | b"h2" | b"h3" | b"h4" | b"h5" | b"h6" => {
+            if ctx.config.build.heading_anchors
+                && let Some(id) = ctx.heading_id.take()
+            {
+                write_heading_anchor(writer, &id)?;
+            }
+            writer.write_event(Event::End(elem.to_owned()))?;
+        }
         _ => writer.write_event(Event::End(elem.to_owned()))?,
     }
     Ok(())
 }
 
-/// Write `<html>` element with `lang` attribute.
+/// Write `<html>` element with `lang` and, if known, `dir` attributes.
+///
+/// A page's `direction` metadata overrides `[base].direction`.
 pub fn write_html_with_lang(
     elem: &BytesStart<'_>,
     writer: &mut XmlWriter,
     config: &SiteConfig,
+    page: Option<&PageMeta>,
 ) -> Result<()> {
+    let language = config.base.language.as_str();
+    let direction = page
+        .and_then(|p| p.content_meta.as_ref())
+        .and_then(|c| c.direction)
+        .unwrap_or(config.base.direction);
+
     let mut elem = elem.to_owned();
-    elem.push_attribute(("lang", config.base.language.as_str()));
+    elem.push_attribute(("lang", language));
+    if let Some(dir) = direction.resolve(language) {
+        elem.push_attribute(("dir", dir));
+    }
     writer.write_event(Event::Start(elem))?;
     Ok(())
 }
 
 /// Write heading element with slugified `id` attribute.
+///
+/// Returns the slugified id, if the heading has one.
 pub fn write_heading_with_slugified_id(
     elem: &BytesStart<'_>,
     writer: &mut XmlWriter,
     config: &SiteConfig,
-) -> Result<()> {
+) -> Result<Option<String>> {
+    let mut id = None;
     let new_elem = rebuild_elem(elem, |key, value| {
         if key == b"id" {
             let v = str::from_utf8(value.as_ref()).unwrap_or_default();
-            slugify_fragment(v, config).into_bytes().into()
+            let slug = slugify_fragment(v, config);
+            id = Some(slug.clone());
+            slug.into_bytes().into()
         } else {
             value.into_owned().into()
         }
     });
     writer.write_event(Event::Start(new_elem))?;
+    Ok(id)
+}
+
+/// Write `<a class="heading-anchor" href="#id">¶</a>` (`[build].heading_anchors`).
+fn write_heading_anchor(writer: &mut XmlWriter, id: &str) -> Result<()> {
+    let href = format!("#{id}");
+    let mut anchor = BytesStart::new("a");
+    anchor.push_attribute(("class", "heading-anchor"));
+    anchor.push_attribute(("href", href.as_str()));
+    writer.write_event(Event::Start(anchor))?;
+    writer.write_event(Event::Text(BytesText::new("¶")))?;
+    writer.write_event(Event::End(BytesEnd::new("a")))?;
     Ok(())
 }
 
@@ -124,38 +209,90 @@ pub fn write_element_with_processed_links(
     writer: &mut XmlWriter,
     ctx: &HtmlContext<'_>,
 ) -> Result<()> {
-    let new_elem = rebuild_elem_try(elem, |key, value| {
-        if matches!(key, b"href" | b"src") {
-            process_link_value(&value, ctx.config, ctx.is_source_index)
-        } else {
-            Ok(value.into_owned().into())
-        }
-    })?;
+    let new_elem = rebuild_elem_try(elem, |key, value| process_link_attr(key, value, ctx))?;
     writer.write_event(Event::Start(new_elem))?;
     Ok(())
 }
 
-/// Write `<img>` element with `color-invert` class for SVG dark mode adaptation.
+/// Process `href`/`src` attribute values, keep others unchanged.
+fn process_link_attr(
+    key: &[u8],
+    value: Cow<'_, [u8]>,
+    ctx: &HtmlContext<'_>,
+) -> Result<Cow<'static, [u8]>> {
+    if matches!(key, b"href" | b"src") {
+        process_link(&value, ctx)
+    } else {
+        Ok(value.into_owned().into())
+    }
+}
+
+/// Process a link value, pointing links to `auto_copy_referenced` files at
+/// their copies.
+fn process_link(value: &[u8], ctx: &HtmlContext<'_>) -> Result<Cow<'static, [u8]>> {
+    if let Some(source) = ctx.source
+        && let Some(url) = process_referenced_link(str::from_utf8(value)?, source, ctx.config)?
+    {
+        return Ok(Cow::Owned(url.into_bytes()));
+    }
+    process_link_value(value, ctx.config, ctx.link_depth)
+}
+
+/// Write `<img>` element with processed `src`.
 ///
-/// Only adds `color-invert` to SVG images (`.svg`, `.svgz`) for proper dark mode support.
-/// Non-SVG images (photos, etc.) are left unchanged to preserve their original colors.
-/// Also processes `src` attribute for path normalization.
-pub fn write_img_with_color_invert(
+/// - `color-invert` class for SVG images (when `auto_enhance` is enabled)
+/// - Intrinsic `width`/`height` for local raster images (when `image_dimensions` is
+///   enabled and neither attribute is already set)
+pub fn write_img(
     elem: &BytesStart<'_>,
     writer: &mut XmlWriter,
     ctx: &HtmlContext<'_>,
 ) -> Result<()> {
-    // Check if this is an SVG image
-    let is_svg = elem.attributes().filter_map(|a| a.ok()).any(|attr| {
-        attr.key.as_ref() == b"src" && {
-            let src = str::from_utf8(attr.value.as_ref()).unwrap_or_default();
-            src.ends_with(".svg") || src.ends_with(".svgz")
+    let src = elem
+        .attributes()
+        .filter_map(|a| a.ok())
+        .find(|attr| attr.key.as_ref() == b"src")
+        .map(|attr| String::from_utf8_lossy(attr.value.as_ref()).into_owned())
+        .unwrap_or_default();
+
+    let mut new_elem = if ctx.config.build.css.auto_enhance {
+        img_with_color_invert(elem, &src, ctx)?
+    } else {
+        rebuild_elem_try(elem, |key, value| process_link_attr(key, value, ctx))?
+    };
+
+    if ctx.config.build.image_dimensions {
+        let has_size = elem
+            .attributes()
+            .filter_map(|a| a.ok())
+            .any(|attr| matches!(attr.key.as_ref(), b"width" | b"height"));
+        if !has_size
+            && let Some((width, height)) =
+                image_dimensions(&src, ctx.html_path, ctx.config, ctx.link_depth)
+        {
+            new_elem.push_attribute(("width", width.to_string().as_str()));
+            new_elem.push_attribute(("height", height.to_string().as_str()));
         }
-    });
+    }
+
+    writer.write_event(Event::Start(new_elem))?;
+    Ok(())
+}
 
+/// Rebuild `<img>` with `color-invert` class for SVG dark mode adaptation.
+///
+/// Only adds `color-invert` to SVG images (`.svg`, `.svgz`) for proper dark mode support.
+/// Non-SVG images (photos, etc.) are left unchanged to preserve their original colors.
+/// Also processes `src` attribute for path normalization.
+fn img_with_color_invert(
+    elem: &BytesStart<'_>,
+    src: &str,
+    ctx: &HtmlContext<'_>,
+) -> Result<BytesStart<'static>> {
     // For non-SVG images, just process links normally
+    let is_svg = src.ends_with(".svg") || src.ends_with(".svgz");
     if !is_svg {
-        return write_element_with_processed_links(elem, writer, ctx);
+        return rebuild_elem_try(elem, |key, value| process_link_attr(key, value, ctx));
     }
 
     // For SVG images, add color-invert class
@@ -163,7 +300,7 @@ pub fn write_img_with_color_invert(
 
     let new_elem = rebuild_elem_try(elem, |key, value| {
         match key {
-            b"src" => process_link_value(&value, ctx.config, ctx.is_source_index),
+            b"src" => process_link(&value, ctx),
             b"class" => {
                 has_class = true;
                 // Append color-invert to existing classes
@@ -180,14 +317,389 @@ pub fn write_img_with_color_invert(
     })?;
 
     // Add class attribute if not present
-    let new_elem = if has_class {
+    Ok(if has_class {
         new_elem
     } else {
         let mut elem = new_elem;
         elem.push_attribute(("class", "color-invert"));
         elem
-    };
+    })
+}
 
-    writer.write_event(Event::Start(new_elem))?;
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::meta::{ContentMeta, PagePaths};
+    use crate::config::{ExtractSvgType, SlugMode};
+
+    /// A `posts/hello` page with the given front matter.
+    fn page_with_meta(meta: ContentMeta) -> PageMeta {
+        PageMeta {
+            paths: PagePaths {
+                source: "/content/posts/hello.typ".into(),
+                html: "/out/posts/hello/index.html".into(),
+                relative: "posts/hello".to_string(),
+                url_path: "/posts/hello/".to_string(),
+                full_url: "https://example.com/posts/hello/".to_string(),
+            },
+            lastmod: None,
+            content_meta: Some(meta),
+            compiled_html: None,
+        }
+    }
+
+    fn render(config: &SiteConfig) -> String {
+        let html = br#"<html><head></head><body><h2 id="foo">Foo</h2><p>text</p></body></html>"#;
+        let out = process_html(
+            Path::new("/content/index.typ"),
+            Path::new("/out/index.html"),
+            html,
+            config,
+            0,
+        )
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_heading_anchors() {
+        let mut config = SiteConfig::default();
+        let out = render(&config);
+        assert!(out.contains(r#"<h2 id="foo">Foo</h2>"#), "{out}");
+        assert!(!out.contains("heading-anchor"), "{out}");
+
+        config.build.heading_anchors = true;
+        let out = render(&config);
+        assert!(
+            out.contains(r##"<h2 id="foo">Foo<a class="heading-anchor" href="#foo">¶</a></h2>"##),
+            "{out}"
+        );
+        // Only headings get an anchor
+        assert_eq!(out.matches("heading-anchor").count(), 1);
+    }
+
+    #[test]
+    fn test_base_href_first_in_head() {
+        let mut config = SiteConfig::default();
+        let html = br#"<html><head><meta charset="utf-8"/><link rel="icon" href="a.png"/></head><body></body></html>"#;
+        let render = |config: &SiteConfig| {
+            let out = process_html(
+                Path::new("/content/index.typ"),
+                Path::new("/out/index.html"),
+                html,
+                config,
+                0,
+            )
+            .unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert!(!render(&config).contains("<base"));
+
+        // Precedes typst's own head elements and their relative URLs
+        config.build.head.base_href = Some("/docs/".into());
+        let out = render(&config);
+        assert!(
+            out.contains(r#"<head><base href="/docs/"/><meta charset="utf-8"/>"#),
+            "{out}"
+        );
+        assert_eq!(out.matches("<base").count(), 1, "{out}");
+    }
+
+    #[test]
+    fn test_head_elements_interpolate_config() {
+        let mut config = SiteConfig::default();
+        config.base.title = "Tom & Jerry".to_string();
+        config
+            .extra
+            .insert("analytics_id".to_string(), "G-12\"34".into());
+        config.build.head.elements = vec![
+            r#"<script data-id="{{ extra.analytics_id }}" src="/a.js"></script>"#.to_string(),
+            r#"<meta name="x" content="{{base.title}} {{ extra.missing }}"/>"#.to_string(),
+        ];
+        let html = b"<html><head></head><body></body></html>";
+        let out = process_html(
+            Path::new("/content/index.typ"),
+            Path::new("/out/index.html"),
+            html,
+            &config,
+            0,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.contains(r#"<script data-id="G-12&quot;34" src="/a.js"></script>"#),
+            "{out}"
+        );
+        // Unknown keys are left as written
+        assert!(
+            out.contains(r#"content="Tom &amp; Jerry {{ extra.missing }}""#),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn test_html_dir_attribute() {
+        use crate::config::TextDirection;
+
+        let mut config = SiteConfig::default();
+        assert!(!render(&config).contains("dir="));
+
+        // Inferred from an RTL language code
+        config.base.language = "ar".to_string();
+        assert!(render(&config).contains(r#"<html lang="ar" dir="rtl">"#));
+
+        // Page metadata overrides the site direction
+        let page = page_with_meta(ContentMeta {
+            direction: Some(TextDirection::Ltr),
+            ..Default::default()
+        });
+        let html = b"<html><head></head><body></body></html>";
+        let out = process_page_html(&page, html, &config, 0).unwrap();
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .contains(r#"<html lang="ar" dir="ltr">"#)
+        );
+    }
+
+    #[test]
+    fn test_json_ld_blog_posting() {
+        let mut config = SiteConfig::default();
+        config.build.head.json_ld = true;
+        let page = page_with_meta(ContentMeta {
+            title: Some("Hello </script> World".to_string()),
+            date: Some("2024-01-15".to_string()),
+            authors: vec!["Alice".to_string()],
+            summary: Some("Fish &amp; <em>chips</em>".to_string()),
+            ..Default::default()
+        });
+        let html = b"<html><head></head><body></body></html>";
+        let out = process_page_html(&page, html, &config, 0).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let start = out.find(r#"<script type="application/ld+json">"#).unwrap();
+        let json = out[start..].split_once('>').unwrap().1;
+        let json = json.split_once("</script>").unwrap().0;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(value["@type"], "BlogPosting");
+        assert_eq!(value["headline"], "Hello </script> World");
+        assert_eq!(value["datePublished"], "2024-01-15");
+        assert_eq!(value["author"][0]["name"], "Alice");
+        assert_eq!(value["description"], "Fish & chips");
+        assert_eq!(
+            value["mainEntityOfPage"]["@id"],
+            "https://example.com/posts/hello/"
+        );
+
+        // Nothing is emitted unless enabled
+        config.build.head.json_ld = false;
+        let out = process_page_html(&page, html, &config, 0).unwrap();
+        assert!(!String::from_utf8(out).unwrap().contains("ld+json"));
+    }
+
+    #[test]
+    fn test_generator_and_author_meta() {
+        let mut config = SiteConfig::default();
+        config.base.author = "Site Owner".to_string();
+        let mut page = page_with_meta(ContentMeta {
+            authors: vec!["Alice".to_string(), "Bob".to_string()],
+            ..Default::default()
+        });
+        let html = b"<html><head></head><body></body></html>";
+        let out = String::from_utf8(process_page_html(&page, html, &config, 0).unwrap()).unwrap();
+        assert!(out.contains(&format!(
+            r#"<meta name="generator" content="tola-ssg {}"/>"#,
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(out.contains(r#"<meta name="author" content="Alice, Bob"/>"#));
+
+        // Without page authors `[base].author` is used; the generator can be turned off
+        page.content_meta = None;
+        config.build.head.generator_meta = false;
+        let out = String::from_utf8(process_page_html(&page, html, &config, 0).unwrap()).unwrap();
+        assert!(!out.contains(r#"name="generator""#));
+        assert!(out.contains(r#"<meta name="author" content="Site Owner"/>"#));
+    }
+
+    #[test]
+    fn test_noindex_robots_meta() {
+        let config = SiteConfig::default();
+        let mut page = page_with_meta(ContentMeta {
+            noindex: true,
+            ..Default::default()
+        });
+        let html = b"<html><head></head><body></body></html>";
+        let out = String::from_utf8(process_page_html(&page, html, &config, 0).unwrap()).unwrap();
+        assert!(out.contains(r#"<meta name="robots" content="noindex, nofollow"/>"#));
+
+        page.content_meta = None;
+        let out = String::from_utf8(process_page_html(&page, html, &config, 0).unwrap()).unwrap();
+        assert!(!out.contains(r#"name="robots""#));
+    }
+
+    #[test]
+    fn test_fragment_no_preserves_heading_id() {
+        let mut config = SiteConfig::default();
+        config.build.slug.fragment = SlugMode::No;
+        config.build.heading_anchors = true;
+        let html = br##"<html><head></head><body><h2 id="My Section">S</h2><a href="#My Section">go</a></body></html>"##;
+        let out = process_html(
+            Path::new("/content/index.typ"),
+            Path::new("/out/index.html"),
+            html,
+            &config,
+            0,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.contains(r##"<h2 id="My Section">S<a class="heading-anchor" href="#My Section">"##),
+            "{out}"
+        );
+        assert!(out.contains(r##"<a href="#My Section">go</a>"##), "{out}");
+    }
+
+    #[test]
+    fn test_dedupe_identical_svgs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let output = dir.path().join("public");
+        std::fs::create_dir_all(&output).unwrap();
+        let logo = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10pt" height="10pt" viewBox="0 0 10 10"><rect width="10" height="10"/></svg>"#;
+        let other = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20pt" height="20pt" viewBox="0 0 20 20"><circle r="5"/></svg>"#;
+        let html = format!("<html><head></head><body>{logo}{other}{logo}</body></html>");
+
+        let mut config = SiteConfig::default();
+        config.build.output = output.clone();
+        config.build.typst.svg.extract_type = ExtractSvgType::JustSvg;
+        let svg_files = |dedupe| {
+            let mut config = config.clone();
+            config.build.typst.svg.dedupe = dedupe;
+            for entry in std::fs::read_dir(&output).unwrap() {
+                std::fs::remove_file(entry.unwrap().path()).unwrap();
+            }
+            let html_path = output.join("index.html");
+            let out = process_html(
+                Path::new("/content/index.typ"),
+                &html_path,
+                html.as_bytes(),
+                &config,
+                0,
+            )
+            .unwrap();
+            let mut files: Vec<_> = std::fs::read_dir(&output)
+                .unwrap()
+                .map(|e| e.unwrap().file_name().into_string().unwrap())
+                .collect();
+            files.sort();
+            (String::from_utf8(out).unwrap(), files)
+        };
+
+        let (_, files) = svg_files(false);
+        assert_eq!(files, ["svg-0.svg", "svg-1.svg", "svg-2.svg"]);
+
+        let (out, files) = svg_files(true);
+        assert_eq!(files, ["svg-0.svg", "svg-1.svg"]);
+        assert_eq!(out.matches(r#"src="/svg-0.svg""#).count(), 2, "{out}");
+        assert_eq!(out.matches(r#"src="/svg-1.svg""#).count(), 1, "{out}");
+    }
+
+    #[test]
+    fn test_svg_indices_per_page_in_source_order() {
+        use rayon::prelude::*;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let output = dir.path().join("public");
+        let rect = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10pt" height="10pt" viewBox="0 0 10 10"><rect width="10" height="10"/></svg>"#;
+        let circle = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20pt" height="20pt" viewBox="0 0 20 20"><circle r="5"/></svg>"#;
+        let pages = [("a", [rect, circle]), ("b", [circle, rect])];
+
+        let mut config = SiteConfig::default();
+        config.build.output = output.clone();
+        config.build.typst.svg.extract_type = ExtractSvgType::JustSvg;
+
+        for _ in 0..4 {
+            pages.par_iter().for_each(|(name, [first, second])| {
+                let html_path = output.join(name).join("index.html");
+                std::fs::create_dir_all(html_path.parent().unwrap()).unwrap();
+                let html = format!("<html><head></head><body>{first}{second}</body></html>");
+                let source = format!("/content/{name}.typ");
+                let out = process_html(Path::new(&source), &html_path, html.as_bytes(), &config, 0)
+                    .unwrap();
+                let out = String::from_utf8(out).unwrap();
+                let (svg0, svg1) = (format!("/{name}/svg-0.svg"), format!("/{name}/svg-1.svg"));
+                assert!(out.find(&svg0).unwrap() < out.find(&svg1).unwrap(), "{out}");
+            });
+
+            // Each page numbers its own SVGs from 0, in document order
+            let read = |path: &str| std::fs::read(output.join(path)).unwrap();
+            assert_eq!(read("a/svg-0.svg"), read("b/svg-1.svg"));
+            assert_eq!(read("a/svg-1.svg"), read("b/svg-0.svg"));
+            assert_ne!(read("a/svg-0.svg"), read("a/svg-1.svg"));
+        }
+    }
+
+    #[test]
+    fn test_small_svg_stays_inline() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = SiteConfig::default();
+        config.build.output = dir.path().to_path_buf();
+        config.build.typst.svg.extract_type = ExtractSvgType::JustSvg;
+        config.build.typst.svg.inline = true;
+        let html = br#"<html><head></head><body><svg xmlns="http://www.w3.org/2000/svg" width="10pt" height="10pt" viewBox="0 0 10 10"><rect width="10" height="10"/></svg></body></html>"#;
+
+        let out = process_html(
+            &dir.path().join("index.typ"),
+            &dir.path().join("index.html"),
+            html,
+            &config,
+            0,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("<svg") && out.contains("<path"), "{out}");
+        assert!(!out.contains("<img"), "{out}");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_more_marker_becomes_anchor() {
+        let config = SiteConfig::default();
+        for marker in ["<tola-more></tola-more>", "<!--more-->"] {
+            let html = format!("<html><head></head><body><p>a</p>{marker}<p>b</p></body></html>");
+            let out = process_html(
+                Path::new("/content/index.typ"),
+                Path::new("/out/index.html"),
+                html.as_bytes(),
+                &config,
+                0,
+            );
+            let out = String::from_utf8(out.unwrap()).unwrap();
+            assert!(
+                out.contains(r#"<p>a</p><span id="more"></span><p>b</p>"#),
+                "{out}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_data_uri_src_not_prefixed() {
+        let mut config = SiteConfig::default();
+        config.build.image_dimensions = true;
+        let src = "data:image/png;base64,iVBORw0KGgo=";
+        let html = format!(r#"<html><head></head><body><img src="{src}"/></body></html>"#);
+
+        for auto_enhance in [false, true] {
+            config.build.css.auto_enhance = auto_enhance;
+            let out = process_html(
+                Path::new("/content/a.typ"),
+                Path::new("/out/a/index.html"),
+                html.as_bytes(),
+                &config,
+                1,
+            );
+            let out = String::from_utf8(out.unwrap()).unwrap();
+            assert!(out.contains(&format!(r#"src="{src}""#)), "{out}");
+            assert!(!out.contains("../"), "{out}");
+        }
+    }
 }
@@ -0,0 +1,312 @@
+//! Summary extraction from compiled page HTML.
+//!
+//! - "Read more" markers: `#html.elem("tola-more")` or `<!--more-->` split the
+//!   body, making the content before it the summary
+//! - `[build].auto_summary`: first paragraph when the metadata has no `summary`
+
+use quick_xml::Writer;
+use quick_xml::escape::{escape, resolve_predefined_entity};
+use quick_xml::events::{BytesEnd, BytesText, Event};
+
+use super::common::create_xml_reader;
+
+/// HTML elements without an end tag (`<br>`), never left open by [`truncate_html`].
+const VOID_ELEMENTS: &[&[u8]] = &[
+    b"area", b"base", b"br", b"col", b"embed", b"hr", b"img", b"input", b"link", b"meta", b"param",
+    b"source", b"track", b"wbr",
+];
+
+/// Element marking where "read more" begins (`#html.elem("tola-more")`).
+pub const MORE_TAG: &[u8] = b"tola-more";
+
+/// Check if an event is a "read more" marker (`<tola-more>` or `<!--more-->`).
+pub fn is_more_marker(event: &Event<'_>) -> bool {
+    match event {
+        Event::Start(elem) | Event::Empty(elem) => elem.name().as_ref() == MORE_TAG,
+        Event::Comment(text) => text.trim_ascii() == b"more",
+        _ => false,
+    }
+}
+
+/// Body HTML before the "read more" marker, or `None` without one.
+///
+/// Elements still open at the marker are closed (or dropped if empty).
+pub fn split_more(html: &[u8]) -> Option<String> {
+    let mut reader = create_xml_reader(html);
+    let mut writer = Writer::new(Vec::new());
+    // Open elements: (name, output length before and after the start tag)
+    let mut open: Vec<(Vec<u8>, usize, usize)> = Vec::new();
+    let mut in_body = false;
+
+    loop {
+        let event = reader.read_event().ok()?;
+        let len = writer.get_ref().len();
+        match event {
+            Event::Eof => return None,
+            Event::Start(ref elem) if !in_body => in_body = elem.name().as_ref() == b"body",
+            _ if !in_body => {}
+            ref event if is_more_marker(event) => break,
+            Event::End(ref elem) if open.is_empty() && elem.name().as_ref() == b"body" => {
+                return None;
+            }
+            Event::Start(elem) => {
+                let name = elem.name().as_ref().to_vec();
+                writer.write_event(Event::Start(elem)).ok()?;
+                open.push((name, len, writer.get_ref().len()));
+            }
+            Event::End(elem) => {
+                open.pop();
+                writer.write_event(Event::End(elem)).ok()?;
+            }
+            event => writer.write_event(event).ok()?,
+        }
+    }
+
+    for (name, before, after) in open.into_iter().rev() {
+        let buf = writer.get_mut();
+        if buf.len() == after {
+            buf.truncate(before);
+        } else {
+            let name = String::from_utf8_lossy(&name).into_owned();
+            writer.write_event(Event::End(BytesEnd::new(name))).ok()?;
+        }
+    }
+
+    let html = String::from_utf8(writer.into_inner()).ok()?;
+    let html = html.trim();
+    (!html.is_empty()).then(|| html.to_string())
+}
+
+/// Extract the text of the first non-empty `<p>` as an escaped summary.
+///
+/// Tags are stripped and whitespace collapsed. Text longer than `max_chars`
+/// is cut on a word boundary and ends with `…`.
+pub fn extract_summary(html: &[u8], max_chars: usize) -> Option<String> {
+    let mut reader = create_xml_reader(html);
+    let mut in_paragraph = false;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(elem) if elem.name().as_ref() == b"p" => {
+                in_paragraph = true;
+                text.clear();
+            }
+            Event::End(elem) if in_paragraph && elem.name().as_ref() == b"p" => {
+                in_paragraph = false;
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !text.is_empty() {
+                    return Some(escape(truncate_words(&text, max_chars)).into_owned());
+                }
+            }
+            Event::Text(t) if in_paragraph => text.push_str(&t.decode().ok()?),
+            Event::GeneralRef(r) if in_paragraph => {
+                if let Ok(Some(ch)) = r.resolve_char_ref() {
+                    text.push(ch);
+                } else if let Some(s) = resolve_predefined_entity(&r.decode().ok()?) {
+                    text.push_str(s);
+                }
+            }
+            Event::Empty(elem) if in_paragraph && elem.name().as_ref() == b"br" => {
+                text.push(' ');
+            }
+            Event::Eof => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Text content of an HTML fragment, with entities resolved and whitespace
+/// collapsed (e.g. for a summary in plain-text metadata).
+pub fn html_to_text(html: &str) -> String {
+    let mut reader = create_xml_reader(html.as_bytes());
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Text(t)) => text.push_str(&t.decode().unwrap_or_default()),
+            Ok(Event::GeneralRef(r)) => {
+                if let Ok(Some(ch)) = r.resolve_char_ref() {
+                    text.push(ch);
+                } else if let Some(s) = r.decode().ok().and_then(|r| resolve_predefined_entity(&r))
+                {
+                    text.push_str(s);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Truncate an HTML fragment to at most `max_chars` characters of text.
+///
+/// Text is cut on a word boundary and ends with `…`. Tags and entities are
+/// never split (an entity counts as one character), and elements still open
+/// at the cut are closed. Fragments within the limit are returned unchanged.
+pub fn truncate_html(html: &str, max_chars: usize) -> String {
+    let mut reader = create_xml_reader(html.as_bytes());
+    let mut writer = Writer::new(Vec::new());
+    let mut open: Vec<Vec<u8>> = Vec::new();
+    let mut remaining = max_chars;
+
+    loop {
+        let Ok(event) = reader.read_event() else {
+            return html.to_string();
+        };
+        let written = match event {
+            Event::Eof => return html.to_string(),
+            Event::Text(text) => {
+                let Ok(decoded) = text.decode() else {
+                    return html.to_string();
+                };
+                let count = decoded.chars().count();
+                if count > remaining {
+                    let head = truncate_words(&decoded, remaining);
+                    // Text events never contain `<` or `&`, so the cut is
+                    // already escaped
+                    let head = Event::Text(BytesText::from_escaped(head));
+                    if writer.write_event(head).is_err() {
+                        return html.to_string();
+                    }
+                    break;
+                }
+                remaining -= count;
+                writer.write_event(Event::Text(text))
+            }
+            Event::GeneralRef(entity) => {
+                if remaining == 0 {
+                    if writer
+                        .write_event(Event::Text(BytesText::new("…")))
+                        .is_err()
+                    {
+                        return html.to_string();
+                    }
+                    break;
+                }
+                remaining -= 1;
+                writer.write_event(Event::GeneralRef(entity))
+            }
+            Event::Start(elem) => {
+                let name = elem.name().as_ref().to_vec();
+                if !VOID_ELEMENTS.contains(&name.as_slice()) {
+                    open.push(name);
+                }
+                writer.write_event(Event::Start(elem))
+            }
+            Event::End(elem) => {
+                if !VOID_ELEMENTS.contains(&elem.name().as_ref()) {
+                    open.pop();
+                }
+                writer.write_event(Event::End(elem))
+            }
+            event => writer.write_event(event),
+        };
+        if written.is_err() {
+            return html.to_string();
+        }
+    }
+
+    for name in open.into_iter().rev() {
+        let name = String::from_utf8_lossy(&name).into_owned();
+        if writer.write_event(Event::End(BytesEnd::new(name))).is_err() {
+            return html.to_string();
+        }
+    }
+    String::from_utf8(writer.into_inner()).unwrap_or_else(|_| html.to_string())
+}
+
+/// Truncate `text` to at most `max_chars` characters (plus `…`) on a word boundary.
+fn truncate_words(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let cut = text
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(i, _)| i);
+    let head = &text[..cut];
+    // Back off to the last complete word, unless the text is one long word
+    let head = if text[cut..].starts_with(' ') {
+        head
+    } else {
+        head.rfind(' ').map_or(head, |i| &head[..i])
+    };
+    format!("{}…", head.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_first_paragraph() {
+        let html = b"<html><body><h1>Title</h1><p>First <strong>bold</strong>\n paragraph.</p><p>Second.</p></body></html>";
+        assert_eq!(
+            extract_summary(html, 200),
+            Some("First bold paragraph.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_skips_empty_and_escapes() {
+        let html = b"<body><p> </p><p>Fish &amp; chips &lt;3</p></body>";
+        assert_eq!(
+            extract_summary(html, 200),
+            Some("Fish &amp; chips &lt;3".to_string())
+        );
+        assert_eq!(extract_summary(b"<body><h1>Only</h1></body>", 200), None);
+    }
+
+    #[test]
+    fn test_split_more() {
+        let html = b"<html><head><title>T</title></head><body>\n<h2>T</h2>\n<p>Intro <em>here</em>.</p>\n<tola-more></tola-more>\n<p>Rest.</p></body></html>";
+        assert_eq!(
+            split_more(html).as_deref(),
+            Some("<h2>T</h2>\n<p>Intro <em>here</em>.</p>")
+        );
+
+        let html = b"<body><p>Intro</p><!-- more --><p>Rest</p></body>";
+        assert_eq!(split_more(html).as_deref(), Some("<p>Intro</p>"));
+
+        // Open elements are closed, empty ones dropped
+        let html = b"<body><div><p>Intro</p><p><tola-more/></p></div></body>";
+        assert_eq!(split_more(html).as_deref(), Some("<div><p>Intro</p></div>"));
+
+        assert_eq!(split_more(b"<body><p>No marker</p></body>"), None);
+    }
+
+    #[test]
+    fn test_truncate_on_word_boundary() {
+        assert_eq!(truncate_words("hello world again", 13), "hello world…");
+        assert_eq!(truncate_words("hello world again", 11), "hello world…");
+        assert_eq!(truncate_words("hello world", 11), "hello world");
+        assert_eq!(truncate_words("unbreakable", 4), "unbr…");
+    }
+
+    #[test]
+    fn test_truncate_html_keeps_tags_whole() {
+        let html = r#"<p>Read <a href="https://example.com/a?b=1&amp;c=2">the full story</a> about fish &amp; chips.</p>"#;
+        assert_eq!(
+            truncate_html(html, 13),
+            r#"<p>Read <a href="https://example.com/a?b=1&amp;c=2">the full…</a></p>"#
+        );
+        // Entities count as one character and are never split
+        assert_eq!(
+            truncate_html(html, 34),
+            r#"<p>Read <a href="https://example.com/a?b=1&amp;c=2">the full story</a> about fish &amp;…</p>"#
+        );
+        assert_eq!(truncate_html(html, 200), html);
+    }
+
+    #[test]
+    fn test_truncate_html_skips_void_elements() {
+        let html = "<p>Line one<br>line two and <img src=\"a.png\">more text here</p>";
+        assert_eq!(
+            truncate_html(html, 21),
+            "<p>Line one<br>line two and <img src=\"a.png\">…</p>"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! `tola version`: build information for bug reports.
+
+use crate::{
+    config::{ExtractSvgType, SiteConfig},
+    typst_lib,
+};
+
+/// SVG extraction methods compiled into this build.
+const EXTRACT_TYPES: [ExtractSvgType; 5] = [
+    ExtractSvgType::Builtin,
+    ExtractSvgType::Magick,
+    ExtractSvgType::Ffmpeg,
+    ExtractSvgType::JustSvg,
+    ExtractSvgType::Embedded,
+];
+
+/// Version string: just the Tola version, or with `verbose` also the
+/// embedded Typst version and feature defaults.
+pub fn version_info(verbose: bool) -> String {
+    let mut info = format!("tola {}", env!("CARGO_PKG_VERSION"));
+    if !verbose {
+        return info;
+    }
+
+    let defaults = SiteConfig::default();
+    let default_extract = extract_type_name(&defaults.build.typst.svg.extract_type);
+    let extract_types: Vec<String> = EXTRACT_TYPES
+        .iter()
+        .map(extract_type_name)
+        .map(|name| {
+            if name == default_extract {
+                format!("{name} (default)")
+            } else {
+                name
+            }
+        })
+        .collect();
+    let tailwind = if defaults.build.css.tailwind.enable {
+        "enabled"
+    } else {
+        "disabled"
+    };
+
+    info.push_str(&format!(
+        "\ntypst {} (embedded)\ntailwind: {tailwind} by default\nsvg extract types: {}",
+        typst_lib::typst_version(),
+        extract_types.join(", ")
+    ));
+    info
+}
+
+/// Name of an extract type as written in `tola.toml`.
+fn extract_type_name(extract_type: &ExtractSvgType) -> String {
+    serde_json::to_value(extract_type)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_owned))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_info() {
+        assert_eq!(
+            version_info(false),
+            format!("tola {}", env!("CARGO_PKG_VERSION"))
+        );
+
+        let info = version_info(true);
+        assert!(info.starts_with(&format!("tola {}\n", env!("CARGO_PKG_VERSION"))));
+        assert!(info.contains(&format!("typst {} ", typst_lib::typst_version())));
+        assert!(typst_lib::typst_version().starts_with("0."));
+        assert!(info.contains("embedded (default)"), "{info}");
+        assert!(info.contains("tailwind: disabled by default"), "{info}");
+    }
+}
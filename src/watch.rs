@@ -8,6 +8,13 @@
 //! - **This module** (`src/watch.rs`): Event loop, debouncing, rebuild strategy
 //! - **`compiler/watch.rs`**: Actual file compilation via [`process_watched_files`]
 //!
+//! # Watch Modes
+//!
+//! - `native` (default): OS file events via `notify::recommended_watcher`
+//! - `poll` (`[serve].watch_mode = "poll"` / `--watch-poll`): periodic mtime
+//!   scans every `[serve].poll_interval` ms, for NFS and other filesystems that
+//!   don't deliver events. Both feed the same debounce/rebuild loop.
+//!
 //! # Architecture
 //!
 //! ```text
@@ -33,14 +40,21 @@
 //! ```
 
 use crate::{
-    compiler::process_watched_files,
-    config::{SiteConfig, cfg, reload_config},
+    compiler::{
+        collect_page_files, is_partial,
+        meta::{is_companion, is_dir_defaults},
+        process_watched_files,
+    },
+    config::{SiteConfig, WatchMode, cfg, reload_config},
     log,
     logger::WatchStatus,
-    utils::category::{FileCategory, categorize_path},
+    utils::{
+        category::{FileCategory, categorize_path, normalize_path},
+        xml::assets::invalidate_asset_top_levels,
+    },
 };
 use anyhow::{Context, Result};
-use notify::{Event, EventKind, RecursiveMode, Watcher};
+use notify::{Event, EventKind, PollWatcher, RecursiveMode, Watcher};
 use rustc_hash::FxHashSet;
 use std::{
     path::{Path, PathBuf},
@@ -237,6 +251,55 @@ impl Debouncer {
     }
 }
 
+/// Changed files sorted by how they are rebuilt.
+#[derive(Debug, Default)]
+struct Changes<'a> {
+    config_changed: bool,
+    /// Templates, utils and partials: rebuild their dependents
+    dependency_triggers: Vec<&'a PathBuf>,
+    /// Content and assets: rebuild directly
+    incremental_targets: Vec<PathBuf>,
+}
+
+/// Categorize changed files into rebuild strategies.
+fn categorize_changes<'a>(paths: &'a [PathBuf], config: &SiteConfig) -> Changes<'a> {
+    let mut changes = Changes::default();
+
+    for path in paths {
+        match categorize_path(path, config) {
+            FileCategory::Config => changes.config_changed = true,
+            FileCategory::Deps => changes.dependency_triggers.push(path),
+            // Directory defaults: rebuild every page below them
+            FileCategory::Content if is_dir_defaults(path) => {
+                let path = normalize_path(path);
+                let dir = path.parent().unwrap_or(&path);
+                changes.incremental_targets.extend(
+                    collect_page_files(config)
+                        .into_iter()
+                        .filter(|page| page.starts_with(dir)),
+                );
+            }
+            // Content partials: rebuild the pages importing them
+            FileCategory::Content if is_partial(&normalize_path(path), config) => {
+                changes.dependency_triggers.push(path);
+            }
+            // YAML companions: rebuild the page they belong to
+            FileCategory::Content if is_companion(path) => {
+                changes.incremental_targets.push(path.with_extension("typ"));
+            }
+            FileCategory::Asset => {
+                // New or removed top-level entries change link classification
+                invalidate_asset_top_levels();
+                changes.incremental_targets.push(path.clone());
+            }
+            FileCategory::Content => changes.incremental_targets.push(path.clone()),
+            FileCategory::Unknown => {}
+        }
+    }
+
+    changes
+}
+
 /// Process file changes. Returns true if full rebuild succeeded (for cooldown).
 fn handle_changes(paths: &[PathBuf], status: &mut WatchStatus, root: &Path) -> bool {
     if paths.is_empty() {
@@ -252,19 +315,11 @@ fn handle_changes(paths: &[PathBuf], status: &mut WatchStatus, root: &Path) -> b
             .to_string()
     };
 
-    // Categorize changed files
-    let mut config_changed = false;
-    let mut dependency_triggers: Vec<&PathBuf> = Vec::new();
-    let mut incremental_targets: Vec<PathBuf> = Vec::new();
-
-    for path in paths {
-        match categorize_path(path, &c) {
-            FileCategory::Config => config_changed = true,
-            FileCategory::Deps => dependency_triggers.push(path),
-            FileCategory::Content | FileCategory::Asset => incremental_targets.push(path.clone()),
-            FileCategory::Unknown => {}
-        }
-    }
+    let Changes {
+        config_changed,
+        dependency_triggers,
+        mut incremental_targets,
+    } = categorize_changes(paths, &c);
 
     // Config changes: reload config then full rebuild
     if config_changed {
@@ -449,7 +504,25 @@ fn log_watch_summary(config: &SiteConfig) {
     }
 }
 
-fn setup_watchers(watcher: &mut impl Watcher, config: &SiteConfig) -> Result<()> {
+/// Create the watcher selected by `[serve].watch_mode`.
+fn create_watcher(
+    config: &SiteConfig,
+    tx: std::sync::mpsc::Sender<notify::Result<Event>>,
+) -> Result<Box<dyn Watcher>> {
+    Ok(match config.serve.watch_mode {
+        WatchMode::Native => {
+            Box::new(notify::recommended_watcher(tx).context("Failed to create file watcher")?)
+        }
+        WatchMode::Poll => {
+            let interval = Duration::from_millis(config.serve.poll_interval);
+            let poll_config = notify::Config::default().with_poll_interval(interval);
+            log!("watch"; "polling every {}ms", config.serve.poll_interval);
+            Box::new(PollWatcher::new(tx, poll_config).context("Failed to create poll watcher")?)
+        }
+    })
+}
+
+fn setup_watchers(watcher: &mut dyn Watcher, config: &SiteConfig) -> Result<()> {
     for &cat in WATCH_CATEGORIES {
         let mode = if cat.is_directory() {
             RecursiveMode::Recursive
@@ -487,8 +560,8 @@ pub fn watch_for_changes_blocking() -> Result<()> {
     }
 
     let (tx, rx) = std::sync::mpsc::channel();
-    let mut watcher = notify::recommended_watcher(tx).context("Failed to create file watcher")?;
-    setup_watchers(&mut watcher, &c)?;
+    let mut watcher = create_watcher(&c, tx)?;
+    setup_watchers(watcher.as_mut(), &c)?;
 
     let mut debouncer = Debouncer::new();
     let mut content_cache = ContentCache::new();
@@ -525,3 +598,102 @@ pub fn watch_for_changes_blocking() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_poll_watcher_detects_change() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("post.typ");
+        fs::write(&file, "= Old").unwrap();
+
+        let mut config = SiteConfig::default();
+        config.serve.watch_mode = WatchMode::Poll;
+        config.serve.poll_interval = 20;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = create_watcher(&config, tx).unwrap();
+        watcher.watch(dir.path(), RecursiveMode::Recursive).unwrap();
+
+        // Poll compares mtimes at one-second resolution
+        std::thread::sleep(Duration::from_millis(1100));
+        fs::write(&file, "= New title").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            let event = rx.recv_timeout(remaining).unwrap().unwrap();
+            if is_relevant(&event) && event.paths.contains(&file) {
+                return;
+            }
+        }
+        panic!("poll watcher did not report change to {}", file.display());
+    }
+
+    #[test]
+    fn test_companion_change_rebuilds_its_page() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().canonicalize().unwrap().join("content");
+        fs::create_dir_all(&content).unwrap();
+        fs::write(content.join("post.typ"), "= Post").unwrap();
+        fs::write(content.join("post.yaml"), "title: Post").unwrap();
+
+        let mut config = SiteConfig::default();
+        config.build.content = content.clone();
+
+        let paths = [content.join("post.yaml")];
+        let changes = categorize_changes(&paths, &config);
+        assert_eq!(changes.incremental_targets, [content.join("post.typ")]);
+    }
+
+    #[test]
+    fn test_dir_defaults_change_rebuilds_pages_below() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().canonicalize().unwrap().join("content");
+        fs::create_dir_all(content.join("posts/2024")).unwrap();
+        fs::write(content.join("about.typ"), "= About").unwrap();
+        fs::write(content.join("posts/first.typ"), "= First").unwrap();
+        fs::write(content.join("posts/2024/second.typ"), "= Second").unwrap();
+        fs::write(content.join("posts/_defaults.toml"), "draft = true").unwrap();
+
+        let mut config = SiteConfig::default();
+        config.build.content = content.clone();
+
+        let paths = [content.join("posts/_defaults.toml")];
+        let mut targets = categorize_changes(&paths, &config).incremental_targets;
+        targets.sort();
+        assert_eq!(
+            targets,
+            [
+                content.join("posts/2024/second.typ"),
+                content.join("posts/first.typ")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_asset_change_invalidates_top_levels() {
+        use crate::utils::xml::assets::is_asset_link;
+
+        let dir = TempDir::new().unwrap();
+        let assets = dir.path().canonicalize().unwrap().join("assets");
+        fs::create_dir_all(assets.join("css")).unwrap();
+
+        let mut config = SiteConfig::default();
+        config.build.assets = assets.clone();
+        assert!(is_asset_link("/css/site.css", &config));
+
+        // The listing is cached until the watcher sees an asset change
+        fs::create_dir_all(assets.join("js")).unwrap();
+        fs::write(assets.join("js/app.js"), "").unwrap();
+        assert!(!is_asset_link("/js/app.js", &config));
+
+        let paths = [assets.join("js/app.js")];
+        let changes = categorize_changes(&paths, &config);
+        assert_eq!(changes.incremental_targets, paths);
+        assert!(is_asset_link("/js/app.js", &config));
+    }
+}